@@ -19,7 +19,11 @@ for key, value in json.loads(sys.argv[1])['value'].items():
 " "$ELF_PARAMS")
 
 
-py.test -n 6 tests/
+# The created operator registry rejects unregistered operators, overridden parameters change
+# every instruction and deposit rates are created once, run them after the parallel tests
+py.test -n 6 tests/ --ignore=tests/test_operator_registry.py --ignore=tests/test_parameters.py \
+  --ignore=tests/test_deposit_rates.py
+py.test tests/test_deposit_rates.py tests/test_parameters.py tests/test_operator_registry.py
 
 echo "Deploy test success"
 exit 0
@@ -1,7 +1,9 @@
 use crate::NeonApiState;
 use neon_lib::rpc::CallDbClient;
 use neon_lib::{rpc, NeonError};
+use std::future::Future;
 use std::sync::Arc;
+use tokio::runtime::Handle;
 
 pub async fn build_rpc_client(
     state: &NeonApiState,
@@ -24,3 +26,15 @@ pub async fn build_call_db_client(
         CallDbClient::new(state.tracer_db.clone(), slot, tx_index_in_block).await?,
     ))
 }
+
+/// Run the emulation on the multi-threaded emulator runtime.
+pub async fn run_emulation<Fut, T>(emulator: &Handle, emulation: Fut) -> Result<T, NeonError>
+where
+    Fut: Future<Output = Result<T, NeonError>> + Send + 'static,
+    T: Send + 'static,
+{
+    emulator
+        .spawn(emulation)
+        .await
+        .map_err(|e| NeonError::Panic(e.to_string()))?
+}
@@ -0,0 +1,61 @@
+use actix_request_identifier::RequestId;
+use actix_web::{http::StatusCode, post, web::Json, Responder};
+use std::convert::Into;
+
+use crate::api_server::handlers::process_error;
+use crate::{
+    api_context, commands::create_access_list as CreateAccessListCommand, context::Context,
+    types::request_models::EmulateRequestModel, NeonApiState,
+};
+
+use super::{parse_emulation_params, process_result};
+
+#[tracing::instrument(skip(state, request_id), fields(id = request_id.as_str()))]
+#[post("/create-access-list")]
+pub async fn create_access_list(
+    state: NeonApiState,
+    request_id: RequestId,
+    Json(emulate_request): Json<EmulateRequestModel>,
+) -> impl Responder {
+    let tx = emulate_request.tx_params.into();
+
+    let rpc_client = match api_context::build_rpc_client(
+        &state,
+        emulate_request.slot,
+        emulate_request.tx_index_in_block,
+    )
+    .await
+    {
+        Ok(rpc_client) => rpc_client,
+        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+    };
+
+    let emulator = state.emulator().clone();
+    let emulation = async move {
+        let context = Context::new(&*rpc_client, &state.config);
+
+        let (token, chain, default_chain, steps, accounts, solana_accounts) =
+            parse_emulation_params(&state.config, &context, &emulate_request.emulation_params)
+                .await?;
+
+        CreateAccessListCommand::execute(
+            context.rpc_client,
+            state.config.evm_loader,
+            tx,
+            token,
+            chain,
+            default_chain,
+            steps,
+            state.config.commitment,
+            &accounts,
+            &solana_accounts,
+        )
+        .await
+    };
+
+    process_result(
+        &api_context::run_emulation(&emulator, emulation)
+            .await
+            .map_err(Into::into),
+    )
+}
@@ -30,26 +30,36 @@ pub async fn emulate(
         Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
     };
 
-    let context = Context::new(&*rpc_client, &state.config);
+    let emulator = state.emulator().clone();
+    let emulation = async move {
+        let context = Context::new(&*rpc_client, &state.config);
 
-    let (token, chain, steps, accounts, solana_accounts) =
-        parse_emulation_params(&state.config, &context, &emulate_request.emulation_params).await;
+        let (token, chain, default_chain, steps, accounts, solana_accounts) =
+            parse_emulation_params(&state.config, &context, &emulate_request.emulation_params)
+                .await?;
 
-    process_result(
-        &EmulateCommand::execute(
+        EmulateCommand::execute(
             context.rpc_client,
             state.config.evm_loader,
             tx,
             token,
             chain,
+            default_chain,
             steps,
             state.config.commitment,
             &accounts,
             &solana_accounts,
             &None,
             None,
+            None,
+            emulate_request.partial_on_step_limit,
         )
         .await
-        .map_err(Into::into),
+    };
+
+    process_result(
+        &api_context::run_emulation(&emulator, emulation)
+            .await
+            .map_err(Into::into),
     )
 }
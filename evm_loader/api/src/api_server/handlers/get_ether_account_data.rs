@@ -1,5 +1,6 @@
 use crate::api_server::handlers::process_error;
 use crate::commands::get_ether_account_data as GetEtherAccountDataCommand;
+use crate::commands::get_neon_elf::CachedElfParams;
 use crate::{api_context, context::Context, types::request_models::GetEtherRequest, NeonApiState};
 use actix_request_identifier::RequestId;
 use actix_web::{get, http::StatusCode, web::Query, Responder};
@@ -21,11 +22,20 @@ pub async fn get_ether_account_data(
 
     let context = Context::new(&*rpc_client, &state.config);
 
+    let cached_elf_params = CachedElfParams::new(&state.config, &context).await;
+    let default_chain_id = cached_elf_params.default_chain_id();
+    let chain_id = match cached_elf_params.resolve_chain_id(req_params.chain_id) {
+        Ok(chain_id) => chain_id,
+        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+    };
+
     process_result(
         &GetEtherAccountDataCommand::execute(
             context.rpc_client,
             &state.config.evm_loader,
             &req_params.ether,
+            chain_id,
+            default_chain_id,
         )
         .await
         .map_err(Into::into),
@@ -1,4 +1,5 @@
 use crate::api_server::handlers::process_error;
+use crate::commands::get_neon_elf::CachedElfParams;
 use crate::{
     api_context, context::Context, types::request_models::GetStorageAtRequest, NeonApiState,
 };
@@ -24,12 +25,21 @@ pub async fn get_storage_at(
 
     let context = Context::new(&*rpc_client, &state.config);
 
+    let cached_elf_params = CachedElfParams::new(&state.config, &context).await;
+    let default_chain_id = cached_elf_params.default_chain_id();
+    let chain_id = match cached_elf_params.resolve_chain_id(req_params.chain_id) {
+        Ok(chain_id) => chain_id,
+        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+    };
+
     process_result(
         &GetStorageAtCommand::execute(
             context.rpc_client,
             &state.config.evm_loader,
             req_params.contract_id,
             &req_params.index,
+            chain_id,
+            default_chain_id,
         )
         .await
         .map_err(Into::into),
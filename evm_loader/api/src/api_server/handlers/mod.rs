@@ -11,10 +11,10 @@ use crate::{Config, Context, NeonApiResult};
 
 use crate::types::request_models::EmulationParamsRequestModel;
 use std::net::AddrParseError;
-use std::str::FromStr;
 use tracing::error;
 
 pub mod build_info;
+pub mod create_access_list;
 pub mod emulate;
 pub mod get_ether_account_data;
 pub mod get_storage_at;
@@ -47,39 +47,21 @@ impl From<AddrParseError> for NeonApiError {
     }
 }
 
+/// Token mint, chain id, default chain id, steps limit, cached ethereum and solana accounts
 pub(crate) async fn parse_emulation_params(
     config: &Config,
     context: &Context<'_>,
     params: &EmulationParamsRequestModel,
-) -> (Pubkey, u64, u64, Vec<Address>, Vec<Pubkey>) {
-    // Read ELF params only if token_mint or chain_id is not set.
-    let mut token: Option<Pubkey> = params.token_mint.map(Into::into);
-    let mut chain = params.chain_id;
-    if token.is_none() || chain.is_none() {
-        let cached_elf_params = CachedElfParams::new(config, context).await;
-        token = token.or_else(|| {
-            Some(
-                Pubkey::from_str(
-                    cached_elf_params
-                        .get("NEON_TOKEN_MINT")
-                        .expect("NEON_TOKEN_MINT load error"),
-                )
-                .expect("NEON_TOKEN_MINT Pubkey ctor error "),
-            )
-        });
-        chain = chain.or_else(|| {
-            Some(
-                u64::from_str(
-                    cached_elf_params
-                        .get("NEON_CHAIN_ID")
-                        .expect("NEON_CHAIN_ID load error"),
-                )
-                .expect("NEON_CHAIN_ID u64 ctor error"),
-            )
-        });
-    }
-    let token = token.expect("token_mint get error");
-    let chain = chain.expect("chain_id get error");
+) -> Result<(Pubkey, u64, u64, u64, Vec<Address>, Vec<Pubkey>), NeonError> {
+    let cached_elf_params = CachedElfParams::new(config, context).await;
+    let default_chain = cached_elf_params.default_chain_id();
+    let chain = cached_elf_params.resolve_chain_id(params.chain_id)?;
+    let token = match params.token_mint {
+        Some(token) => token.into(),
+        None => cached_elf_params
+            .token_mint(chain)
+            .ok_or(NeonError::UnknownChain(chain))?,
+    };
     let max_steps = params.max_steps_to_execute;
 
     let accounts = params.cached_accounts.clone().unwrap_or_default();
@@ -90,7 +72,14 @@ pub(crate) async fn parse_emulation_params(
         .map(|vec| vec.into_iter().map(Into::into).collect())
         .unwrap_or_default();
 
-    (token, chain, max_steps, accounts, solana_accounts)
+    Ok((
+        token,
+        chain,
+        default_chain,
+        max_steps,
+        accounts,
+        solana_accounts,
+    ))
 }
 
 fn process_result<T: Serialize>(
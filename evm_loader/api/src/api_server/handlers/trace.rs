@@ -30,29 +30,38 @@ pub async fn trace(
         Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
     };
 
-    let context = Context::new(&*rpc_client, &state.config);
+    let emulator = state.emulator().clone();
+    let emulation = async move {
+        let context = Context::new(&*rpc_client, &state.config);
 
-    let (token, chain, steps, accounts, solana_accounts) = parse_emulation_params(
-        &state.config,
-        &context,
-        &trace_request.emulate_request.emulation_params,
-    )
-    .await;
+        let (token, chain, default_chain, steps, accounts, solana_accounts) =
+            parse_emulation_params(
+                &state.config,
+                &context,
+                &trace_request.emulate_request.emulation_params,
+            )
+            .await?;
 
-    process_result(
-        &trace_transaction(
+        trace_transaction(
             context.rpc_client,
             state.config.evm_loader,
             tx,
             token,
             chain,
+            default_chain,
             steps,
             state.config.commitment,
             &accounts,
             &solana_accounts,
             trace_request.trace_call_config.unwrap_or_default(),
+            None,
         )
         .await
-        .map_err(Into::into),
+    };
+
+    process_result(
+        &api_context::run_emulation(&emulator, emulation)
+            .await
+            .map_err(Into::into),
     )
 }
@@ -1,12 +1,15 @@
 use crate::Config;
 use neon_lib::types::TracerDb;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use std::sync::Arc;
+use std::{env, num::NonZeroUsize, sync::Arc};
+use tokio::runtime::{Builder, Handle, Runtime};
 
 pub struct State {
     pub tracer_db: TracerDb,
     pub rpc_client: Arc<RpcClient>,
     pub config: Config,
+    /// Emulations run on all cores, actix workers only serve the requests
+    emulator: Option<Runtime>,
 }
 
 impl State {
@@ -19,6 +22,38 @@ impl State {
                 config.commitment,
             )),
             config,
+            emulator: Some(
+                Builder::new_multi_thread()
+                    .worker_threads(emulator_threads())
+                    .thread_name("neon-emulator")
+                    .enable_all()
+                    .build()
+                    .expect("failed to build the emulator runtime"),
+            ),
         }
     }
+
+    pub fn emulator(&self) -> &Handle {
+        self.emulator
+            .as_ref()
+            .expect("emulator runtime is shut down")
+            .handle()
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        // State is dropped inside the actix runtime, where a runtime can't be dropped in place
+        if let Some(emulator) = self.emulator.take() {
+            emulator.shutdown_background();
+        }
+    }
+}
+
+fn emulator_threads() -> usize {
+    env::var("NEON_API_EMULATOR_THREADS")
+        .ok()
+        .and_then(|threads| threads.parse().ok())
+        .filter(|threads| *threads > 0)
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, NonZeroUsize::get))
 }
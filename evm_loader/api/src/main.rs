@@ -22,6 +22,7 @@ use actix_web::web::Data;
 use std::{env, net::SocketAddr, str::FromStr};
 
 use crate::api_server::handlers::build_info::build_info_route;
+use crate::api_server::handlers::create_access_list::create_access_list;
 use crate::api_server::handlers::emulate::emulate;
 use crate::api_server::handlers::get_ether_account_data::get_ether_account_data;
 use crate::api_server::handlers::get_storage_at::get_storage_at;
@@ -68,6 +69,7 @@ async fn main() -> NeonApiResult<()> {
             web::scope("/api")
                 .app_data(state.clone())
                 .service(build_info_route)
+                .service(create_access_list)
                 .service(emulate)
                 .service(get_ether_account_data)
                 .service(get_storage_at)
@@ -9,9 +9,10 @@ mod program_options;
 
 use neon_lib::{
     commands::{
-        cancel_trx, collect_treasury, create_ether_account, deposit, emulate,
-        get_ether_account_data, get_neon_elf, get_neon_elf::CachedElfParams, get_storage_at,
-        init_environment, trace,
+        cancel_trx, collect_storage, collect_treasury, create_access_list, create_ether_account,
+        deposit, deposit_rates, deposit_spl, emulate, emulate_iterative, get_account_versions,
+        get_ether_account_data, get_holder, get_neon_elf, get_neon_elf::CachedElfParams,
+        get_storage_at, init_environment, operator_registry, parameters, trace, treasury_status,
     },
     errors, rpc,
     types::{self, AccessListItem},
@@ -21,6 +22,7 @@ use neon_lib::{
 use clap::ArgMatches;
 pub use config::Config;
 use std::io::Read;
+use std::path::Path;
 
 use ethnum::U256;
 use evm_loader::evm::tracing::TraceCallConfig;
@@ -29,11 +31,10 @@ use serde_json::json;
 use solana_clap_utils::input_parsers::{pubkey_of, value_of, values_of};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
 use tokio::time::Instant;
 
 pub use neon_lib::context::*;
-use neon_lib::rpc::CallDbClient;
+use neon_lib::rpc::{CallDbClient, SnapshotClient};
 
 use crate::build_info::get_build_info;
 use crate::{
@@ -54,7 +55,9 @@ async fn run<'a>(options: &'a ArgMatches<'a>) -> NeonCliResult {
 
     let (cmd, params) = options.subcommand();
 
-    let rpc_client: Box<dyn rpc::Rpc> = if let Some(slot) = slot {
+    let rpc_client: Box<dyn rpc::Rpc> = if let Some(path) = options.value_of("snapshot") {
+        Box::new(SnapshotClient::load(Path::new(path))?)
+    } else if let Some(slot) = slot {
         Box::new(
             CallDbClient::new(
                 TracerDb::new(config.db_config.as_ref().expect("db-config not found")),
@@ -131,39 +134,86 @@ async fn execute<'a>(
     match (cmd, params) {
         ("emulate", Some(params)) => {
             let (tx, trace_call_config) = parse_tx(params);
-            let (token, chain, steps, accounts, solana_accounts) =
-                parse_tx_params(config, context, params).await;
+            let (token, chain, default_chain, steps, accounts, solana_accounts) =
+                parse_tx_params(config, context, params).await?;
             emulate::execute(
                 context.rpc_client,
                 config.evm_loader,
                 tx,
                 token,
                 chain,
+                default_chain,
                 steps,
                 config.commitment,
                 &accounts,
                 &solana_accounts,
                 &trace_call_config.block_overrides,
                 trace_call_config.state_overrides,
+                params.value_of("export_snapshot").map(Path::new),
+                params.is_present("partial_on_step_limit"),
+            )
+            .await
+            .map(|result| json!(result))
+        }
+        ("emulate-iterative", Some(params)) => {
+            let (tx, _) = parse_tx(params);
+            let (token, chain, default_chain, steps, accounts, solana_accounts) =
+                parse_tx_params(config, context, params).await?;
+            let holder_size =
+                value_of(params, "holder_size").unwrap_or(emulate_iterative::DEFAULT_HOLDER_SIZE);
+            emulate_iterative::execute(
+                context.rpc_client,
+                config.evm_loader,
+                tx,
+                token,
+                chain,
+                default_chain,
+                steps,
+                config.commitment,
+                &accounts,
+                &solana_accounts,
+                value_of(params, "step_size"),
+                holder_size,
+            )
+            .await
+            .map(|result| json!(result))
+        }
+        ("create-access-list", Some(params)) => {
+            let (tx, _) = parse_tx(params);
+            let (token, chain, default_chain, steps, accounts, solana_accounts) =
+                parse_tx_params(config, context, params).await?;
+            create_access_list::execute(
+                context.rpc_client,
+                config.evm_loader,
+                tx,
+                token,
+                chain,
+                default_chain,
+                steps,
+                config.commitment,
+                &accounts,
+                &solana_accounts,
             )
             .await
             .map(|result| json!(result))
         }
         ("trace", Some(params)) => {
             let (tx, trace_call_config) = parse_tx(params);
-            let (token, chain, steps, accounts, solana_accounts) =
-                parse_tx_params(config, context, params).await;
+            let (token, chain, default_chain, steps, accounts, solana_accounts) =
+                parse_tx_params(config, context, params).await?;
             trace::trace_transaction(
                 context.rpc_client,
                 config.evm_loader,
                 tx,
                 token,
                 chain,
+                default_chain,
                 steps,
                 config.commitment,
                 &accounts,
                 &solana_accounts,
                 trace_call_config,
+                params.value_of("export_snapshot").map(Path::new),
             )
             .await
             .map(|trace| json!(trace))
@@ -175,11 +225,14 @@ async fn execute<'a>(
                 .as_any()
                 .downcast_ref::<RpcClient>()
                 .expect("cast to solana_client::nonblocking::rpc_client::RpcClient error");
+            let (chain_id, default_chain_id) = chain_id_of(config, context, params).await?;
             create_ether_account::execute(
                 rpc_client,
                 config.evm_loader,
                 context.signer()?.as_ref(),
                 &ether,
+                chain_id,
+                default_chain_id,
             )
             .await
             .map(|result| json!(result))
@@ -192,22 +245,46 @@ async fn execute<'a>(
                 .expect("cast to solana_client::nonblocking::rpc_client::RpcClient error");
             let amount = value_of(params, "amount").expect("amount parse error");
             let ether = address_of(params, "ether").expect("ether parse error");
+            let elf_params = CachedElfParams::new(config, context).await;
+            let chain_id = elf_params.resolve_chain_id(value_of(params, "chain_id"))?;
+            let token_mint = elf_params
+                .token_mint(chain_id)
+                .ok_or(NeonError::UnknownChain(chain_id))?;
             deposit::execute(
                 rpc_client,
                 config.evm_loader,
                 context.signer()?.as_ref(),
                 amount,
                 &ether,
+                chain_id,
+                elf_params.default_chain_id(),
+                token_mint,
             )
             .await
             .map(|result| json!(result))
         }
-        ("get-ether-account-data", Some(params)) => {
+        ("deposit-spl", Some(params)) => {
+            let mint = pubkey_of(params, "mint").expect("mint parse error");
+            let amount = value_of(params, "amount").expect("amount parse error");
             let ether = address_of(params, "ether").expect("ether parse error");
-            get_ether_account_data::execute(context.rpc_client, &config.evm_loader, &ether)
+            let chain_id = value_of(params, "chain_id");
+            deposit_spl::execute(config, context, &mint, amount, &ether, chain_id)
                 .await
                 .map(|result| json!(result))
         }
+        ("get-ether-account-data", Some(params)) => {
+            let ether = address_of(params, "ether").expect("ether parse error");
+            let (chain_id, default_chain_id) = chain_id_of(config, context, params).await?;
+            get_ether_account_data::execute(
+                context.rpc_client,
+                &config.evm_loader,
+                &ether,
+                chain_id,
+                default_chain_id,
+            )
+            .await
+            .map(|result| json!(result))
+        }
         ("cancel-trx", Some(params)) => {
             let storage_account =
                 pubkey_of(params, "storage_account").expect("storage_account parse error");
@@ -226,7 +303,87 @@ async fn execute<'a>(
                 .await
                 .map(|result| json!(result))
         }
-        ("collect-treasury", Some(_)) => collect_treasury::execute(config, context)
+        ("collect-treasury", Some(params)) => {
+            let chain_id = value_of(params, "chain_id");
+            collect_treasury::execute(config, context, chain_id)
+                .await
+                .map(|result| json!(result))
+        }
+        ("treasury-status", Some(params)) => {
+            let chain_id = value_of(params, "chain_id");
+            treasury_status::execute(config, context, chain_id)
+                .await
+                .map(|result| json!(result))
+        }
+        ("collect-storage", Some(_)) => collect_storage::execute(config, context)
+            .await
+            .map(|result| json!(result)),
+        ("get-account-versions", Some(_)) => get_account_versions::execute(config, context)
+            .await
+            .map(|result| json!(result)),
+        ("get-holder", Some(params)) => {
+            let holder = pubkey_of(params, "holder_account").expect("holder_account parse error");
+            get_holder::execute(config, context, &holder)
+                .await
+                .map(|result| json!(result))
+        }
+        ("init-operator-registry", Some(params)) => {
+            let authority = pubkey_of(params, "authority").expect("authority parse error");
+            operator_registry::init(config, context, &authority)
+                .await
+                .map(|result| json!(result))
+        }
+        ("add-operator", Some(params)) => {
+            let operator = pubkey_of(params, "operator").expect("operator parse error");
+            operator_registry::add(config, context, &operator)
+                .await
+                .map(|result| json!(result))
+        }
+        ("remove-operator", Some(params)) => {
+            let operator = pubkey_of(params, "operator").expect("operator parse error");
+            operator_registry::remove(config, context, &operator)
+                .await
+                .map(|result| json!(result))
+        }
+        ("suspend-operator", Some(params)) => {
+            let operator = pubkey_of(params, "operator").expect("operator parse error");
+            let suspend = !params.is_present("resume");
+            operator_registry::suspend(config, context, &operator, suspend)
+                .await
+                .map(|result| json!(result))
+        }
+        ("get-operators", Some(_)) => operator_registry::get_operators(config, context)
+            .await
+            .map(|result| json!(result)),
+        ("init-parameters", Some(params)) => {
+            let authority = pubkey_of(params, "authority").expect("authority parse error");
+            parameters::init(config, context, &authority)
+                .await
+                .map(|result| json!(result))
+        }
+        ("set-parameter", Some(params)) => {
+            let name = params.value_of("name").expect("name parse error");
+            let value = value_of::<u64>(params, "value");
+            parameters::set(config, context, name, value)
+                .await
+                .map(|result| json!(result))
+        }
+        ("init-deposit-rates", Some(params)) => {
+            let authority = pubkey_of(params, "authority").expect("authority parse error");
+            deposit_rates::init(config, context, &authority)
+                .await
+                .map(|result| json!(result))
+        }
+        ("set-deposit-rate", Some(params)) => {
+            let mint = pubkey_of(params, "mint").expect("mint parse error");
+            let numerator = value_of(params, "numerator").expect("numerator parse error");
+            let denominator = value_of(params, "denominator").unwrap_or(1);
+            let chain_id = value_of(params, "chain_id");
+            deposit_rates::set(config, context, &mint, chain_id, numerator, denominator)
+                .await
+                .map(|result| json!(result))
+        }
+        ("get-deposit-rates", Some(_)) => deposit_rates::get_rates(config, context)
             .await
             .map(|result| json!(result)),
         ("init-environment", Some(params)) => {
@@ -241,9 +398,17 @@ async fn execute<'a>(
         ("get-storage-at", Some(params)) => {
             let contract_id = address_of(params, "contract_id").expect("contract_it parse error");
             let index = u256_of(params, "index").expect("index parse error");
-            get_storage_at::execute(context.rpc_client, &config.evm_loader, contract_id, &index)
-                .await
-                .map(|hash| json!(hex::encode(hash.0)))
+            let (chain_id, default_chain_id) = chain_id_of(config, context, params).await?;
+            get_storage_at::execute(
+                context.rpc_client,
+                &config.evm_loader,
+                contract_id,
+                &index,
+                chain_id,
+                default_chain_id,
+            )
+            .await
+            .map(|hash| json!(hex::encode(hash.0)))
         }
         _ => unreachable!(),
     }
@@ -268,6 +433,12 @@ fn parse_tx(params: &ArgMatches) -> (TxParams, TraceCallConfig) {
 
     let gas_limit = u256_of(params, "gas_limit");
 
+    let gas_price = u256_of(params, "gas_price");
+
+    let max_fee_per_gas = u256_of(params, "max_fee_per_gas");
+
+    let max_priority_fee_per_gas = u256_of(params, "max_priority_fee_per_gas");
+
     let access_list = access_list_of(params, "access_list");
 
     let tx_params = TxParams {
@@ -277,52 +448,56 @@ fn parse_tx(params: &ArgMatches) -> (TxParams, TraceCallConfig) {
         data,
         value,
         gas_limit,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
         access_list,
     };
 
     (tx_params, trace_config)
 }
 
+/// Token mint, chain id, default chain id, steps limit, cached ethereum and solana accounts
 pub async fn parse_tx_params<'a>(
     config: &Config,
     context: &Context<'_>,
     params: &'a ArgMatches<'a>,
-) -> (Pubkey, u64, u64, Vec<Address>, Vec<Pubkey>) {
-    // Read ELF params only if token_mint or chain_id is not set.
-    let mut token = pubkey_of(params, "token_mint");
-    let mut chain = value_of(params, "chain_id");
-    if token.is_none() || chain.is_none() {
-        let cached_elf_params = CachedElfParams::new(config, context).await;
-        token = token.or_else(|| {
-            Some(
-                Pubkey::from_str(
-                    cached_elf_params
-                        .get("NEON_TOKEN_MINT")
-                        .expect("NEON_TOKEN_MINT load error"),
-                )
-                .expect("NEON_TOKEN_MINT Pubkey ctor error "),
-            )
-        });
-        chain = chain.or_else(|| {
-            Some(
-                u64::from_str(
-                    cached_elf_params
-                        .get("NEON_CHAIN_ID")
-                        .expect("NEON_CHAIN_ID load error"),
-                )
-                .expect("NEON_CHAIN_ID u64 ctor error"),
-            )
-        });
-    }
-    let token = token.expect("token_mint get error");
-    let chain = chain.expect("chain_id get error");
+) -> Result<(Pubkey, u64, u64, u64, Vec<Address>, Vec<Pubkey>), NeonError> {
+    let cached_elf_params = CachedElfParams::new(config, context).await;
+    let default_chain = cached_elf_params.default_chain_id();
+    let chain = cached_elf_params.resolve_chain_id(value_of(params, "chain_id"))?;
+    let token = match pubkey_of(params, "token_mint") {
+        Some(token) => token,
+        None => cached_elf_params
+            .token_mint(chain)
+            .ok_or(NeonError::UnknownChain(chain))?,
+    };
     let max_steps =
         value_of::<u64>(params, "max_steps_to_execute").expect("max_steps_to_execute parse error");
 
     let accounts = values_of::<Address>(params, "cached_accounts").unwrap_or_default();
     let solana_accounts = values_of::<Pubkey>(params, "solana_accounts").unwrap_or_default();
 
-    (token, chain, max_steps, accounts, solana_accounts)
+    Ok((
+        token,
+        chain,
+        default_chain,
+        max_steps,
+        accounts,
+        solana_accounts,
+    ))
+}
+
+/// Requested chain id, the program default one if omitted, and the default chain id
+async fn chain_id_of(
+    config: &Config,
+    context: &Context<'_>,
+    params: &ArgMatches<'_>,
+) -> Result<(u64, u64), NeonError> {
+    let cached_elf_params = CachedElfParams::new(config, context).await;
+    let chain_id = cached_elf_params.resolve_chain_id(value_of(params, "chain_id"))?;
+
+    Ok((chain_id, cached_elf_params.default_chain_id()))
 }
 
 fn address_or_deploy_of(matches: &ArgMatches<'_>, name: &str) -> Option<Address> {
@@ -139,6 +139,35 @@ fn trx_params<'a, 'b>(cmd: &'static str, desc: &'static str) -> App<'a, 'b> {
                 .validator(is_valid_u256)
                 .help("Gas limit"),
         )
+        .arg(
+            Arg::with_name("gas_price")
+                .long("gas_price")
+                .value_name("GAS_PRICE")
+                .takes_value(true)
+                .required(false)
+                .validator(is_valid_u256)
+                .help("Gas price"),
+        )
+        .arg(
+            Arg::with_name("max_fee_per_gas")
+                .long("max_fee_per_gas")
+                .value_name("MAX_FEE_PER_GAS")
+                .takes_value(true)
+                .required(false)
+                .conflicts_with("gas_price")
+                .validator(is_valid_u256)
+                .help("EIP-1559 max fee per gas"),
+        )
+        .arg(
+            Arg::with_name("max_priority_fee_per_gas")
+                .long("max_priority_fee_per_gas")
+                .value_name("MAX_PRIORITY_FEE_PER_GAS")
+                .takes_value(true)
+                .required(false)
+                .requires("max_fee_per_gas")
+                .validator(is_valid_u256)
+                .help("EIP-1559 max priority fee per gas"),
+        )
         .arg(
             Arg::with_name("access_list")
                 .long("access-list")
@@ -167,6 +196,14 @@ fn trx_params<'a, 'b>(cmd: &'static str, desc: &'static str) -> App<'a, 'b> {
                 .validator(is_valid_address)
                 .help("List of cached solana account pubkeys"),
         )
+        .arg(
+            Arg::with_name("export_snapshot")
+                .long("export-snapshot")
+                .value_name("PATH")
+                .takes_value(true)
+                .required(false)
+                .help("Save accounts touched by the emulation to the snapshot file, JSON if PATH ends with .json, bincode otherwise"),
+        )
 }
 
 #[allow(clippy::too_many_lines)]
@@ -208,6 +245,15 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                 .validator(is_amount::<u64, _>)
                 .help("Slot number to work with archived data"),
         )
+        .arg(
+            Arg::with_name("snapshot")
+                .long("snapshot")
+                .value_name("PATH")
+                .takes_value(true)
+                .required(false)
+                .conflicts_with("slot")
+                .help("Snapshot file to work offline with saved accounts, see --export-snapshot"),
+        )
         .arg(
             Arg::with_name("verbose")
                 .short("v")
@@ -284,6 +330,13 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                 "emulate",
                 "Emulation transaction. Additional `TransactionParams` can be provided via STDIN as a JSON object.",
             )
+            .arg(
+                Arg::with_name("partial_on_step_limit")
+                    .long("partial-on-step-limit")
+                    .takes_value(false)
+                    .required(false)
+                    .help("Return the partial result instead of the error when max_steps_to_execute is reached"),
+            )
         )
         .subcommand(
             trx_params(
@@ -291,10 +344,41 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                 "Emulation transaction to collecting traces. Additional `TransactionParams` can be provided via STDIN as a JSON object.",
             )
         )
+        .subcommand(
+            trx_params(
+                "emulate-iterative",
+                "Emulation transaction in iterations, serializing the EVM state between them as the program does. Additional `TransactionParams` can be provided via STDIN as a JSON object.",
+            )
+            .arg(
+                Arg::with_name("step_size")
+                    .long("step-size")
+                    .value_name("STEPS")
+                    .takes_value(true)
+                    .required(false)
+                    .validator(is_amount::<u64, _>)
+                    .help("EVM steps per iteration, EVM_STEPS_MIN by default"),
+            )
+            .arg(
+                Arg::with_name("holder_size")
+                    .long("holder-size")
+                    .value_name("BYTES")
+                    .takes_value(true)
+                    .required(false)
+                    .validator(is_amount::<usize, _>)
+                    .help("Holder account size to check the serialized EVM state against"),
+            )
+        )
+        .subcommand(
+            trx_params(
+                "create-access-list",
+                "Emulation transaction to build EIP-2930 access list of the touched addresses and storage slots. Additional `TransactionParams` can be provided via STDIN as a JSON object.",
+            )
+        )
         .subcommand(
             SubCommand::with_name("create-ether-account")
                 .about("Create ethereum account")
                 .arg(ether_arg(1))
+                .arg(chain_id_arg())
         )
         .subcommand(
             SubCommand::with_name("deposit")
@@ -309,11 +393,37 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                         .help("Amount to deposit"),
                 )
                 .arg(ether_arg(2))
+                .arg(chain_id_arg())
+        )
+        .subcommand(
+            SubCommand::with_name("deposit-spl")
+                .about("Deposit SPL tokens or wrapped SOL to ether account at the on-chain conversion rate")
+                .arg(
+                    Arg::with_name("mint")
+                        .index(1)
+                        .value_name("MINT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("mint of the deposited token"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .index(2)
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_amount::<u64, _>)
+                        .help("Amount to deposit in the token base units"),
+                )
+                .arg(ether_arg(3))
+                .arg(chain_id_arg())
         )
         .subcommand(
             SubCommand::with_name("get-ether-account-data")
                 .about("Get values stored in associated with given address account data")
                 .arg(ether_arg(1))
+                .arg(chain_id_arg())
         )
         .subcommand(
             SubCommand::with_name("cancel-trx")
@@ -328,6 +438,164 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                         .help("storage account for transaction"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("get-holder")
+                .about("Show holder account state and written transaction byte ranges")
+                .arg(
+                    Arg::with_name("holder_account")
+                        .index(1)
+                        .value_name("HOLDER_ACCOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("holder account with transaction"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("init-operator-registry")
+                .about("Create on-chain operator registry, signer must be the program upgrade authority")
+                .arg(
+                    Arg::with_name("authority")
+                        .index(1)
+                        .value_name("AUTHORITY")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("key managing the registry"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("add-operator")
+                .about("Add operator to the registry, signer must be the registry authority")
+                .arg(
+                    Arg::with_name("operator")
+                        .index(1)
+                        .value_name("OPERATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("operator key"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("remove-operator")
+                .about("Remove operator from the registry, signer must be the registry authority")
+                .arg(
+                    Arg::with_name("operator")
+                        .index(1)
+                        .value_name("OPERATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("operator key"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("suspend-operator")
+                .about("Suspend registered operator, signer must be the registry authority")
+                .arg(
+                    Arg::with_name("operator")
+                        .index(1)
+                        .value_name("OPERATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("operator key"),
+                )
+                .arg(
+                    Arg::with_name("resume")
+                        .long("resume")
+                        .takes_value(false)
+                        .help("Resume suspended operator"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("get-operators")
+                .about("Get operators from the on-chain registry")
+        )
+        .subcommand(
+            SubCommand::with_name("init-parameters")
+                .about("Create runtime parameters account, signer must be the program upgrade authority")
+                .arg(
+                    Arg::with_name("authority")
+                        .index(1)
+                        .value_name("AUTHORITY")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("key managing the parameters"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("set-parameter")
+                .about("Override runtime parameter, signer must be the parameters authority")
+                .arg(
+                    Arg::with_name("name")
+                        .index(1)
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .required(true)
+                        .help("parameter name, e.g. PAYMENT_TO_TREASURE"),
+                )
+                .arg(
+                    Arg::with_name("value")
+                        .index(2)
+                        .value_name("VALUE")
+                        .takes_value(true)
+                        .required(false)
+                        .validator(is_amount::<u64, _>)
+                        .help("new value, restores compiled default if omitted"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("init-deposit-rates")
+                .about("Create deposit rates account, signer must be the program upgrade authority")
+                .arg(
+                    Arg::with_name("authority")
+                        .index(1)
+                        .value_name("AUTHORITY")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("key managing the deposit rates"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("set-deposit-rate")
+                .about("Set conversion rate of the SPL token deposit, signer must be the deposit rates authority")
+                .arg(
+                    Arg::with_name("mint")
+                        .index(1)
+                        .value_name("MINT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("mint of the accepted token"),
+                )
+                .arg(
+                    Arg::with_name("numerator")
+                        .index(2)
+                        .value_name("NUMERATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_amount::<u64, _>)
+                        .help("gas token base units per DENOMINATOR token base units, 0 removes the mint"),
+                )
+                .arg(
+                    Arg::with_name("denominator")
+                        .index(3)
+                        .value_name("DENOMINATOR")
+                        .takes_value(true)
+                        .required(false)
+                        .validator(is_amount::<u64, _>)
+                        .help("token base units, 1 if omitted"),
+                )
+                .arg(chain_id_arg())
+        )
+        .subcommand(
+            SubCommand::with_name("get-deposit-rates")
+                .about("Get accepted deposit mints and their conversion rates")
+        )
         .subcommand(
             SubCommand::with_name("neon-elf-params")
                 .about("Get NEON values stored in elf")
@@ -343,6 +611,20 @@ pub fn parse<'a>() -> ArgMatches<'a> {
         .subcommand(
             SubCommand::with_name("collect-treasury")
                 .about("Collect lamports from auxiliary treasury accounts to the main treasury balance")
+                .arg(chain_id_arg())
+        )
+        .subcommand(
+            SubCommand::with_name("treasury-status")
+                .about("Report balances, rent-exempt minimums and collectable lamports of the treasury accounts")
+                .arg(chain_id_arg())
+        )
+        .subcommand(
+            SubCommand::with_name("collect-storage")
+                .about("Close all-zero and outdated contract storage accounts and collect their rent")
+        )
+        .subcommand(
+            SubCommand::with_name("get-account-versions")
+                .about("Count program accounts by type and report the ones with outdated layout")
         )
         .subcommand(
             SubCommand::with_name("init-environment")
@@ -393,6 +675,7 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                         .validator(is_valid_u256)
                         .required(true),
                 )
+                .arg(chain_id_arg())
         )
         .get_matches()
 }
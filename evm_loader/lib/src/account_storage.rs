@@ -1,5 +1,14 @@
 use async_trait::async_trait;
-use std::{cell::RefCell, collections::HashMap, convert::TryInto, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    convert::TryInto,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
 
 use crate::{rpc::Rpc, NeonError};
 use ethnum::U256;
@@ -8,8 +17,8 @@ use evm_loader::account_storage::{find_slot_hash, AccountOperation, AccountsOper
 use evm_loader::evm::tracing::{AccountOverrides, BlockOverrides};
 use evm_loader::{
     account::{
-        ether_storage::EthereumStorageAddress, EthereumAccount, EthereumStorage,
-        ACCOUNT_SEED_VERSION,
+        ether_storage::EthereumStorageAddress, parameters, EthereumAccount, EthereumStorage,
+        Parameters,
     },
     account_storage::AccountStorage,
     config::STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT,
@@ -18,22 +27,39 @@ use evm_loader::{
     types::Address,
 };
 use log::{debug, error, info, trace, warn};
+use mpl_token_metadata::state::{MAX_EDITION_LEN, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN};
 use serde::{Deserialize, Serialize};
 use solana_client::client_error;
 use solana_sdk::entrypoint::MAX_PERMITTED_DATA_INCREASE;
 use solana_sdk::{
     account::Account,
     account_info::AccountInfo,
+    clock::Clock,
     commitment_config::CommitmentConfig,
+    epoch_schedule::EpochSchedule,
+    instruction::AccountMeta,
+    program_pack::Pack,
     pubkey,
     pubkey::Pubkey,
     rent::Rent,
-    sysvar::{slot_hashes, Sysvar},
+    system_instruction::SystemInstruction,
+    system_program,
+    sysvar::{self, clock, epoch_schedule, slot_hashes, Sysvar},
 };
+use spl_token::instruction::TokenInstruction;
 
 use crate::types::PubkeyBase58;
 
 const FAKE_OPERATOR: Pubkey = pubkey!("neonoperator1111111111111111111111111111111");
+/// Neon account of the emulated operator, receives the gas payment
+pub const FAKE_OPERATOR_ADDRESS: Address = Address([0xFE; 20]);
+
+/// `get_multiple_accounts` RPC limit
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+/// Storage pages loaded together with the requested one, contracts tend to use adjacent slots
+const STORAGE_PAGES_PREFETCH: u64 = 4;
+/// Limit of hardcoded addresses prefetched from the contract code
+const MAX_CALL_TARGETS_PREFETCH: usize = 16;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeonAccount {
@@ -82,10 +108,13 @@ impl NeonAccount {
     pub async fn rpc_load(
         rpc_client: &dyn Rpc,
         evm_loader: &Pubkey,
+        chain_id: u64,
+        default_chain_id: u64,
         address: Address,
         writable: bool,
     ) -> Self {
-        let (key, _) = make_solana_program_address(&address, evm_loader);
+        let (key, _) =
+            make_solana_program_address(&address, evm_loader, chain_id, default_chain_id);
         info!("get_account_from_solana {address} => {key}");
 
         let account = match rpc_client.get_account(&key).await {
@@ -99,26 +128,104 @@ impl NeonAccount {
     }
 }
 
+/// Purpose of the Solana account in the transaction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolanaAccountRole {
+    #[default]
+    Other,
+    Program,
+    Sysvar,
+    /// Program derived address signing external instructions
+    PdaSigner,
+    TokenAccount,
+    Mint,
+    /// Metaplex metadata or edition account
+    Metadata,
+}
+
+impl SolanaAccountRole {
+    /// Specific roles take precedence over the generic ones
+    fn merge(self, role: Self) -> Self {
+        match (self, role) {
+            (_, Self::Other) => self,
+            (Self::Other | Self::PdaSigner, _) => role,
+            _ => self,
+        }
+    }
+
+    #[must_use]
+    pub fn is_token(self) -> bool {
+        matches!(self, Self::TokenAccount | Self::Mint | Self::Metadata)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SolanaAccount {
     pubkey: PubkeyBase58,
     is_writable: bool,
+    #[serde(default)]
+    role: SolanaAccountRole,
+    /// Accounts allocated by the transaction: the account has to be created
+    #[serde(default)]
+    new: bool,
+    /// Accounts allocated by the transaction: the required size
+    #[serde(default)]
+    size: usize,
+    /// Accounts allocated by the transaction: the size before the transaction
+    #[serde(default)]
+    size_current: usize,
     #[serde(skip)]
     data: Option<Account>,
 }
 
+impl SolanaAccount {
+    fn new(pubkey: Pubkey, is_writable: bool, data: Option<Account>) -> Self {
+        let role = if sysvar::is_sysvar_id(&pubkey) {
+            SolanaAccountRole::Sysvar
+        } else {
+            SolanaAccountRole::Other
+        };
+
+        Self {
+            pubkey: pubkey.into(),
+            is_writable,
+            role,
+            new: false,
+            size: 0,
+            size_current: 0,
+            data,
+        }
+    }
+
+    #[must_use]
+    pub fn role(&self) -> SolanaAccountRole {
+        self.role
+    }
+}
+
 #[allow(clippy::module_name_repetitions)]
 pub struct EmulatorAccountStorage<'a> {
-    pub accounts: RefCell<HashMap<Address, NeonAccount>>,
-    pub solana_accounts: RefCell<HashMap<Pubkey, SolanaAccount>>,
+    pub accounts: RwLock<HashMap<Address, NeonAccount>>,
+    pub solana_accounts: RwLock<HashMap<Pubkey, SolanaAccount>>,
     rpc_client: &'a dyn Rpc,
     evm_loader: Pubkey,
     block_number: u64,
     block_timestamp: i64,
     neon_token_mint: Pubkey,
     chain_id: u64,
+    default_chain_id: u64,
     commitment: CommitmentConfig,
     state_overrides: Option<AccountOverrides>,
+    /// Speculatively loaded accounts, moved to `accounts` or `solana_accounts` on the first use
+    prefetched: RwLock<HashMap<Pubkey, Option<Account>>>,
+    rpc_calls: AtomicU64,
+    /// Accounts as they were loaded from the cluster, `None` if the account doesn't exist
+    loaded: RwLock<BTreeMap<Pubkey, Option<Account>>>,
+    /// Storage slots read by the emulation
+    storage_access: RwLock<BTreeMap<Address, BTreeSet<U256>>>,
+    /// Addresses whose state is read by the emulation, preloaded and prefetched ones aren't listed
+    address_access: RwLock<BTreeSet<Address>>,
 }
 
 impl<'a> EmulatorAccountStorage<'a> {
@@ -127,17 +234,23 @@ impl<'a> EmulatorAccountStorage<'a> {
         evm_loader: Pubkey,
         token_mint: Pubkey,
         chain_id: u64,
+        default_chain_id: u64,
         commitment: CommitmentConfig,
         block_overrides: &Option<BlockOverrides>,
         state_overrides: Option<AccountOverrides>,
     ) -> Result<EmulatorAccountStorage<'a>, NeonError> {
         trace!("backend::new");
 
+        let mut rpc_calls = 0;
+
         let block_number = match block_overrides
             .as_ref()
             .and_then(|overrides| overrides.number)
         {
-            None => rpc_client.get_slot().await?,
+            None => {
+                rpc_calls += 1;
+                rpc_client.get_slot().await?
+            }
             Some(number) => number,
         };
 
@@ -145,30 +258,247 @@ impl<'a> EmulatorAccountStorage<'a> {
             .as_ref()
             .and_then(|overrides| overrides.time)
         {
-            None => rpc_client.get_block_time(block_number).await?,
+            None => {
+                rpc_calls += 1;
+                rpc_client.get_block_time(block_number).await?
+            }
             Some(time) => time,
         };
 
         Ok(Self {
-            accounts: RefCell::new(HashMap::new()),
-            solana_accounts: RefCell::new(HashMap::new()),
+            accounts: RwLock::new(HashMap::new()),
+            solana_accounts: RwLock::new(HashMap::new()),
             rpc_client,
             evm_loader,
             block_number,
             block_timestamp,
             neon_token_mint: token_mint,
             chain_id,
+            default_chain_id,
             commitment,
             state_overrides,
+            prefetched: RwLock::new(HashMap::new()),
+            rpc_calls: AtomicU64::new(rpc_calls),
+            loaded: RwLock::new(BTreeMap::new()),
+            storage_access: RwLock::new(BTreeMap::new()),
+            address_access: RwLock::new(BTreeSet::new()),
         })
     }
 
+    /// Number of RPC requests made by the storage
+    pub fn rpc_calls(&self) -> u64 {
+        self.rpc_calls.load(Ordering::Relaxed)
+    }
+
+    fn count_rpc_call(&self) {
+        self.rpc_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Remember the first observed state of the account
+    fn record_loaded(&self, pubkey: Pubkey, account: &Option<Account>) {
+        self.loaded
+            .write()
+            .unwrap()
+            .entry(pubkey)
+            .or_insert_with(|| account.clone());
+    }
+
+    /// Accounts loaded from the cluster during the emulation, `None` if the account doesn't exist
+    pub fn loaded_accounts(&self) -> BTreeMap<Pubkey, Option<Account>> {
+        self.loaded.read().unwrap().clone()
+    }
+
+    /// Storage slots read by the emulation, grouped by contract
+    pub fn storage_access(&self) -> BTreeMap<Address, BTreeSet<U256>> {
+        self.storage_access.read().unwrap().clone()
+    }
+
+    /// Addresses whose state is read by the emulation
+    pub fn address_access(&self) -> BTreeSet<Address> {
+        self.address_access.read().unwrap().clone()
+    }
+
+    /// Load accounts in batches, the result is used instead of the RPC request on the first access
+    async fn prefetch(&self, mut pubkeys: Vec<Pubkey>) {
+        {
+            let prefetched = self.prefetched.read().unwrap();
+            let solana_accounts = self.solana_accounts.read().unwrap();
+            pubkeys.retain(|pubkey| {
+                !prefetched.contains_key(pubkey)
+                    && solana_accounts
+                        .get(pubkey)
+                        .map_or(true, |account| account.data.is_none())
+            });
+        }
+        pubkeys.sort_unstable();
+        pubkeys.dedup();
+
+        for chunk in pubkeys.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            self.count_rpc_call();
+            match self.rpc_client.get_multiple_accounts(chunk).await {
+                Ok(accounts) => {
+                    let mut prefetched = self.prefetched.write().unwrap();
+                    for (pubkey, account) in chunk.iter().zip(accounts) {
+                        self.record_loaded(*pubkey, &account);
+                        prefetched.insert(*pubkey, account);
+                    }
+                }
+                Err(err) => warn!("prefetch {} accounts error: {err:?}", chunk.len()),
+            }
+        }
+    }
+
+    /// Prefetch ethereum accounts and their storage pages in batches.
+    /// Slots stored inside the contract account don't need additional requests.
+    pub async fn prefetch_accounts(
+        &self,
+        addresses: &[Address],
+        storage_slots: &[(Address, U256)],
+    ) {
+        let mut pubkeys = Vec::with_capacity(addresses.len() + storage_slots.len());
+        {
+            let accounts = self.accounts.read().unwrap();
+            for address in addresses {
+                if !accounts.contains_key(address) {
+                    pubkeys.push(
+                        make_solana_program_address(
+                            address,
+                            &self.evm_loader,
+                            self.chain_id,
+                            self.default_chain_id,
+                        )
+                        .0,
+                    );
+                }
+            }
+        }
+
+        for (address, index) in storage_slots {
+            if *index >= U256::from(STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT) {
+                pubkeys.push(self.storage_page_address(address, index));
+            }
+        }
+
+        self.prefetch(pubkeys).await;
+    }
+
+    fn storage_page_address(&self, address: &Address, index: &U256) -> Pubkey {
+        let index = index & !U256::new(0xFF);
+
+        let (base, _) = address.find_solana_address_in_chain(
+            &self.evm_loader,
+            self.chain_id,
+            self.default_chain_id,
+        );
+        *EthereumStorageAddress::new(&self.evm_loader, &base, &index).pubkey()
+    }
+
+    /// Storage page is requested, load the following pages of the contract together with it
+    async fn prefetch_storage_pages(&self, address: &Address, index: &U256) {
+        let page = self.storage_page_address(address, index);
+        let is_loaded = self.prefetched.read().unwrap().contains_key(&page)
+            || self
+                .solana_accounts
+                .read()
+                .unwrap()
+                .get(&page)
+                .map_or(false, |account| account.data.is_some());
+        if is_loaded {
+            return;
+        }
+
+        let pubkeys = (0..STORAGE_PAGES_PREFETCH)
+            .filter_map(|n| index.checked_add(U256::from(n * 256)))
+            .map(|index| self.storage_page_address(address, &index))
+            .collect();
+
+        self.prefetch(pubkeys).await;
+    }
+
+    /// Contract is loaded, prefetch accounts of the addresses hardcoded in its code
+    async fn prefetch_call_targets(&self, address: &Address) {
+        let code = {
+            let mut accounts = self.accounts.write().unwrap();
+            match accounts.get_mut(address) {
+                Some(NeonAccount {
+                    account,
+                    data: Some(account_data),
+                    ..
+                }) => {
+                    let info = account_info(account.as_ref(), account_data);
+                    EthereumAccount::from_account(&self.evm_loader, &info)
+                        .ok()
+                        .and_then(|a| a.contract_data().map(|c| c.code().to_vec()))
+                        .unwrap_or_default()
+                }
+                _ => Vec::new(),
+            }
+        };
+        if code.is_empty() {
+            return;
+        }
+
+        let targets: Vec<Address> = call_targets(&code)
+            .into_iter()
+            .filter(|target| target != address)
+            .take(MAX_CALL_TARGETS_PREFETCH)
+            .collect();
+
+        self.prefetch_accounts(&targets, &[]).await;
+    }
+
+    async fn load_account(&self, pubkey: &Pubkey) -> Option<Account> {
+        if let Some(account) = self.prefetched.write().unwrap().remove(pubkey) {
+            return account;
+        }
+
+        self.count_rpc_call();
+        match self.rpc_client.get_account(pubkey).await {
+            Ok(account) => {
+                let account = Some(account);
+                self.record_loaded(*pubkey, &account);
+                account
+            }
+            Err(err) => {
+                error!("rpc_client.get_account {pubkey} error: {err:?}");
+                None
+            }
+        }
+    }
+
+    /// Runtime parameters of the program: on-chain overrides or compiled defaults
+    pub async fn parameters(&self) -> parameters::Values {
+        let (parameters_key, _) = Parameters::address(&self.evm_loader);
+
+        self.count_rpc_call();
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&parameters_key, self.commitment)
+            .await
+            .ok()
+            .map(|response| response.value);
+        if let Some(account) = &account {
+            self.record_loaded(parameters_key, account);
+        }
+
+        match account.flatten() {
+            Some(mut account) => {
+                let info = account_info(&parameters_key, &mut account);
+                Parameters::from_account(&self.evm_loader, &info)
+                    .and_then(|parameters| parameters.values())
+                    .unwrap_or_default()
+            }
+            None => parameters::Values::default(),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn with_accounts(
         rpc_client: &'a dyn Rpc,
         evm_loader: Pubkey,
         token_mint: Pubkey,
         chain_id: u64,
+        default_chain_id: u64,
         commitment: CommitmentConfig,
         accounts: &[Address],
         solana_accounts: &[Pubkey],
@@ -180,6 +510,7 @@ impl<'a> EmulatorAccountStorage<'a> {
             evm_loader,
             token_mint,
             chain_id,
+            default_chain_id,
             commitment,
             block_overrides,
             state_overrides,
@@ -199,68 +530,97 @@ impl<'a> EmulatorAccountStorage<'a> {
     ) {
         let pubkeys: Vec<_> = addresses
             .iter()
-            .map(|address| make_solana_program_address(address, &self.evm_loader).0)
+            .map(|address| {
+                make_solana_program_address(
+                    address,
+                    &self.evm_loader,
+                    self.chain_id,
+                    self.default_chain_id,
+                )
+                .0
+            })
             .chain(solana_accounts.iter().copied())
             .collect();
 
+        self.count_rpc_call();
         if let Ok(accounts) = self.rpc_client.get_multiple_accounts(&pubkeys).await {
+            for (pubkey, account) in pubkeys.iter().zip(&accounts) {
+                self.record_loaded(*pubkey, account);
+            }
+
             let entries = addresses
                 .iter()
                 .zip(accounts.iter().take(addresses.len()))
                 .zip(pubkeys.iter().take(addresses.len()));
             for ((&address, account), &pubkey) in entries {
-                self.accounts.borrow_mut().insert(
+                self.accounts.write().unwrap().insert(
                     address,
                     NeonAccount::new(address, pubkey, account.clone(), false),
                 );
             }
 
             let entries = accounts.iter().skip(addresses.len()).zip(solana_accounts);
-            let mut solana_accounts_storage = self.solana_accounts.borrow_mut();
+            let mut solana_accounts_storage = self.solana_accounts.write().unwrap();
             for (account, &pubkey) in entries {
-                solana_accounts_storage.insert(
-                    pubkey,
-                    SolanaAccount {
-                        pubkey: pubkey.into(),
-                        is_writable: false,
-                        data: account.clone(),
-                    },
-                );
+                solana_accounts_storage
+                    .insert(pubkey, SolanaAccount::new(pubkey, false, account.clone()));
             }
         }
     }
 
     pub async fn get_account(&self, pubkey: &Pubkey) -> client_error::Result<Option<Account>> {
-        if let Some(account) = self.solana_accounts.borrow().get(pubkey) {
+        if let Some(account) = self.solana_accounts.read().unwrap().get(pubkey) {
             if let Some(ref data) = account.data {
                 return Ok(Some(data.clone()));
             }
         }
 
-        let result = self
-            .rpc_client
-            .get_account_with_commitment(pubkey, self.commitment)
-            .await?;
+        let prefetched = self.prefetched.write().unwrap().remove(pubkey);
+        let value = match prefetched {
+            Some(value) => value,
+            None => {
+                self.count_rpc_call();
+                let value = self
+                    .rpc_client
+                    .get_account_with_commitment(pubkey, self.commitment)
+                    .await?
+                    .value;
+                self.record_loaded(*pubkey, &value);
+                value
+            }
+        };
 
         self.solana_accounts
-            .borrow_mut()
+            .write()
+            .unwrap()
             .entry(*pubkey)
-            .and_modify(|a| a.data = result.value.clone())
-            .or_insert(SolanaAccount {
-                pubkey: pubkey.into(),
-                is_writable: false,
-                data: result.value.clone(),
-            });
+            .and_modify(|a| a.data = value.clone())
+            .or_insert_with(|| SolanaAccount::new(*pubkey, false, value.clone()));
+
+        Ok(value)
+    }
 
-        Ok(result.value)
+    async fn get_sysvar(&self, pubkey: &Pubkey) -> evm_loader::error::Result<Account> {
+        match self.get_account(pubkey).await {
+            Ok(Some(account)) => Ok(account),
+            Ok(None) => Err(evm_loader::error::Error::Custom(format!(
+                "Sysvar {pubkey} not found"
+            ))),
+            Err(e) => Err(evm_loader::error::Error::Custom(format!(
+                "Error querying account {pubkey} from Solana: {e}"
+            ))),
+        }
     }
 
     pub async fn get_account_from_solana(
         rpc_client: &'a dyn Rpc,
         evm_loader: &'a Pubkey,
         address: &Address,
+        chain_id: u64,
+        default_chain_id: u64,
     ) -> (Pubkey, Option<Account>) {
-        let (solana_address, _solana_nonce) = make_solana_program_address(address, evm_loader);
+        let (solana_address, _solana_nonce) =
+            make_solana_program_address(address, evm_loader, chain_id, default_chain_id);
         info!("get_account_from_solana {} => {}", address, solana_address);
 
         if let Ok(acc) = rpc_client.get_account(&solana_address).await {
@@ -277,15 +637,29 @@ impl<'a> EmulatorAccountStorage<'a> {
     }
 
     async fn add_ethereum_account(&self, address: &Address, writable: bool) -> bool {
-        if let Some(ref mut account) = self.accounts.borrow_mut().get_mut(address) {
+        if let Some(ref mut account) = self.accounts.write().unwrap().get_mut(address) {
             account.writable |= writable;
 
             return true;
         }
 
-        let account =
-            NeonAccount::rpc_load(self.rpc_client, &self.evm_loader, *address, writable).await;
-        self.accounts.borrow_mut().insert(*address, account);
+        let (key, _) = make_solana_program_address(
+            address,
+            &self.evm_loader,
+            self.chain_id,
+            self.default_chain_id,
+        );
+        info!("get_account_from_solana {address} => {key}");
+
+        let account = self.load_account(&key).await;
+        let is_loaded = account.is_some();
+
+        let account = NeonAccount::new(*address, key, account, writable);
+        self.accounts.write().unwrap().insert(*address, account);
+
+        if is_loaded {
+            self.prefetch_call_targets(address).await;
+        }
 
         false
     }
@@ -299,13 +673,9 @@ impl<'a> EmulatorAccountStorage<'a> {
             return;
         }
 
-        let mut solana_accounts = self.solana_accounts.borrow_mut();
+        let mut solana_accounts = self.solana_accounts.write().unwrap();
 
-        let account = SolanaAccount {
-            pubkey: pubkey.into(),
-            is_writable,
-            data: None,
-        };
+        let account = SolanaAccount::new(pubkey, is_writable, None);
         if is_writable {
             solana_accounts
                 .entry(pubkey)
@@ -351,7 +721,11 @@ impl<'a> EmulatorAccountStorage<'a> {
                     if *index < U256::from(STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT) {
                         self.add_ethereum_account(address, true).await;
                     } else {
-                        let (base, _) = address.find_solana_address(self.program_id());
+                        let (base, _) = address.find_solana_address_in_chain(
+                            self.program_id(),
+                            self.chain_id,
+                            self.default_chain_id,
+                        );
                         let storage_account =
                             EthereumStorageAddress::new(self.program_id(), &base, index);
                         self.add_solana_account(*storage_account.pubkey(), true)
@@ -384,8 +758,9 @@ impl<'a> EmulatorAccountStorage<'a> {
                 Action::ExternalInstruction {
                     program_id,
                     accounts,
+                    data,
+                    seeds,
                     fee,
-                    ..
                 } => {
                     info!("external call {program_id}");
 
@@ -396,6 +771,9 @@ impl<'a> EmulatorAccountStorage<'a> {
                             .await;
                     }
 
+                    self.tag_solana_accounts(program_id, accounts, data, seeds)
+                        .await;
+
                     gas = gas.saturating_add(*fee);
                 }
             }
@@ -404,13 +782,80 @@ impl<'a> EmulatorAccountStorage<'a> {
         gas
     }
 
+    /// Set roles of the external instruction accounts and sizes of the accounts it allocates
+    async fn tag_solana_accounts(
+        &self,
+        program_id: &Pubkey,
+        accounts: &[AccountMeta],
+        data: &[u8],
+        seeds: &[Vec<u8>],
+    ) {
+        let mut roles = vec![(*program_id, SolanaAccountRole::Program)];
+        let mut sizes = vec![];
+
+        if !seeds.is_empty() {
+            for account in accounts.iter().filter(|a| a.is_signer) {
+                roles.push((account.pubkey, SolanaAccountRole::PdaSigner));
+            }
+        }
+
+        let key = |index: usize| accounts.get(index).map(|a| a.pubkey);
+
+        if system_program::check_id(program_id) {
+            match bincode::deserialize(data) {
+                Ok(SystemInstruction::Allocate { space }) => sizes.extend(key(0).zip(Some(space))),
+                Ok(SystemInstruction::CreateAccount { space, .. }) => {
+                    sizes.extend(key(1).zip(Some(space)));
+                }
+                _ => {}
+            }
+        } else if spl_token::check_id(program_id) {
+            roles.extend(token_instruction_roles(data, &key));
+        } else if spl_associated_token_account::check_id(program_id) {
+            roles.extend(key(1).map(|k| (k, SolanaAccountRole::TokenAccount)));
+            roles.extend(key(3).map(|k| (k, SolanaAccountRole::Mint)));
+            sizes.extend(key(1).map(|k| (k, spl_token::state::Account::LEN as u64)));
+        } else if mpl_token_metadata::check_id(program_id) {
+            for (pubkey, size) in metaplex_instruction_accounts(data, &key) {
+                if accounts.iter().any(|a| a.pubkey == pubkey) {
+                    roles.push((pubkey, SolanaAccountRole::Metadata));
+                    sizes.extend(size.map(|size| (pubkey, size as u64)));
+                }
+            }
+        }
+
+        for (pubkey, role) in roles {
+            if let Some(account) = self.solana_accounts.write().unwrap().get_mut(&pubkey) {
+                account.role = account.role.merge(role);
+            }
+        }
+
+        for (pubkey, size) in sizes {
+            let current = match self.get_account(&pubkey).await {
+                Ok(account) => account,
+                Err(err) => {
+                    warn!("get_account {pubkey} error: {err:?}");
+                    None
+                }
+            };
+
+            if let Some(account) = self.solana_accounts.write().unwrap().get_mut(&pubkey) {
+                account.new = current.as_ref().map_or(true, |a| {
+                    system_program::check_id(&a.owner) && a.data.is_empty()
+                });
+                account.size_current = current.map_or(0, |a| a.data.len());
+                account.size = account.size.max(size.try_into().unwrap_or(usize::MAX));
+            }
+        }
+    }
+
     pub async fn apply_accounts_operations(&self, operations: AccountsOperations) -> u64 {
         let mut gas = 0_u64;
         let rent = Rent::get().expect("Rent get error");
 
         let mut iterations = 0_usize;
 
-        let mut accounts = self.accounts.borrow_mut();
+        let mut accounts = self.accounts.write().unwrap();
         for (address, operation) in operations {
             let new_size = match operation {
                 AccountOperation::Create { space } => space,
@@ -437,9 +882,10 @@ impl<'a> EmulatorAccountStorage<'a> {
     where
         F: FnOnce(&EthereumAccount) -> R,
     {
+        self.address_access.write().unwrap().insert(*address);
         self.add_ethereum_account(address, false).await;
 
-        let mut accounts = self.accounts.borrow_mut();
+        let mut accounts = self.accounts.write().unwrap();
         let solana_account = accounts.get_mut(address).expect("get account error");
 
         if let Some(account_data) = &mut solana_account.data {
@@ -463,9 +909,10 @@ impl<'a> EmulatorAccountStorage<'a> {
     where
         F: FnOnce(ether_contract::ContractData) -> R,
     {
+        self.address_access.write().unwrap().insert(*address);
         self.add_ethereum_account(address, false).await;
 
-        let mut accounts = self.accounts.borrow_mut();
+        let mut accounts = self.accounts.write().unwrap();
         let solana_account = accounts.get_mut(address).expect("get account error");
 
         if let Some(account_data) = &mut solana_account.data {
@@ -481,7 +928,7 @@ impl<'a> EmulatorAccountStorage<'a> {
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
     fn neon_token_mint(&self) -> &Pubkey {
         info!("neon_token_mint");
@@ -521,12 +968,33 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
         }
     }
 
+    /// Clock of the emulated block. The epoch and the leader schedule epoch are derived
+    /// for the overridden slot, `epoch_start_timestamp` is left the one of the current epoch.
+    async fn clock(&self) -> evm_loader::error::Result<Clock> {
+        info!("clock");
+
+        let mut clock: Clock = bincode::deserialize(&self.get_sysvar(&clock::ID).await?.data)?;
+        if clock.slot != self.block_number {
+            let epoch_schedule: EpochSchedule =
+                bincode::deserialize(&self.get_sysvar(&epoch_schedule::ID).await?.data)?;
+
+            clock.slot = self.block_number;
+            clock.epoch = epoch_schedule.get_epoch(self.block_number);
+            clock.leader_schedule_epoch =
+                epoch_schedule.get_leader_schedule_epoch(self.block_number);
+        }
+        clock.unix_timestamp = self.block_timestamp;
+
+        Ok(clock)
+    }
+
     async fn exists(&self, address: &Address) -> bool {
         info!("exists {address}");
 
+        self.address_access.write().unwrap().insert(*address);
         self.add_ethereum_account(address, false).await;
 
-        let accounts = self.accounts.borrow();
+        let accounts = self.accounts.read().unwrap();
         accounts.contains_key(address)
     }
 
@@ -580,14 +1048,15 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
 
         info!("code {address}");
 
-        self.ethereum_contract_map_or(address, Buffer::empty(), |c| {
-            self.state_overrides
-                .as_ref()
-                .and_then(|account_overrides| account_overrides.get(address)?.code.as_ref())
-                .map_or_else(
-                    || Buffer::from_slice(&c.code()),
-                    |code| Buffer::from_slice(&code.0),
-                )
+        let code_override = self
+            .state_overrides
+            .as_ref()
+            .and_then(|account_overrides| account_overrides.get(address)?.code.as_ref());
+
+        self.ethereum_account_map_or(address, Buffer::empty(), |a| match code_override {
+            Some(code) => Buffer::from_slice(&code.0),
+            None if a.is_contract() => unsafe { Buffer::from_account(a.info, a.code_location()) },
+            None => Buffer::empty(),
         })
         .await
     }
@@ -602,6 +1071,14 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
     }
 
     async fn storage(&self, address: &Address, index: &U256) -> [u8; 32] {
+        self.address_access.write().unwrap().insert(*address);
+        self.storage_access
+            .write()
+            .unwrap()
+            .entry(*address)
+            .or_default()
+            .insert(*index);
+
         if let Some(account_overrides) = &self.state_overrides {
             if let Some(account_override) = account_overrides.get(address) {
                 match (&account_override.state, &account_override.state_diff) {
@@ -633,16 +1110,23 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
             let subindex = (index & 0xFF).as_u8();
             let index = index & !U256::new(0xFF);
 
-            let (base, _) = address.find_solana_address(self.program_id());
+            let (base, _) = address.find_solana_address_in_chain(
+                self.program_id(),
+                self.chain_id,
+                self.default_chain_id,
+            );
             let storage_address = EthereumStorageAddress::new(self.program_id(), &base, &index);
 
             self.add_solana_account(*storage_address.pubkey(), false)
                 .await;
+            self.prefetch_storage_pages(address, &index).await;
 
             let rpc_response = self
                 .get_account(storage_address.pubkey())
                 .await
                 .expect("Error querying account from Solana");
+            // Account info is not `Send`, the generation is requested before it's created
+            let generation = self.generation(address).await;
 
             if let Some(mut account) = rpc_response {
                 if solana_sdk::system_program::check_id(&account.owner) {
@@ -654,7 +1138,7 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
                         .expect("EthereumAccount ctor error");
                     if (storage.address != *address)
                         || (storage.index != index)
-                        || (storage.generation != self.generation(address).await)
+                        || (storage.generation != generation)
                     {
                         debug!("storage collision");
                         <[u8; 32]>::default()
@@ -684,6 +1168,10 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
         self.chain_id
     }
 
+    fn default_chain_id(&self) -> u64 {
+        self.default_chain_id
+    }
+
     async fn clone_solana_account(&self, address: &Pubkey) -> OwnedAccountInfo {
         info!("clone_solana_account {}", address);
 
@@ -714,8 +1202,21 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
 
     async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
     where
-        F: FnOnce(&AccountInfo) -> R,
+        F: FnOnce(&AccountInfo) -> R + Send,
     {
+        // Ethereum accounts are mapped by the contract code buffers restored between iterations
+        let ethereum_account = self
+            .accounts
+            .read()
+            .unwrap()
+            .values()
+            .find(|account| account.account.0 == *address)
+            .and_then(|account| account.data.clone());
+        if let Some(mut account) = ethereum_account {
+            let info = account_info(address, &mut account);
+            return action(&info);
+        }
+
         self.add_solana_account(*address, false).await;
 
         let mut account = self
@@ -743,9 +1244,475 @@ pub fn account_info<'a>(key: &'a Pubkey, account: &'a mut Account) -> AccountInf
     }
 }
 
-pub fn make_solana_program_address(ether_address: &Address, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[&[ACCOUNT_SEED_VERSION], ether_address.as_bytes()],
-        program_id,
-    )
+/// Addresses pushed by `PUSH20` instructions, skipping precompiles and small constants
+fn call_targets(code: &[u8]) -> Vec<Address> {
+    const PUSH1: u8 = 0x60;
+    const PUSH20: u8 = 0x73;
+    const PUSH32: u8 = 0x7F;
+
+    let mut targets = Vec::new();
+
+    let mut pc = 0;
+    while pc < code.len() {
+        let opcode = code[pc];
+        pc += 1;
+
+        if !(PUSH1..=PUSH32).contains(&opcode) {
+            continue;
+        }
+
+        let size = usize::from(opcode - PUSH1 + 1);
+        if opcode == PUSH20 && pc + size <= code.len() {
+            let bytes: [u8; 20] = code[pc..pc + size].try_into().unwrap();
+            if bytes[..18].iter().any(|b| *b != 0) {
+                let target = Address::from(bytes);
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+
+        pc += size;
+    }
+
+    targets
+}
+
+pub fn make_solana_program_address(
+    ether_address: &Address,
+    program_id: &Pubkey,
+    chain_id: u64,
+    default_chain_id: u64,
+) -> (Pubkey, u8) {
+    ether_address.find_solana_address_in_chain(program_id, chain_id, default_chain_id)
+}
+
+/// Roles of the SPL Token instruction accounts
+fn token_instruction_roles(
+    data: &[u8],
+    key: &impl Fn(usize) -> Option<Pubkey>,
+) -> Vec<(Pubkey, SolanaAccountRole)> {
+    use SolanaAccountRole::{Mint, TokenAccount};
+
+    let roles: &[SolanaAccountRole] = match TokenInstruction::unpack(data) {
+        Ok(TokenInstruction::InitializeMint { .. } | TokenInstruction::InitializeMint2 { .. }) => {
+            &[Mint]
+        }
+        Ok(
+            TokenInstruction::InitializeAccount
+            | TokenInstruction::InitializeAccount2 { .. }
+            | TokenInstruction::InitializeAccount3 { .. }
+            | TokenInstruction::Burn { .. }
+            | TokenInstruction::BurnChecked { .. }
+            | TokenInstruction::FreezeAccount
+            | TokenInstruction::ThawAccount,
+        ) => &[TokenAccount, Mint],
+        Ok(TokenInstruction::Transfer { .. }) => &[TokenAccount, TokenAccount],
+        Ok(TokenInstruction::TransferChecked { .. }) => &[TokenAccount, Mint, TokenAccount],
+        Ok(TokenInstruction::MintTo { .. } | TokenInstruction::MintToChecked { .. }) => {
+            &[Mint, TokenAccount]
+        }
+        Ok(TokenInstruction::ApproveChecked { .. }) => &[TokenAccount, Mint],
+        Ok(
+            TokenInstruction::Approve { .. }
+            | TokenInstruction::Revoke
+            | TokenInstruction::CloseAccount,
+        ) => &[TokenAccount],
+        _ => &[],
+    };
+
+    roles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, role)| key(index).map(|pubkey| (pubkey, *role)))
+        .collect()
+}
+
+/// Metadata and edition PDAs of the metaplex instruction mints,
+/// with sizes of the accounts the instruction creates
+fn metaplex_instruction_accounts(
+    data: &[u8],
+    key: &impl Fn(usize) -> Option<Pubkey>,
+) -> Vec<(Pubkey, Option<usize>)> {
+    // Borsh tags of `MetadataInstruction` variants called by the metaplex precompile
+    const MINT_NEW_EDITION_FROM_MASTER_EDITION_VIA_TOKEN: u8 = 11;
+    const CREATE_MASTER_EDITION_V3: u8 = 17;
+    const VERIFY_COLLECTION: u8 = 18;
+    const SET_AND_VERIFY_COLLECTION: u8 = 25;
+    const BURN_NFT: u8 = 29;
+    const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+    // Mint index, created metadata and edition sizes
+    let mints: &[(usize, Option<usize>, Option<usize>)] = match data.first() {
+        Some(&CREATE_METADATA_ACCOUNT_V3) => &[(1, Some(MAX_METADATA_LEN), None)],
+        Some(&CREATE_MASTER_EDITION_V3) => &[(1, None, Some(MAX_MASTER_EDITION_LEN))],
+        Some(&MINT_NEW_EDITION_FROM_MASTER_EDITION_VIA_TOKEN) => {
+            &[(3, Some(MAX_METADATA_LEN), Some(MAX_EDITION_LEN))]
+        }
+        Some(&VERIFY_COLLECTION) => &[(3, None, None)],
+        Some(&SET_AND_VERIFY_COLLECTION) => &[(4, None, None)],
+        Some(&BURN_NFT) => &[(2, None, None)],
+        _ => &[],
+    };
+
+    mints
+        .iter()
+        .filter_map(|(index, metadata_size, edition_size)| {
+            let mint = key(*index)?;
+            let (metadata, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
+            // Print editions have the same seeds as master ones
+            let (edition, _) = mpl_token_metadata::pda::find_master_edition_account(&mint);
+
+            Some([(metadata, *metadata_size), (edition, *edition_size)])
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{Snapshot, SnapshotAccount, SnapshotClient};
+    use evm_loader::config::CHAIN_ID;
+    use solana_sdk::instruction::Instruction;
+
+    fn sysvar_account<T: Serialize>(value: &T) -> Account {
+        Account {
+            lamports: 1,
+            data: bincode::serialize(value).unwrap(),
+            owner: sysvar::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn snapshot_client(slot: u64, accounts: Vec<(Pubkey, Account)>) -> SnapshotClient {
+        SnapshotClient::new(Snapshot {
+            slot,
+            block_time: 1_700_000_000,
+            rent: Rent::default(),
+            accounts: accounts
+                .into_iter()
+                .map(|(pubkey, account)| SnapshotAccount { pubkey, account })
+                .collect(),
+        })
+    }
+
+    async fn emulator_storage(
+        rpc_client: &dyn Rpc,
+        block_number: Option<u64>,
+    ) -> EmulatorAccountStorage<'_> {
+        let block_overrides = block_number.map(|number| BlockOverrides {
+            number: Some(number),
+            difficulty: None,
+            time: Some(1_700_000_000),
+            gas_limit: None,
+            coinbase: None,
+            random: None,
+            base_fee: None,
+        });
+
+        EmulatorAccountStorage::new(
+            rpc_client,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            CHAIN_ID,
+            CHAIN_ID,
+            CommitmentConfig::confirmed(),
+            &block_overrides,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn clock_at(slot: u64, epoch_schedule: &EpochSchedule) -> Clock {
+        Clock {
+            slot,
+            epoch_start_timestamp: 1_600_000_000,
+            epoch: epoch_schedule.get_epoch(slot),
+            leader_schedule_epoch: epoch_schedule.get_leader_schedule_epoch(slot),
+            unix_timestamp: 1_700_000_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn clock_of_overridden_slot() {
+        let epoch_schedule = EpochSchedule::custom(64, 64, false);
+        let rpc_client = snapshot_client(
+            100,
+            vec![
+                (clock::ID, sysvar_account(&clock_at(100, &epoch_schedule))),
+                (epoch_schedule::ID, sysvar_account(&epoch_schedule)),
+            ],
+        );
+
+        let storage = emulator_storage(&rpc_client, None).await;
+        let clock = storage.clock().await.unwrap();
+        assert_eq!(clock.slot, 100);
+        assert_eq!(clock.epoch, epoch_schedule.get_epoch(100));
+
+        let storage = emulator_storage(&rpc_client, Some(1000)).await;
+        let clock = storage.clock().await.unwrap();
+        assert_eq!(clock.slot, 1000);
+        assert_eq!(clock.epoch, epoch_schedule.get_epoch(1000));
+        assert_eq!(
+            clock.leader_schedule_epoch,
+            epoch_schedule.get_leader_schedule_epoch(1000)
+        );
+
+        let calls = storage.rpc_calls();
+        storage.clock().await.unwrap();
+        assert_eq!(storage.rpc_calls(), calls, "sysvars are cached");
+    }
+
+    #[tokio::test]
+    async fn snapshot_of_loaded_state() {
+        let epoch_schedule = EpochSchedule::custom(64, 64, false);
+        let existing = Pubkey::new_unique();
+        let missing = Pubkey::new_unique();
+        let account = |lamports| Account {
+            lamports,
+            ..Account::default()
+        };
+
+        let rpc_client = snapshot_client(
+            100,
+            vec![
+                (sysvar::rent::ID, sysvar_account(&Rent::default())),
+                (clock::ID, sysvar_account(&clock_at(100, &epoch_schedule))),
+                (existing, account(1)),
+            ],
+        );
+        let storage = emulator_storage(&rpc_client, None).await;
+        storage.get_account(&existing).await.unwrap();
+        storage.get_account(&missing).await.unwrap();
+
+        // The cluster state changes after the emulation
+        let changed_client = snapshot_client(
+            101,
+            vec![
+                (sysvar::rent::ID, sysvar_account(&Rent::default())),
+                (clock::ID, sysvar_account(&clock_at(101, &epoch_schedule))),
+                (existing, account(2)),
+                (missing, account(3)),
+            ],
+        );
+        let snapshot = Snapshot::collect(&changed_client, storage.program_id(), &storage)
+            .await
+            .unwrap();
+
+        let accounts: HashMap<Pubkey, Account> = snapshot
+            .accounts
+            .iter()
+            .map(|entry| (entry.pubkey, entry.account.clone()))
+            .collect();
+        assert_eq!(snapshot.slot, 100);
+        assert_eq!(accounts[&existing].lamports, 1);
+        assert!(!accounts.contains_key(&missing));
+        assert!(accounts.contains_key(&clock::ID));
+        assert!(accounts.contains_key(&sysvar::rent::ID));
+
+        for name in ["snapshot.json", "snapshot.bin"] {
+            let path = std::env::temp_dir().join(format!("{}-{name}", Pubkey::new_unique()));
+            snapshot.save(&path).unwrap();
+            let loaded = Snapshot::load(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(loaded.accounts.len(), snapshot.accounts.len());
+            assert_eq!(loaded.accounts[0].pubkey, snapshot.accounts[0].pubkey);
+        }
+    }
+
+    #[test]
+    fn call_targets_of_code() {
+        let target = [0xAB; 20];
+        let small = {
+            let mut small = [0; 20];
+            small[19] = 0x09;
+            small
+        };
+
+        let mut code = vec![0x73];
+        code.extend_from_slice(&target);
+        code.push(0x73); // Precompile address is skipped
+        code.extend_from_slice(&small);
+        code.push(0x7F); // PUSH32 data is not scanned for PUSH20
+        code.extend_from_slice(&[0x73; 32]);
+        code.push(0x73); // Repeated target is listed once
+        code.extend_from_slice(&target);
+        code.push(0x73); // Truncated PUSH20
+        code.extend_from_slice(&[0xCD; 19]);
+
+        assert_eq!(call_targets(&code), vec![Address::from(target)]);
+        assert!(call_targets(&[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn accessed_addresses() {
+        let rpc_client = snapshot_client(100, vec![]);
+        let storage = emulator_storage(&rpc_client, None).await;
+
+        let [preloaded, read, stored] = [1, 2, 3].map(|n| Address::from([n; 20]));
+        storage.initialize_cached_accounts(&[preloaded], &[]).await;
+        storage.balance(&read).await;
+        storage.storage(&stored, &U256::ONE).await;
+
+        assert!(storage.accounts.read().unwrap().contains_key(&preloaded));
+        assert_eq!(storage.address_access(), BTreeSet::from([read, stored]));
+        assert_eq!(
+            storage.storage_access(),
+            BTreeMap::from([(stored, BTreeSet::from([U256::ONE]))])
+        );
+    }
+
+    #[tokio::test]
+    async fn clock_not_found() {
+        let rpc_client = snapshot_client(100, vec![]);
+        let storage = emulator_storage(&rpc_client, None).await;
+
+        assert!(storage.clock().await.is_err());
+    }
+
+    fn instruction_key(instruction: &Instruction) -> impl Fn(usize) -> Option<Pubkey> + '_ {
+        |index| instruction.accounts.get(index).map(|a| a.pubkey)
+    }
+
+    #[test]
+    fn token_instruction_account_roles() {
+        use SolanaAccountRole::{Mint, TokenAccount};
+
+        let [source, mint, destination, owner] = [(); 4].map(|_| Pubkey::new_unique());
+        let roles = |instruction: Instruction| {
+            token_instruction_roles(&instruction.data, &instruction_key(&instruction))
+        };
+
+        let transfer =
+            spl_token::instruction::transfer(&spl_token::ID, &source, &destination, &owner, &[], 1)
+                .unwrap();
+        assert_eq!(
+            roles(transfer),
+            vec![(source, TokenAccount), (destination, TokenAccount)]
+        );
+
+        let transfer = spl_token::instruction::transfer_checked(
+            &spl_token::ID,
+            &source,
+            &mint,
+            &destination,
+            &owner,
+            &[],
+            1,
+            9,
+        )
+        .unwrap();
+        assert_eq!(
+            roles(transfer),
+            vec![
+                (source, TokenAccount),
+                (mint, Mint),
+                (destination, TokenAccount)
+            ]
+        );
+
+        let approve = spl_token::instruction::approve_checked(
+            &spl_token::ID,
+            &source,
+            &mint,
+            &destination,
+            &owner,
+            &[],
+            1,
+            9,
+        )
+        .unwrap();
+        assert_eq!(roles(approve), vec![(source, TokenAccount), (mint, Mint)]);
+
+        let mint_to =
+            spl_token::instruction::mint_to(&spl_token::ID, &mint, &destination, &owner, &[], 1)
+                .unwrap();
+        assert_eq!(
+            roles(mint_to),
+            vec![(mint, Mint), (destination, TokenAccount)]
+        );
+
+        let initialize =
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint, &owner, None, 9)
+                .unwrap();
+        assert_eq!(roles(initialize), vec![(mint, Mint)]);
+
+        let sync = spl_token::instruction::sync_native(&spl_token::ID, &source).unwrap();
+        assert!(roles(sync).is_empty());
+    }
+
+    #[test]
+    fn metaplex_instruction_pdas() {
+        use mpl_token_metadata::pda::{find_master_edition_account, find_metadata_account};
+
+        let [master_mint, mint, authority, token] = [(); 4].map(|_| Pubkey::new_unique());
+        let (metadata, _) = find_metadata_account(&mint);
+        let (edition, _) = find_master_edition_account(&mint);
+        let (master_metadata, _) = find_metadata_account(&master_mint);
+        let (master_edition, _) = find_master_edition_account(&master_mint);
+        let pdas = |instruction: Instruction| {
+            metaplex_instruction_accounts(&instruction.data, &instruction_key(&instruction))
+        };
+
+        let create_master_edition = mpl_token_metadata::instruction::create_master_edition_v3(
+            mpl_token_metadata::ID,
+            edition,
+            mint,
+            authority,
+            authority,
+            metadata,
+            authority,
+            None,
+        );
+        assert_eq!(
+            pdas(create_master_edition),
+            vec![(metadata, None), (edition, Some(MAX_MASTER_EDITION_LEN))]
+        );
+
+        let print_edition =
+            mpl_token_metadata::instruction::mint_new_edition_from_master_edition_via_token(
+                mpl_token_metadata::ID,
+                metadata,
+                edition,
+                master_edition,
+                mint,
+                authority,
+                authority,
+                authority,
+                token,
+                authority,
+                master_metadata,
+                master_mint,
+                1,
+            );
+        assert_eq!(
+            pdas(print_edition),
+            vec![
+                (metadata, Some(MAX_METADATA_LEN)),
+                (edition, Some(MAX_EDITION_LEN))
+            ]
+        );
+
+        let burn = mpl_token_metadata::instruction::burn_nft(
+            mpl_token_metadata::ID,
+            metadata,
+            authority,
+            mint,
+            token,
+            edition,
+            spl_token::ID,
+            None,
+        );
+        assert_eq!(pdas(burn), vec![(metadata, None), (edition, None)]);
+
+        assert!(pdas(mpl_token_metadata::instruction::puff_metadata_account(
+            mpl_token_metadata::ID,
+            metadata
+        ))
+        .is_empty());
+    }
 }
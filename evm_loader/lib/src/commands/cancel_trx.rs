@@ -9,9 +9,14 @@ use solana_sdk::{
     signer::Signer,
 };
 
-use evm_loader::account::State;
+use evm_loader::account::{OperatorRegistry, Parameters, State};
 
-use crate::{account_storage::account_info, commands::send_transaction, rpc::Rpc, NeonResult};
+use crate::{
+    account_storage::account_info,
+    commands::{push_if_created, send_transaction},
+    rpc::Rpc,
+    NeonResult,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CancelTrxReturn {
@@ -44,6 +49,12 @@ pub async fn execute(
             accounts_meta.push(AccountMeta::new_readonly(blocked_account_meta.key, false));
         }
     }
+
+    let (registry, _) = OperatorRegistry::address(&evm_loader);
+    push_if_created(rpc_client, &evm_loader, registry, &mut accounts_meta).await?;
+    let (parameters, _) = Parameters::address(&evm_loader);
+    push_if_created(rpc_client, &evm_loader, parameters, &mut accounts_meta).await?;
+
     for meta in &accounts_meta {
         info!("\t{:?}", meta);
     }
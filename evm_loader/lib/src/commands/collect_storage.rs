@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use evm_loader::account::{EthereumAccount, EthereumStorage, OperatorRegistry};
+use evm_loader::types::Address;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::{
+    account_storage::account_info,
+    commands::{push_if_created, send_transaction},
+    Config, Context, NeonResult,
+};
+
+/// Contract and storage account pairs in a single transaction
+const PAIRS_PER_TRANSACTION: usize = 16;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CollectStorageReturn {
+    pub total_accounts: usize,
+    pub collected_accounts: usize,
+    pub collected_lamports: u64,
+}
+
+pub async fn execute(config: &Config, context: &Context<'_>) -> NeonResult<CollectStorageReturn> {
+    let signer = context.signer()?;
+
+    let client = context
+        .rpc_client
+        .as_any()
+        .downcast_ref::<RpcClient>()
+        .expect("cast to solana_client::rpc_client::RpcClient error");
+
+    let storage_accounts = client
+        .get_program_accounts_with_config(
+            &config.evm_loader,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    vec![EthereumStorage::TAG],
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(config.commitment),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+
+    let total_accounts = storage_accounts.len();
+    info!("Found {total_accounts} storage accounts");
+
+    let mut contract_generations: HashMap<Address, Option<(Pubkey, u32)>> = HashMap::new();
+    let mut collectible = Vec::new();
+
+    for (storage_key, mut storage_account) in storage_accounts {
+        let lamports = storage_account.lamports;
+        let storage_info = account_info(&storage_key, &mut storage_account);
+        let storage = match EthereumStorage::from_account(&config.evm_loader, &storage_info) {
+            Ok(storage) => storage,
+            Err(e) => {
+                warn!("Skip {storage_key} - {e}");
+                continue;
+            }
+        };
+
+        if !contract_generations.contains_key(&storage.address) {
+            let (contract_key, _) = storage.address.find_solana_address(&config.evm_loader);
+            let contract = context
+                .rpc_client
+                .get_account_with_commitment(&contract_key, config.commitment)
+                .await?
+                .value
+                .and_then(|mut account| {
+                    let info = account_info(&contract_key, &mut account);
+                    let contract = EthereumAccount::from_account(&config.evm_loader, &info).ok()?;
+                    (!contract.rw_blocked).then_some((contract_key, contract.generation))
+                });
+
+            contract_generations.insert(storage.address, contract);
+        }
+
+        let (contract_key, generation) = match contract_generations[&storage.address] {
+            Some(contract) => contract,
+            None => {
+                info!(
+                    "Skip {storage_key} - contract {} is missing or blocked",
+                    storage.address
+                );
+                continue;
+            }
+        };
+
+        if (storage.generation == generation) && !storage.is_zeroed() {
+            continue;
+        }
+
+        info!("Collect {storage_key}: {lamports} lamports");
+        collectible.push((contract_key, storage_key, lamports));
+    }
+
+    let mut collected_accounts = 0;
+    let mut collected_lamports = 0_u64;
+
+    let (registry, _) = OperatorRegistry::address(&config.evm_loader);
+    let mut registry_accounts = Vec::new();
+    push_if_created(
+        context.rpc_client,
+        &config.evm_loader,
+        registry,
+        &mut registry_accounts,
+    )
+    .await?;
+
+    for chunk in collectible.chunks(PAIRS_PER_TRANSACTION) {
+        let mut accounts = Vec::with_capacity(1 + 2 * chunk.len() + 1);
+        accounts.push(AccountMeta::new(signer.pubkey(), true));
+        for (contract_key, storage_key, _) in chunk {
+            accounts.push(AccountMeta::new_readonly(*contract_key, false));
+            accounts.push(AccountMeta::new(*storage_key, false));
+        }
+        accounts.extend_from_slice(&registry_accounts);
+
+        let instruction = Instruction::new_with_bytes(config.evm_loader, &[0x2F], accounts);
+        match send_transaction(context.rpc_client, &*signer, &[instruction]).await {
+            Ok(signature) => {
+                info!("Collected {} accounts: {signature}", chunk.len());
+
+                collected_accounts += chunk.len();
+                collected_lamports = chunk
+                    .iter()
+                    .fold(collected_lamports, |sum, (_, _, lamports)| {
+                        sum.saturating_add(*lamports)
+                    });
+            }
+            Err(e) => warn!("Failed to collect {} accounts: {e}", chunk.len()),
+        }
+    }
+
+    Ok(CollectStorageReturn {
+        total_accounts,
+        collected_accounts,
+        collected_lamports,
+    })
+}
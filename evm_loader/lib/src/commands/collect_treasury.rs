@@ -1,19 +1,18 @@
 use crate::rpc::check_account_for_fee;
-use crate::{
-    commands::get_neon_elf::read_elf_parameters_from_account, errors::NeonError, Config, Context,
-    NeonResult,
-};
-use evm_loader::account::{MainTreasury, Treasury};
+use crate::{commands::treasury_status, Config, Context, NeonResult};
+use evm_loader::account::MainTreasury;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     message::Message,
+    pubkey::Pubkey,
     system_program,
     transaction::Transaction,
 };
 use spl_token::instruction::sync_native;
+use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CollectTreasuryReturn {
@@ -21,14 +20,17 @@ pub struct CollectTreasuryReturn {
     pub balance: u64,
 }
 
-pub async fn execute(config: &Config, context: &Context<'_>) -> NeonResult<CollectTreasuryReturn> {
-    let neon_params = read_elf_parameters_from_account(config, context).await?;
-    let signer = context.signer()?;
+/// Pools collected by one Solana transaction, limited by the transaction size
+const POOLS_PER_TRANSACTION: usize = 20;
 
-    let pool_count: u32 = neon_params
-        .get("NEON_POOL_COUNT")
-        .and_then(|value| value.parse().ok())
-        .ok_or(NeonError::IncorrectProgram(config.evm_loader))?;
+pub async fn execute(
+    config: &Config,
+    context: &Context<'_>,
+    chain_id: Option<u64>,
+) -> NeonResult<CollectTreasuryReturn> {
+    let signer = context.signer()?;
+    let status = treasury_status::execute(config, context, chain_id).await?;
+    let chain_id = status.chain_id;
 
     let main_balance_address = MainTreasury::address(&config.evm_loader).0;
 
@@ -40,55 +42,55 @@ pub async fn execute(config: &Config, context: &Context<'_>) -> NeonResult<Colle
         .downcast_ref::<RpcClient>()
         .expect("cast to solana_client::rpc_client::RpcClient error");
 
-    for i in 0..pool_count {
-        let (aux_balance_address, _) = Treasury::address(&config.evm_loader, i);
+    let mut collectable = Vec::new();
+    for pool in &status.pools {
+        if !pool.exists {
+            warn!("{:4}: not found account {}", pool.index, pool.address);
+        } else if pool.collectable == 0 {
+            info!("{:4}: skip account {}", pool.index, pool.address);
+        } else {
+            info!(
+                "{:4}: collect {} lamports from {}",
+                pool.index, pool.collectable, pool.address
+            );
+            let address = Pubkey::from_str(&pool.address).expect("treasury status pool address");
+            collectable.push((pool.index, address));
+        }
+    }
 
-        if let Some(aux_balance_account) = context
-            .rpc_client
-            .get_account_with_commitment(&aux_balance_address, config.commitment)
-            .await?
-            .value
-        {
-            let minimal_balance = context
-                .rpc_client
-                .get_minimum_balance_for_rent_exemption(aux_balance_account.data.len())
-                .await?;
-            let available_lamports = aux_balance_account.lamports.saturating_sub(minimal_balance);
-            if available_lamports > 0 {
-                info!(
-                    "{:4}: collect {} lamports from {}",
-                    i, available_lamports, aux_balance_address
-                );
-                let mut message = Message::new(
-                    &[Instruction::new_with_bincode(
-                        config.evm_loader,
-                        &(30_u8, i),
-                        vec![
-                            AccountMeta::new(main_balance_address, false),
-                            AccountMeta::new(aux_balance_address, false),
-                            AccountMeta::new_readonly(system_program::id(), false),
-                        ],
-                    )],
-                    Some(&signer.pubkey()),
-                );
-                let blockhash = context.rpc_client.get_latest_blockhash().await?;
-                message.recent_blockhash = blockhash;
+    for indices in collectable.chunks(POOLS_PER_TRANSACTION) {
+        let mut data = vec![0x38_u8];
+        data.extend_from_slice(&chain_id.to_le_bytes());
+        let mut accounts = vec![
+            AccountMeta::new(main_balance_address, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        for &(index, pool) in indices {
+            data.extend_from_slice(&index.to_le_bytes());
+            accounts.push(AccountMeta::new(pool, false));
+        }
 
-                check_account_for_fee(client, &signer.pubkey(), &message).await?;
+        let mut message = Message::new(
+            &[Instruction::new_with_bytes(
+                config.evm_loader,
+                &data,
+                accounts,
+            )],
+            Some(&signer.pubkey()),
+        );
+        let blockhash = context.rpc_client.get_latest_blockhash().await?;
+        message.recent_blockhash = blockhash;
 
-                let mut trx = Transaction::new_unsigned(message);
-                trx.try_sign(&[&*signer], blockhash)?;
-                context
-                    .rpc_client
-                    .send_and_confirm_transaction_with_spinner(&trx)
-                    .await?;
-            } else {
-                info!("{:4}: skip account {}", i, aux_balance_address);
-            }
-        } else {
-            warn!("{:4}: not found account {}", i, aux_balance_address);
-        }
+        check_account_for_fee(client, &signer.pubkey(), &message).await?;
+
+        let mut trx = Transaction::new_unsigned(message);
+        trx.try_sign(&[&*signer], blockhash)?;
+        context
+            .rpc_client
+            .send_and_confirm_transaction_with_spinner(&trx)
+            .await?;
     }
+
     let mut message = Message::new(
         &[sync_native(&spl_token::id(), &main_balance_address)?],
         Some(&signer.pubkey()),
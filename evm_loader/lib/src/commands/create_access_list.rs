@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use evm_loader::{
+    evm::is_precompile_address, executor::ExecutorState, types::hexbytes::HexBytes, types::Address,
+};
+
+use crate::{
+    account_storage::EmulatorAccountStorage,
+    commands::emulate::{check_step_limit, emulate_transaction},
+    rpc::Rpc,
+    types::{AccessListItem, TxParams},
+    NeonResult,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAccessListResult {
+    pub access_list: Vec<AccessListItem>,
+    /// Gas used by the transaction with the generated access list
+    pub used_gas: u64,
+    /// Gas used by the transaction without an access list
+    pub used_gas_without_access_list: u64,
+}
+
+impl Display for CreateAccessListResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ access_list: {}, used_gas: {}, used_gas_without_access_list: {} }}",
+            self.access_list.len(),
+            self.used_gas,
+            self.used_gas_without_access_list,
+        )
+    }
+}
+
+/// Build EIP-2930 access list of the addresses and storage slots touched by the emulation.
+/// As `eth_createAccessList` does, the sender, the target and precompiles are listed
+/// only if their storage is accessed.
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    rpc_client: &dyn Rpc,
+    evm_loader: Pubkey,
+    tx_params: TxParams,
+    token_mint: Pubkey,
+    chain_id: u64,
+    default_chain_id: u64,
+    step_limit: u64,
+    commitment: CommitmentConfig,
+    accounts: &[Address],
+    solana_accounts: &[Pubkey],
+) -> NeonResult<CreateAccessListResult> {
+    let emulate = |tx_params: TxParams| {
+        emulate_transaction(
+            rpc_client,
+            evm_loader,
+            tx_params,
+            token_mint,
+            chain_id,
+            default_chain_id,
+            step_limit,
+            commitment,
+            accounts,
+            solana_accounts,
+            &None,
+            None,
+            None,
+        )
+    };
+
+    let (emulation_result, storage) = emulate(TxParams {
+        access_list: None,
+        ..tx_params.clone()
+    })
+    .await?;
+    check_step_limit(&emulation_result)?;
+
+    let access_list = access_list(&storage, &tx_params);
+
+    let (access_list_result, _) = emulate(TxParams {
+        access_list: Some(access_list.clone()),
+        ..tx_params
+    })
+    .await?;
+    check_step_limit(&access_list_result)?;
+
+    Ok(CreateAccessListResult {
+        access_list,
+        used_gas: access_list_result.used_gas,
+        used_gas_without_access_list: emulation_result.used_gas,
+    })
+}
+
+fn access_list(storage: &EmulatorAccountStorage, tx_params: &TxParams) -> Vec<AccessListItem> {
+    let backend = ExecutorState::new(storage);
+    let is_excluded = |address: &Address| {
+        (*address == tx_params.from)
+            || (Some(*address) == tx_params.to)
+            || is_precompile_address(address)
+            || backend.is_precompile_extension(address)
+    };
+
+    let mut storage_access = storage.storage_access();
+    for address in storage.address_access() {
+        if !is_excluded(&address) {
+            storage_access.entry(address).or_default();
+        }
+    }
+
+    storage_access
+        .into_iter()
+        .map(|(address, keys)| AccessListItem {
+            address,
+            storage_keys: keys
+                .into_iter()
+                .map(|key| HexBytes::from(key.to_be_bytes().to_vec()))
+                .collect(),
+        })
+        .collect()
+}
@@ -25,13 +25,16 @@ pub async fn execute(
     evm_loader: Pubkey,
     signer: &dyn Signer,
     ether_address: &Address,
+    chain_id: u64,
+    default_chain_id: u64,
 ) -> NeonResult<CreateEtherAccountReturn> {
-    let (solana_address, nonce) = ether_address.find_solana_address(&evm_loader);
+    let (solana_address, nonce) =
+        ether_address.find_solana_address_in_chain(&evm_loader, chain_id, default_chain_id);
     debug!("Create ethereum account {solana_address} <- {ether_address} {nonce}");
 
     let create_account_v03_instruction = Instruction::new_with_bincode(
         evm_loader,
-        &(0x28_u8, ether_address.as_bytes()),
+        &(0x28_u8, ether_address.as_bytes(), chain_id),
         vec![
             AccountMeta::new(signer.pubkey(), true),
             AccountMeta::new_readonly(system_program::id(), false),
@@ -22,16 +22,19 @@ pub struct DepositReturn {
 }
 
 /// Executes subcommand `deposit`.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     rpc_client: &RpcClient,
     evm_loader: Pubkey,
     signer: &dyn Signer,
     amount: u64,
     ether_address: &Address,
+    chain_id: u64,
+    default_chain_id: u64,
+    token_mint_id: Pubkey,
 ) -> NeonResult<DepositReturn> {
-    let (ether_pubkey, _) = ether_address.find_solana_address(&evm_loader);
-
-    let token_mint_id = evm_loader::config::token_mint::id();
+    let (ether_pubkey, _) =
+        ether_address.find_solana_address_in_chain(&evm_loader, chain_id, default_chain_id);
 
     let signer_token_pubkey = get_associated_token_address(&signer.pubkey(), &token_mint_id);
     let evm_token_authority = Pubkey::find_program_address(&[b"Deposit"], &evm_loader).0;
@@ -46,6 +49,7 @@ pub async fn execute(
             evm_pool_pubkey,
             ether_address,
             ether_pubkey,
+            chain_id,
         ),
     ];
 
@@ -101,10 +105,11 @@ fn deposit_instruction(
     destination_pubkey: Pubkey,
     ether_address: &Address,
     ether_account_pubkey: Pubkey,
+    chain_id: u64,
 ) -> Instruction {
     Instruction::new_with_bincode(
         evm_loader,
-        &(0x27_u8, ether_address.as_bytes()),
+        &(0x27_u8, ether_address.as_bytes(), chain_id),
         vec![
             AccountMeta::new(source_pubkey, false),
             AccountMeta::new(destination_pubkey, false),
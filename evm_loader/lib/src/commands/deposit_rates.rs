@@ -0,0 +1,138 @@
+use evm_loader::account::DepositRates;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+};
+
+use crate::{
+    account_storage::account_info,
+    commands::{get_neon_elf::CachedElfParams, send_transaction},
+    errors::NeonError,
+    Config, Context, NeonResult,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositRatesUpdateReturn {
+    pub deposit_rates: String,
+    pub transaction: Signature,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositRateInfo {
+    pub mint: String,
+    pub chain_id: u64,
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDepositRatesReturn {
+    pub deposit_rates: String,
+    pub authority: String,
+    pub rates: Vec<DepositRateInfo>,
+}
+
+/// Create the deposit rates account. Signer must be the program upgrade authority.
+pub async fn init(
+    config: &Config,
+    context: &Context<'_>,
+    authority: &Pubkey,
+) -> NeonResult<DepositRatesUpdateReturn> {
+    let signer = context.signer()?;
+    let (deposit_rates, _) = DepositRates::address(&config.evm_loader);
+    let (program_data, _) =
+        Pubkey::find_program_address(&[config.evm_loader.as_ref()], &bpf_loader_upgradeable::id());
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x39_u8, authority.to_bytes()),
+        vec![
+            AccountMeta::new(deposit_rates, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(DepositRatesUpdateReturn {
+        deposit_rates: deposit_rates.to_string(),
+        transaction,
+    })
+}
+
+/// Set conversion rate of the mint deposit, zero `numerator` removes the mint.
+/// Signer must be the deposit rates authority.
+pub async fn set(
+    config: &Config,
+    context: &Context<'_>,
+    mint: &Pubkey,
+    chain_id: Option<u64>,
+    numerator: u64,
+    denominator: u64,
+) -> NeonResult<DepositRatesUpdateReturn> {
+    let chain_id = CachedElfParams::new(config, context)
+        .await
+        .resolve_chain_id(chain_id)?;
+
+    let signer = context.signer()?;
+    let (deposit_rates, _) = DepositRates::address(&config.evm_loader);
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x3A_u8, mint.to_bytes(), chain_id, numerator, denominator),
+        vec![
+            AccountMeta::new(deposit_rates, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(DepositRatesUpdateReturn {
+        deposit_rates: deposit_rates.to_string(),
+        transaction,
+    })
+}
+
+pub async fn get_rates(
+    config: &Config,
+    context: &Context<'_>,
+) -> NeonResult<GetDepositRatesReturn> {
+    let (deposit_rates_key, _) = DepositRates::address(&config.evm_loader);
+
+    let mut account = context
+        .rpc_client
+        .get_account_with_commitment(&deposit_rates_key, config.commitment)
+        .await?
+        .value
+        .ok_or(NeonError::AccountNotFound(deposit_rates_key))?;
+
+    let info = account_info(&deposit_rates_key, &mut account);
+    let deposit_rates = DepositRates::from_account(&config.evm_loader, &info)?;
+
+    let rates = deposit_rates
+        .rates()?
+        .into_iter()
+        .map(|rate| DepositRateInfo {
+            mint: rate.mint.to_string(),
+            chain_id: rate.chain_id,
+            numerator: rate.numerator,
+            denominator: rate.denominator,
+        })
+        .collect();
+
+    Ok(GetDepositRatesReturn {
+        deposit_rates: deposit_rates_key.to_string(),
+        authority: deposit_rates.authority.to_string(),
+        rates,
+    })
+}
@@ -0,0 +1,117 @@
+use evm_loader::{account::DepositRates, types::Address};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    system_instruction, system_program,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+use crate::{
+    commands::{get_neon_elf::CachedElfParams, send_transaction},
+    Config, Context, NeonResult,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositSplReturn {
+    pub mint: String,
+    pub transaction: Signature,
+}
+
+/// Executes subcommand `deposit-spl`.
+/// Deposits `amount` of `mint` tokens from the signer associated token account.
+/// Lamports of the signer are wrapped first if `mint` is the native mint.
+pub async fn execute(
+    config: &Config,
+    context: &Context<'_>,
+    mint: &Pubkey,
+    amount: u64,
+    ether_address: &Address,
+    chain_id: Option<u64>,
+) -> NeonResult<DepositSplReturn> {
+    let elf_params = CachedElfParams::new(config, context).await;
+    let default_chain_id = elf_params.default_chain_id();
+    let chain_id = elf_params.resolve_chain_id(chain_id)?;
+
+    let signer = context.signer()?;
+    let (ether_pubkey, _) =
+        ether_address.find_solana_address_in_chain(&config.evm_loader, chain_id, default_chain_id);
+    let (deposit_rates, _) = DepositRates::address(&config.evm_loader);
+
+    let source = get_associated_token_address(&signer.pubkey(), mint);
+    let (deposit_authority, _) = Pubkey::find_program_address(&[b"Deposit"], &config.evm_loader);
+    let pool = get_associated_token_address(&deposit_authority, mint);
+
+    let mut instructions = Vec::new();
+
+    if !account_exists(config, context, &pool).await? {
+        instructions.push(create_associated_token_account(
+            &signer.pubkey(),
+            &deposit_authority,
+            mint,
+            &spl_token::id(),
+        ));
+    }
+
+    if *mint == spl_token::native_mint::id() {
+        if !account_exists(config, context, &source).await? {
+            instructions.push(create_associated_token_account(
+                &signer.pubkey(),
+                &signer.pubkey(),
+                mint,
+                &spl_token::id(),
+            ));
+        }
+        instructions.push(system_instruction::transfer(
+            &signer.pubkey(),
+            &source,
+            amount,
+        ));
+        instructions.push(spl_token::instruction::sync_native(
+            &spl_token::id(),
+            &source,
+        )?);
+    }
+
+    instructions.push(spl_token::instruction::approve(
+        &spl_token::id(),
+        &source,
+        &ether_pubkey,
+        &signer.pubkey(),
+        &[],
+        amount,
+    )?);
+    instructions.push(Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x3B_u8, ether_address.as_bytes(), chain_id),
+        vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(ether_pubkey, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(deposit_rates, false),
+        ],
+    ));
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &instructions).await?;
+
+    Ok(DepositSplReturn {
+        mint: mint.to_string(),
+        transaction,
+    })
+}
+
+async fn account_exists(config: &Config, context: &Context<'_>, key: &Pubkey) -> NeonResult<bool> {
+    let account = context
+        .rpc_client
+        .get_account_with_commitment(key, config.commitment)
+        .await?
+        .value;
+
+    Ok(account.is_some())
+}
@@ -1,7 +1,8 @@
 use std::fmt::{Display, Formatter};
+use std::path::Path;
 
 use ethnum::U256;
-use log::{debug, info};
+use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 
@@ -9,8 +10,11 @@ use evm_loader::evm::tracing::TracerTypeOpt;
 use evm_loader::evm::tracing::{AccountOverrides, BlockOverrides};
 use evm_loader::{
     account_storage::AccountStorage,
-    config::{EVM_STEPS_MIN, PAYMENT_TO_TREASURE},
-    evm::{ExitStatus, Machine},
+    error::Error,
+    evm::{
+        compute_meter::{MAX_COMPUTE_UNITS, MAX_HEAP_SIZE},
+        ExitStatus, Machine,
+    },
     executor::{Action, ExecutorState},
     gasometer::LAMPORTS_PER_SIGNATURE,
     types::{Address, Transaction},
@@ -18,10 +22,10 @@ use evm_loader::{
 
 use crate::types::TxParams;
 use crate::{
-    account_storage::{EmulatorAccountStorage, NeonAccount, SolanaAccount},
+    account_storage::{EmulatorAccountStorage, NeonAccount, SolanaAccount, FAKE_OPERATOR_ADDRESS},
     errors::NeonError,
-    rpc::Rpc,
-    syscall_stubs::Stubs,
+    rpc::{Rpc, Snapshot},
+    syscall_stubs::with_syscall_stubs,
     NeonResult,
 };
 
@@ -33,7 +37,22 @@ pub struct EmulationResult {
     pub exit_status: String,
     pub steps_executed: u64,
     pub used_gas: u64,
+    /// `used_gas * gas_price`, charged to the sender and credited to the operator
+    #[serde(default)]
+    pub gas_payment: U256,
     pub actions: Vec<Action>,
+    /// Estimated compute units of each iteration
+    #[serde(default)]
+    pub compute_units: Vec<u64>,
+    /// Estimated peak heap usage
+    #[serde(default)]
+    pub heap_size: usize,
+    /// Call depth where the execution stopped, reported on the step limit only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_depth: Option<usize>,
+    /// Program counter where the execution stopped, reported on the step limit only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pc: Option<usize>,
 }
 
 impl Display for EmulationResult {
@@ -57,7 +76,12 @@ impl From<evm_loader::evm::tracing::EmulationResult> for EmulationResult {
             result: value.exit_status.into_result().unwrap_or_default(),
             steps_executed: value.steps_executed,
             used_gas: value.used_gas,
+            gas_payment: value.gas_payment,
             actions: value.actions,
+            compute_units: value.compute_units,
+            heap_size: value.heap_size,
+            call_depth: value.call_depth,
+            pc: value.pc,
         }
     }
 }
@@ -66,7 +90,11 @@ impl From<evm_loader::evm::tracing::EmulationResult> for EmulationResult {
 pub struct EmulationResultWithAccounts {
     pub accounts: Vec<NeonAccount>,
     pub solana_accounts: Vec<SolanaAccount>,
+    /// SPL token accounts, mints and Metaplex accounts, a subset of `solana_accounts`
     pub token_accounts: Vec<SolanaAccount>,
+    /// Number of RPC requests made by the emulation
+    #[serde(default)]
+    pub rpc_calls: u64,
     #[serde(flatten)]
     pub emulation_result: EmulationResult,
 }
@@ -116,12 +144,15 @@ pub async fn execute(
     tx_params: TxParams,
     token_mint: Pubkey,
     chain_id: u64,
+    default_chain_id: u64,
     step_limit: u64,
     commitment: CommitmentConfig,
     accounts: &[Address],
     solana_accounts: &[Pubkey],
     block_overrides: &Option<BlockOverrides>,
     state_overrides: Option<AccountOverrides>,
+    snapshot_path: Option<&Path>,
+    partial_on_step_limit: bool,
 ) -> NeonResult<EmulationResultWithAccounts> {
     let (emulation_result, storage) = emulate_transaction(
         rpc_client,
@@ -129,6 +160,7 @@ pub async fn execute(
         tx_params,
         token_mint,
         chain_id,
+        default_chain_id,
         step_limit,
         commitment,
         accounts,
@@ -138,13 +170,36 @@ pub async fn execute(
         None,
     )
     .await?;
-    let accounts = storage.accounts.borrow().values().cloned().collect();
-    let solana_accounts = storage.solana_accounts.borrow().values().cloned().collect();
+
+    if !partial_on_step_limit {
+        check_step_limit(&emulation_result)?;
+    }
+
+    if let Some(path) = snapshot_path {
+        Snapshot::collect(rpc_client, &evm_loader, &storage)
+            .await?
+            .save(path)?;
+    }
+
+    let accounts = storage.accounts.read().unwrap().values().cloned().collect();
+    let solana_accounts: Vec<SolanaAccount> = storage
+        .solana_accounts
+        .read()
+        .unwrap()
+        .values()
+        .cloned()
+        .collect();
+    let token_accounts = solana_accounts
+        .iter()
+        .filter(|account| account.role().is_token())
+        .cloned()
+        .collect();
 
     Ok(EmulationResultWithAccounts {
         accounts,
         solana_accounts,
-        token_accounts: vec![],
+        token_accounts,
+        rpc_calls: storage.rpc_calls(),
         emulation_result: emulation_result.into(),
     })
 }
@@ -156,6 +211,7 @@ pub(crate) async fn emulate_transaction<'a>(
     tx_params: TxParams,
     token_mint: Pubkey,
     chain_id: u64,
+    default_chain_id: u64,
     step_limit: u64,
     commitment: CommitmentConfig,
     accounts: &[Address],
@@ -170,13 +226,12 @@ pub(crate) async fn emulate_transaction<'a>(
     ),
     NeonError,
 > {
-    setup_syscall_stubs(rpc_client).await?;
-
     let storage = EmulatorAccountStorage::with_accounts(
         rpc_client,
         evm_loader,
         token_mint,
         chain_id,
+        default_chain_id,
         commitment,
         accounts,
         solana_accounts,
@@ -185,8 +240,9 @@ pub(crate) async fn emulate_transaction<'a>(
     )
     .await?;
 
-    emulate_trx(tx_params, &storage, chain_id, step_limit, tracer)
-        .await
+    let emulation = emulate_trx(tx_params, &storage, chain_id, step_limit, tracer);
+    with_syscall_stubs(&storage, emulation)
+        .await?
         .map(move |result| (result, storage))
 }
 
@@ -197,77 +253,54 @@ pub(crate) async fn emulate_trx<'a>(
     step_limit: u64,
     tracer: TracerTypeOpt,
 ) -> Result<evm_loader::evm::tracing::EmulationResult, NeonError> {
-    let (exit_status, actions, steps_executed) = {
-        let mut backend = ExecutorState::new(storage);
-        let trx_payload = if tx_params.access_list.is_some() {
-            let access_list = tx_params
-                .access_list
-                .expect("access_list is present")
-                .into_iter()
-                .map(|item| {
-                    (
-                        item.address,
-                        item.storage_keys
-                            .into_iter()
-                            .map(|k| {
-                                evm_loader::types::StorageKey::try_from(k)
-                                    .expect("key to be correct")
-                            })
-                            .collect(),
-                    )
-                })
-                .collect();
-            evm_loader::types::TransactionPayload::AccessList(evm_loader::types::AccessListTx {
-                nonce: match tx_params.nonce {
-                    Some(nonce) => nonce,
-                    None => storage.nonce(&tx_params.from).await,
-                },
-                gas_price: U256::ZERO,
-                gas_limit: tx_params.gas_limit.unwrap_or(U256::MAX),
-                target: tx_params.to,
-                value: tx_params.value.unwrap_or_default(),
-                call_data: evm_loader::evm::Buffer::from_slice(&tx_params.data.unwrap_or_default()),
-                r: U256::default(),
-                s: U256::default(),
-                chain_id: chain_id.into(),
-                recovery_id: u8::default(),
-                access_list,
-            })
-        } else {
-            evm_loader::types::TransactionPayload::Legacy(evm_loader::types::LegacyTx {
-                nonce: match tx_params.nonce {
-                    Some(nonce) => nonce,
-                    None => storage.nonce(&tx_params.from).await,
-                },
-                gas_price: U256::ZERO,
-                gas_limit: tx_params.gas_limit.unwrap_or(U256::MAX),
-                target: tx_params.to,
-                value: tx_params.value.unwrap_or_default(),
-                call_data: evm_loader::evm::Buffer::from_slice(&tx_params.data.unwrap_or_default()),
-                v: U256::default(),
-                r: U256::default(),
-                s: U256::default(),
-                chain_id: Some(chain_id.into()),
-                recovery_id: u8::default(),
-            })
-        };
+    prefetch_transaction_accounts(&tx_params, storage).await;
 
-        let mut trx = Transaction {
-            transaction: trx_payload,
-            byte_len: usize::default(),
-            hash: <[u8; 32]>::default(),
-            signed_hash: <[u8; 32]>::default(),
-        };
+    let origin = tx_params.from;
+    check_fee_params(&tx_params)?;
+    let gas_price = tx_params.gas_price();
+    check_gas_balance(&tx_params, storage).await?;
 
-        let mut evm = Machine::new(&mut trx, tx_params.from, &mut backend, tracer).await?;
+    let mut trx = emulated_transaction(tx_params, storage, chain_id).await;
+
+    let (exit_status, mut actions, steps_executed, position, compute_units, heap_size) = {
+        let mut backend = ExecutorState::new(storage);
+        let mut evm = Machine::new(&mut trx, origin, &mut backend, tracer).await?;
+        let evm_steps_min = storage.parameters().await.evm_steps_min;
+        evm.compute_meter()
+            .lock()
+            .expect("compute meter lock is poisoned")
+            .set_iteration_steps(evm_steps_min);
 
         let (result, steps_executed) = evm.execute(step_limit, &mut backend).await?;
-        if result == ExitStatus::StepLimit {
-            return Err(NeonError::TooManySteps);
-        }
 
+        let compute_meter = evm.compute_meter();
+        let compute_meter = compute_meter
+            .lock()
+            .expect("compute meter lock is poisoned");
+
+        // The step limit interrupts the execution in the middle of a call
+        let (steps_executed, position) = if result == ExitStatus::StepLimit {
+            let position = (evm.call_depth(), evm.pc());
+            warn!(
+                "Step limit {step_limit} is reached at depth {}, pc {}",
+                position.0, position.1
+            );
+            (steps_executed.min(step_limit), Some(position))
+        } else {
+            (steps_executed, None)
+        };
+
+        // Open call frames of the interrupted execution only hold revert points into the actions,
+        // their actions are already appended as if the frames were committed
         let actions = backend.into_actions();
-        (result, actions, steps_executed)
+        (
+            result,
+            actions,
+            steps_executed,
+            position,
+            compute_meter.iterations(),
+            compute_meter.peak_heap(),
+        )
     };
 
     debug!("Execute done, result={exit_status:?}");
@@ -275,24 +308,297 @@ pub(crate) async fn emulate_trx<'a>(
 
     let accounts_operations = storage.calc_accounts_operations(&actions).await;
 
-    let max_iterations = (steps_executed + (EVM_STEPS_MIN - 1)) / EVM_STEPS_MIN;
-    let steps_gas = max_iterations * (LAMPORTS_PER_SIGNATURE + PAYMENT_TO_TREASURE);
+    let parameters = storage.parameters().await;
+    let max_iterations =
+        (steps_executed + (parameters.evm_steps_min - 1)) / parameters.evm_steps_min;
+    let steps_gas = max_iterations * (LAMPORTS_PER_SIGNATURE + parameters.payment_to_treasure);
     let begin_end_gas = 2 * LAMPORTS_PER_SIGNATURE;
     let actions_gas = storage.apply_actions(&actions).await;
     let accounts_gas = storage.apply_accounts_operations(accounts_operations).await;
     info!("Gas - steps: {steps_gas}, actions: {actions_gas}, accounts: {accounts_gas}");
+    info!("RPC calls: {}", storage.rpc_calls());
+    check_compute_limits(&compute_units, heap_size);
+
+    let used_gas = steps_gas + begin_end_gas + actions_gas + accounts_gas;
+    let gas_payment = U256::from(used_gas).saturating_mul(gas_price);
+    if (gas_payment != 0) && (exit_status != ExitStatus::StepLimit) {
+        let balance = storage.balance(&origin).await;
+        append_gas_payment(&mut actions, origin, balance, gas_payment)?;
+    }
 
     Ok(evm_loader::evm::tracing::EmulationResult {
         exit_status,
         steps_executed,
-        used_gas: steps_gas + begin_end_gas + actions_gas + accounts_gas,
+        used_gas,
+        gas_payment,
         actions,
+        compute_units,
+        heap_size,
+        call_depth: position.map(|(depth, _)| depth),
+        pc: position.map(|(_, pc)| pc),
     })
 }
 
-pub(crate) async fn setup_syscall_stubs(rpc_client: &dyn Rpc) -> Result<(), NeonError> {
-    let syscall_stubs = Stubs::new(rpc_client).await?;
-    solana_sdk::program_stubs::set_syscall_stubs(syscall_stubs);
+/// Results of the execution interrupted by the step limit are partial
+pub(crate) fn check_step_limit(
+    emulation_result: &evm_loader::evm::tracing::EmulationResult,
+) -> NeonResult<()> {
+    if emulation_result.exit_status == ExitStatus::StepLimit {
+        return Err(NeonError::TooManySteps);
+    }
+
+    Ok(())
+}
+
+/// Either the legacy `gas_price` or EIP-1559 fees, the priority fee can't exceed the max fee
+fn check_fee_params(tx_params: &TxParams) -> NeonResult<()> {
+    let error = |message: &str| Err(NeonError::TxParametersParsingError(message.to_string()));
+
+    match (
+        tx_params.gas_price,
+        tx_params.max_fee_per_gas,
+        tx_params.max_priority_fee_per_gas,
+    ) {
+        (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+            error("gas price can't be combined with EIP-1559 fees")
+        }
+        (None, None, Some(_)) => error("max priority fee requires max fee"),
+        (None, Some(max_fee), Some(priority_fee)) if priority_fee > max_fee => {
+            error("max priority fee exceeds max fee")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The sender must be able to pay for `gas_limit` at the max price on top of the value.
+/// Without `gas_limit` only the value is checked, the used gas is checked after the execution.
+async fn check_gas_balance(
+    tx_params: &TxParams,
+    storage: &EmulatorAccountStorage<'_>,
+) -> NeonResult<()> {
+    let max_gas_price = tx_params.max_gas_price();
+    if max_gas_price == 0 {
+        return Ok(());
+    }
+
+    let required = tx_params
+        .gas_limit
+        .unwrap_or_default()
+        .saturating_mul(max_gas_price)
+        .saturating_add(tx_params.value.unwrap_or_default());
+    if storage.balance(&tx_params.from).await < required {
+        return Err(Error::InsufficientBalance(tx_params.from, required).into());
+    }
+
+    Ok(())
+}
+
+/// Mirrors `transfer_gas_payment`: the sender pays the operator after the state is applied
+fn append_gas_payment(
+    actions: &mut Vec<Action>,
+    origin: Address,
+    balance: U256,
+    gas_payment: U256,
+) -> Result<(), Error> {
+    if balance_after_actions(balance, &origin, actions) < gas_payment {
+        return Err(Error::InsufficientBalance(origin, gas_payment));
+    }
+
+    actions.push(Action::NeonTransfer {
+        source: origin,
+        target: FAKE_OPERATOR_ADDRESS,
+        value: gas_payment,
+    });
 
     Ok(())
 }
+
+/// Sender balance after the execution, before the gas payment
+fn balance_after_actions(balance: U256, origin: &Address, actions: &[Action]) -> U256 {
+    actions
+        .iter()
+        .fold(balance, |balance, action| match action {
+            Action::NeonTransfer {
+                source,
+                target,
+                value,
+            } if (source == origin) && (target != origin) => balance.saturating_sub(*value),
+            Action::NeonTransfer {
+                source,
+                target,
+                value,
+            } if (source != origin) && (target == origin) => balance.saturating_add(*value),
+            Action::NeonWithdraw { source, value } if source == origin => {
+                balance.saturating_sub(*value)
+            }
+            _ => balance,
+        })
+}
+
+fn check_compute_limits(compute_units: &[u64], heap_size: usize) {
+    for (index, units) in compute_units.iter().enumerate() {
+        if *units > MAX_COMPUTE_UNITS {
+            warn!("Iteration {index} is likely to exceed compute units limit: {units} > {MAX_COMPUTE_UNITS}");
+        }
+    }
+
+    if heap_size > MAX_HEAP_SIZE {
+        warn!("Transaction is likely to exceed heap limit: {heap_size} > {MAX_HEAP_SIZE}");
+    }
+}
+
+/// Transaction as it is executed by the emulator, EIP-1559 fees are converted to the gas price
+pub(crate) async fn emulated_transaction(
+    tx_params: TxParams,
+    storage: &EmulatorAccountStorage<'_>,
+    chain_id: u64,
+) -> Transaction {
+    let gas_price = tx_params.gas_price();
+    let trx_payload = if tx_params.access_list.is_some() {
+        let access_list = tx_params
+            .access_list
+            .expect("access_list is present")
+            .into_iter()
+            .map(|item| {
+                (
+                    item.address,
+                    item.storage_keys
+                        .into_iter()
+                        .map(|k| {
+                            evm_loader::types::StorageKey::try_from(k).expect("key to be correct")
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        evm_loader::types::TransactionPayload::AccessList(evm_loader::types::AccessListTx {
+            nonce: match tx_params.nonce {
+                Some(nonce) => nonce,
+                None => storage.nonce(&tx_params.from).await,
+            },
+            gas_price,
+            gas_limit: tx_params.gas_limit.unwrap_or(U256::MAX),
+            target: tx_params.to,
+            value: tx_params.value.unwrap_or_default(),
+            call_data: evm_loader::evm::Buffer::from_slice(&tx_params.data.unwrap_or_default()),
+            r: U256::default(),
+            s: U256::default(),
+            chain_id: chain_id.into(),
+            recovery_id: u8::default(),
+            access_list,
+        })
+    } else {
+        evm_loader::types::TransactionPayload::Legacy(evm_loader::types::LegacyTx {
+            nonce: match tx_params.nonce {
+                Some(nonce) => nonce,
+                None => storage.nonce(&tx_params.from).await,
+            },
+            gas_price,
+            gas_limit: tx_params.gas_limit.unwrap_or(U256::MAX),
+            target: tx_params.to,
+            value: tx_params.value.unwrap_or_default(),
+            call_data: evm_loader::evm::Buffer::from_slice(&tx_params.data.unwrap_or_default()),
+            v: U256::default(),
+            r: U256::default(),
+            s: U256::default(),
+            chain_id: Some(chain_id.into()),
+            recovery_id: u8::default(),
+        })
+    };
+
+    Transaction {
+        transaction: trx_payload,
+        byte_len: usize::default(),
+        hash: <[u8; 32]>::default(),
+        signed_hash: <[u8; 32]>::default(),
+    }
+}
+
+/// Load accounts known before the execution in one batch: sender, target and the access list
+pub(crate) async fn prefetch_transaction_accounts(
+    tx_params: &TxParams,
+    storage: &EmulatorAccountStorage<'_>,
+) {
+    let mut addresses = vec![tx_params.from];
+    addresses.extend(tx_params.to);
+
+    let mut storage_slots = vec![];
+    for item in tx_params.access_list.iter().flatten() {
+        addresses.push(item.address);
+        for key in &item.storage_keys {
+            if let Ok(key) = <[u8; 32]>::try_from(key.as_slice()) {
+                storage_slots.push((item.address, U256::from_be_bytes(key)));
+            }
+        }
+    }
+
+    storage.prefetch_accounts(&addresses, &storage_slots).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx_params(
+        gas_price: Option<u32>,
+        max_fee_per_gas: Option<u32>,
+        max_priority_fee_per_gas: Option<u32>,
+    ) -> TxParams {
+        TxParams {
+            nonce: None,
+            from: Address::from([1; 20]),
+            to: None,
+            data: None,
+            value: None,
+            gas_limit: None,
+            gas_price: gas_price.map(U256::from),
+            max_fee_per_gas: max_fee_per_gas.map(U256::from),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.map(U256::from),
+            access_list: None,
+        }
+    }
+
+    #[test]
+    fn fee_params() {
+        let check = |gas_price, max_fee, priority_fee| {
+            let tx_params = tx_params(gas_price, max_fee, priority_fee);
+            check_fee_params(&tx_params).map(|_| tx_params.gas_price())
+        };
+
+        assert_eq!(check(None, None, None).unwrap(), 0);
+        assert_eq!(check(Some(10), None, None).unwrap(), 10);
+        assert_eq!(check(None, Some(10), None).unwrap(), 10);
+        assert_eq!(check(None, Some(10), Some(3)).unwrap(), 3);
+
+        assert!(check(Some(10), Some(10), None).is_err());
+        assert!(check(Some(10), None, Some(3)).is_err());
+        assert!(check(None, None, Some(3)).is_err());
+        assert!(check(None, Some(10), Some(11)).is_err());
+    }
+
+    #[test]
+    fn gas_payment_action() {
+        let origin = Address::from([1; 20]);
+        let other = Address::from([2; 20]);
+        let transfer = |source, target, value: u32| Action::NeonTransfer {
+            source,
+            target,
+            value: U256::from(value),
+        };
+
+        let mut actions = vec![transfer(origin, other, 60), transfer(other, origin, 10)];
+        assert_eq!(
+            balance_after_actions(U256::new(100), &origin, &actions),
+            U256::new(50)
+        );
+
+        assert!(append_gas_payment(&mut actions, origin, U256::new(100), U256::new(51)).is_err());
+        assert_eq!(actions.len(), 2);
+
+        append_gas_payment(&mut actions, origin, U256::new(100), U256::new(50)).unwrap();
+        assert_eq!(
+            format!("{:?}", actions.last().unwrap()),
+            format!("{:?}", transfer(origin, FAKE_OPERATOR_ADDRESS, 50))
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt::{Display, Formatter};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, pubkey::Pubkey,
+    system_instruction::MAX_PERMITTED_DATA_LENGTH,
+};
+
+use evm_loader::{
+    account::State,
+    config::EVM_STEPS_LAST_ITERATION_MAX,
+    evm::{compute_meter::ComputeMeterType, ExitStatus, Machine},
+    executor::{Action, ExecutorState},
+    types::{Address, Transaction},
+};
+
+use crate::{
+    account_storage::EmulatorAccountStorage,
+    commands::emulate::{emulated_transaction, prefetch_transaction_accounts},
+    errors::NeonError,
+    rpc::Rpc,
+    syscall_stubs::with_syscall_stubs,
+    types::TxParams,
+    NeonResult,
+};
+
+/// Holder account size used when it is not specified
+pub const DEFAULT_HOLDER_SIZE: usize = 256 * 1024;
+
+type EmulatorBackend<'a, 'r> = ExecutorState<'r, EmulatorAccountStorage<'a>>;
+type EmulatorEvm<'a, 'r> = Machine<EmulatorBackend<'a, 'r>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IterationInfo {
+    pub steps_executed: u64,
+    /// Serialized `ExecutorState` size after the iteration
+    pub evm_state_len: usize,
+    /// Serialized `Machine` size after the iteration
+    pub evm_machine_len: usize,
+    /// Estimated compute units of the iteration
+    pub compute_units: u64,
+    /// Estimated peak heap usage of the iteration
+    pub heap_size: usize,
+}
+
+impl IterationInfo {
+    fn new(
+        steps_executed: u64,
+        evm_state_len: usize,
+        evm_machine_len: usize,
+        compute_meter: &ComputeMeterType,
+    ) -> Self {
+        let compute_meter = compute_meter
+            .lock()
+            .expect("compute meter lock is poisoned");
+        Self {
+            steps_executed,
+            evm_state_len,
+            evm_machine_len,
+            compute_units: compute_meter.iterations().iter().sum(),
+            heap_size: compute_meter.peak_heap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IterativeEmulationResult {
+    pub exit_status: String,
+    pub steps_executed: u64,
+    pub step_size: u64,
+    pub holder_size: usize,
+    /// Space left for the EVM state in the holder after the blocked accounts
+    pub evm_data_capacity: usize,
+    pub max_evm_data_len: usize,
+    /// The first iteration only creates the EVM, as `TransactionStep*` instructions do
+    pub iterations: Vec<IterationInfo>,
+    /// Differences between the iterative and the single run execution
+    pub divergence: Vec<String>,
+}
+
+impl Display for IterativeEmulationResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ exit_status: {}, steps_executed: {}, iterations: {}, evm_data: {}/{}, divergence: {} }}",
+            self.exit_status,
+            self.steps_executed,
+            self.iterations.len(),
+            self.max_evm_data_len,
+            self.evm_data_capacity,
+            self.divergence.len(),
+        )
+    }
+}
+
+struct Execution {
+    exit_status: ExitStatus,
+    steps_executed: u64,
+    actions: Vec<Action>,
+}
+
+/// Execute the transaction twice: in a single run and in iterations of `step_size` steps,
+/// serializing `ExecutorState` and `Machine` between iterations exactly as the program does
+/// with the `State` account.
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    rpc_client: &dyn Rpc,
+    evm_loader: Pubkey,
+    tx_params: TxParams,
+    token_mint: Pubkey,
+    chain_id: u64,
+    default_chain_id: u64,
+    step_limit: u64,
+    commitment: CommitmentConfig,
+    accounts: &[Address],
+    solana_accounts: &[Pubkey],
+    step_size: Option<u64>,
+    holder_size: usize,
+) -> NeonResult<IterativeEmulationResult> {
+    let storage = EmulatorAccountStorage::with_accounts(
+        rpc_client,
+        evm_loader,
+        token_mint,
+        chain_id,
+        default_chain_id,
+        commitment,
+        accounts,
+        solana_accounts,
+        &None,
+        None,
+    )
+    .await?;
+
+    let emulation = async {
+        prefetch_transaction_accounts(&tx_params, &storage).await;
+
+        let step_size = match step_size {
+            Some(step_size) => step_size,
+            None => storage.parameters().await.evm_steps_min,
+        };
+        if step_size == 0 {
+            return Err(NeonError::TxParametersParsingError(
+                "step size must be positive".to_string(),
+            ));
+        }
+
+        let origin = tx_params.from;
+        let mut trx = emulated_transaction(tx_params, &storage, chain_id).await;
+        let mut iterative_trx = trx.clone();
+
+        let single = execute_single(&storage, &mut trx, origin, step_limit).await?;
+        let (iterative, iterations) =
+            execute_iterations(&storage, &mut iterative_trx, origin, step_size, step_limit).await?;
+
+        let accounts_len =
+            storage.accounts.read().unwrap().len() + storage.solana_accounts.read().unwrap().len();
+        let evm_data_capacity = State::evm_data_capacity(holder_size, accounts_len);
+
+        let mut divergence = compare(&single, &iterative);
+        for (index, iteration) in iterations.iter().enumerate() {
+            let len = iteration.evm_state_len + iteration.evm_machine_len;
+            if len > evm_data_capacity {
+                divergence.push(format!(
+                    "iteration {index}: EVM state of {len} bytes exceeds holder capacity {evm_data_capacity}"
+                ));
+            }
+        }
+
+        for message in &divergence {
+            warn!("{message}");
+        }
+        info!(
+            "{} iterations, {} steps executed",
+            iterations.len(),
+            iterative.steps_executed
+        );
+
+        Ok(IterativeEmulationResult {
+            exit_status: iterative.exit_status.status().to_string(),
+            steps_executed: iterative.steps_executed,
+            step_size,
+            holder_size,
+            evm_data_capacity,
+            max_evm_data_len: iterations
+                .iter()
+                .map(|i| i.evm_state_len + i.evm_machine_len)
+                .max()
+                .unwrap_or_default(),
+            iterations,
+            divergence,
+        })
+    };
+
+    with_syscall_stubs(&storage, emulation).await?
+}
+
+async fn execute_single(
+    storage: &EmulatorAccountStorage<'_>,
+    trx: &mut Transaction,
+    origin: Address,
+    step_limit: u64,
+) -> NeonResult<Execution> {
+    let mut backend = ExecutorState::new(storage);
+    let mut evm = Machine::new(trx, origin, &mut backend, None).await?;
+
+    let (exit_status, steps_executed) = evm.execute(step_limit, &mut backend).await?;
+    if exit_status == ExitStatus::StepLimit {
+        return Err(NeonError::TooManySteps);
+    }
+
+    Ok(Execution {
+        exit_status,
+        steps_executed,
+        actions: backend.into_actions(),
+    })
+}
+
+/// Mirrors `transaction_step::do_begin` and `transaction_step::do_continue`
+async fn execute_iterations(
+    storage: &EmulatorAccountStorage<'_>,
+    trx: &mut Transaction,
+    origin: Address,
+    step_size: u64,
+    step_limit: u64,
+) -> NeonResult<(Execution, Vec<IterationInfo>)> {
+    #[allow(clippy::cast_possible_truncation)]
+    let mut buffer = vec![0_u8; MAX_PERMITTED_DATA_LENGTH as usize];
+    let mut iterations = Vec::new();
+
+    let (mut evm_state_len, mut evm_machine_len, compute_meter) = {
+        let mut backend = ExecutorState::new(storage);
+        let evm = Machine::new(trx, origin, &mut backend, None).await?;
+
+        let (evm_state_len, evm_machine_len) = serialize_evm_state(&mut buffer, &backend, &evm)?;
+        (evm_state_len, evm_machine_len, evm.compute_meter())
+    };
+    iterations.push(IterationInfo::new(
+        0,
+        evm_state_len,
+        evm_machine_len,
+        &compute_meter,
+    ));
+
+    let mut total_steps = 0_u64;
+    loop {
+        let (mut backend, mut evm) =
+            deserialize_evm_state(&buffer, evm_state_len, evm_machine_len, storage).await?;
+
+        let (result, steps_executed) = match backend.exit_status() {
+            Some(status) => (status.clone(), 0_u64),
+            None => evm.execute(step_size, &mut backend).await?,
+        };
+        total_steps += steps_executed;
+
+        if (result != ExitStatus::StepLimit) && (steps_executed > 0) {
+            backend.set_exit_status(result.clone());
+        }
+
+        if steps_executed > 0 {
+            (evm_state_len, evm_machine_len) = serialize_evm_state(&mut buffer, &backend, &evm)?;
+        }
+
+        iterations.push(IterationInfo::new(
+            steps_executed,
+            evm_state_len,
+            evm_machine_len,
+            &evm.compute_meter(),
+        ));
+
+        match result {
+            ExitStatus::StepLimit if total_steps >= step_limit => {
+                return Err(NeonError::TooManySteps);
+            }
+            ExitStatus::StepLimit => {}
+            _ if steps_executed > EVM_STEPS_LAST_ITERATION_MAX => {}
+            exit_status => {
+                let execution = Execution {
+                    exit_status,
+                    steps_executed: total_steps,
+                    actions: backend.into_actions(),
+                };
+                return Ok((execution, iterations));
+            }
+        }
+    }
+}
+
+fn serialize_evm_state(
+    buffer: &mut [u8],
+    backend: &EmulatorBackend,
+    evm: &EmulatorEvm,
+) -> NeonResult<(usize, usize)> {
+    let evm_state_len = backend.serialize_into(buffer)?;
+    let evm_machine_len = evm.serialize_into(&mut buffer[evm_state_len..])?;
+
+    Ok((evm_state_len, evm_machine_len))
+}
+
+async fn deserialize_evm_state<'a, 'r>(
+    buffer: &[u8],
+    evm_state_len: usize,
+    evm_machine_len: usize,
+    storage: &'r EmulatorAccountStorage<'a>,
+) -> NeonResult<(EmulatorBackend<'a, 'r>, EmulatorEvm<'a, 'r>)> {
+    let backend = ExecutorState::deserialize_from(&buffer[..evm_state_len], storage)?;
+
+    let evm_data = &buffer[evm_state_len..][..evm_machine_len];
+    let evm = Machine::deserialize_from(evm_data, &backend).await?;
+
+    Ok((backend, evm))
+}
+
+fn compare(single: &Execution, iterative: &Execution) -> Vec<String> {
+    let mut divergence = Vec::new();
+
+    if single.exit_status != iterative.exit_status {
+        divergence.push(format!(
+            "exit status: {:?} in a single run, {:?} in iterations",
+            single.exit_status, iterative.exit_status
+        ));
+    }
+
+    if single.steps_executed != iterative.steps_executed {
+        divergence.push(format!(
+            "steps executed: {} in a single run, {} in iterations",
+            single.steps_executed, iterative.steps_executed
+        ));
+    }
+
+    // Action doesn't implement PartialEq
+    if format!("{:?}", single.actions) != format!("{:?}", iterative.actions) {
+        divergence.push(format!(
+            "actions: {} in a single run, {} in iterations",
+            single.actions.len(),
+            iterative.actions.len()
+        ));
+    }
+
+    divergence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(exit_status: ExitStatus, steps_executed: u64, nonce_of: &[u8]) -> Execution {
+        Execution {
+            exit_status,
+            steps_executed,
+            actions: nonce_of
+                .iter()
+                .map(|n| Action::EvmIncrementNonce {
+                    address: Address::from([*n; 20]),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn divergence() {
+        let single = execution(ExitStatus::Stop, 10, &[1, 2]);
+
+        assert!(compare(&single, &execution(ExitStatus::Stop, 10, &[1, 2])).is_empty());
+
+        let divergence = compare(&single, &execution(ExitStatus::StepLimit, 10, &[1, 2]));
+        assert_eq!(
+            divergence,
+            vec!["exit status: Stop in a single run, StepLimit in iterations"]
+        );
+
+        let divergence = compare(&single, &execution(ExitStatus::Stop, 12, &[1, 2]));
+        assert_eq!(
+            divergence,
+            vec!["steps executed: 10 in a single run, 12 in iterations"]
+        );
+
+        // Same number of actions with other content
+        let divergence = compare(&single, &execution(ExitStatus::Stop, 10, &[1, 3]));
+        assert_eq!(
+            divergence,
+            vec!["actions: 2 in a single run, 2 in iterations"]
+        );
+
+        let divergence = compare(&single, &execution(ExitStatus::Revert(vec![]), 11, &[1]));
+        assert_eq!(divergence.len(), 3);
+    }
+}
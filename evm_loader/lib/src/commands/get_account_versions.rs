@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use evm_loader::account::{ether_account, ether_storage, holder, state, AccountData, Packable};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+};
+
+use crate::{Config, Context, NeonResult};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AccountVersions {
+    pub tag: u8,
+    pub current: usize,
+    pub outdated: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetAccountVersionsReturn {
+    pub total: usize,
+    pub outdated: usize,
+    pub accounts: BTreeMap<String, AccountVersions>,
+    /// Accounts with tags which are neither current nor migratable
+    pub unknown: BTreeMap<u8, usize>,
+}
+
+fn account_type<T: Packable + std::fmt::Debug>(
+    name: &'static str,
+    accounts: &mut BTreeMap<String, AccountVersions>,
+    tags: &mut BTreeMap<u8, (&'static str, bool)>,
+) {
+    accounts.insert(
+        name.to_string(),
+        AccountVersions {
+            tag: AccountData::<T>::TAG,
+            ..AccountVersions::default()
+        },
+    );
+
+    tags.insert(AccountData::<T>::TAG, (name, false));
+    for (tag, _) in T::DEPRECATED {
+        tags.insert(*tag, (name, true));
+    }
+}
+
+pub async fn execute(
+    config: &Config,
+    context: &Context<'_>,
+) -> NeonResult<GetAccountVersionsReturn> {
+    let client = context
+        .rpc_client
+        .as_any()
+        .downcast_ref::<RpcClient>()
+        .expect("cast to solana_client::rpc_client::RpcClient error");
+
+    let mut accounts = BTreeMap::new();
+    let mut tags = BTreeMap::new();
+    account_type::<ether_account::Data>("EthereumAccount", &mut accounts, &mut tags);
+    account_type::<ether_storage::Data>("EthereumStorage", &mut accounts, &mut tags);
+    account_type::<state::Data>("State", &mut accounts, &mut tags);
+    account_type::<state::FinalizedData>("FinalizedState", &mut accounts, &mut tags);
+    account_type::<holder::Data>("Holder", &mut accounts, &mut tags);
+
+    // Only the tag byte is required
+    let program_accounts = client
+        .get_program_accounts_with_config(
+            &config.evm_loader,
+            RpcProgramAccountsConfig {
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    data_slice: Some(UiDataSliceConfig {
+                        offset: 0,
+                        length: 1,
+                    }),
+                    commitment: Some(config.commitment),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+
+    let total = program_accounts.len();
+    let mut outdated = 0;
+    let mut unknown = BTreeMap::new();
+
+    for (_, account) in program_accounts {
+        let tag = match account.data.first() {
+            Some(&tag) => tag,
+            None => continue,
+        };
+
+        match tags.get(&tag) {
+            Some(&(name, true)) => {
+                outdated += 1;
+                accounts.entry(name.to_string()).or_default().outdated += 1;
+            }
+            Some(&(name, false)) => {
+                accounts.entry(name.to_string()).or_default().current += 1;
+            }
+            None => {
+                *unknown.entry(tag).or_insert(0) += 1;
+            }
+        }
+    }
+
+    Ok(GetAccountVersionsReturn {
+        total,
+        outdated,
+        accounts,
+        unknown,
+    })
+}
@@ -42,9 +42,17 @@ pub async fn execute(
     rpc_client: &dyn Rpc,
     evm_loader: &Pubkey,
     ether_address: &Address,
+    chain_id: u64,
+    default_chain_id: u64,
 ) -> NeonResult<GetEtherAccountDataReturn> {
-    match EmulatorAccountStorage::get_account_from_solana(rpc_client, evm_loader, ether_address)
-        .await
+    match EmulatorAccountStorage::get_account_from_solana(
+        rpc_client,
+        evm_loader,
+        ether_address,
+        chain_id,
+        default_chain_id,
+    )
+    .await
     {
         (solana_address, Some(mut acc)) => {
             let acc_info = account_info(&solana_address, &mut acc);
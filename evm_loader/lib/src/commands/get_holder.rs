@@ -0,0 +1,255 @@
+use evm_loader::account::Holder;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::str::FromStr;
+
+use crate::{account_storage::account_info, errors::NeonError, Config, Context, NeonResult};
+
+/// `HolderCreate` instruction tag
+const HOLDER_CREATE_TAG: u8 = 0x24;
+/// `HolderWrite` instruction tag
+const HOLDER_WRITE_TAG: u8 = 0x26;
+
+/// Effect of the instruction on the holder data
+#[derive(Debug, PartialEq, Eq)]
+enum HolderEvent {
+    /// Bytes range of the transaction is written
+    Write(usize, usize),
+    /// Holder is created or cleared for another transaction, earlier writes are lost
+    Reset,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetHolderReturn {
+    pub owner: String,
+    pub transaction_hash: String,
+    pub transaction_len: usize,
+    pub expected_len: Option<usize>,
+    pub filled: Vec<(usize, usize)>,
+    pub missing: Vec<(usize, usize)>,
+    pub is_complete: bool,
+    pub hash_matches: bool,
+}
+
+pub async fn execute(
+    config: &Config,
+    context: &Context<'_>,
+    holder_key: &Pubkey,
+) -> NeonResult<GetHolderReturn> {
+    let mut account = context
+        .rpc_client
+        .get_account_with_commitment(holder_key, config.commitment)
+        .await?
+        .value
+        .ok_or(NeonError::AccountNotFound(*holder_key))?;
+
+    let info = account_info(holder_key, &mut account);
+    let holder = Holder::from_account(&config.evm_loader, &info)?;
+
+    let transaction = holder.transaction();
+    let expected_len = Holder::expected_transaction_len(&transaction).ok();
+    let hash_matches = solana_sdk::keccak::hash(&transaction).to_bytes() == holder.transaction_hash;
+
+    let filled = written_ranges(config, context, holder_key, &holder.transaction_hash).await?;
+    let missing = missing_ranges(&filled, expected_len.unwrap_or(holder.transaction_len));
+    let is_complete =
+        expected_len.map_or(false, |len| len <= holder.transaction_len) && missing.is_empty();
+
+    Ok(GetHolderReturn {
+        owner: holder.owner.to_string(),
+        transaction_hash: hex::encode(holder.transaction_hash),
+        transaction_len: holder.transaction_len,
+        expected_len,
+        filled,
+        missing,
+        is_complete,
+        hash_matches,
+    })
+}
+
+/// Collect byte ranges written by successful `HolderWrite` instructions for the given transaction.
+/// History is scanned from the newest signature back to the last holder reset.
+async fn written_ranges(
+    config: &Config,
+    context: &Context<'_>,
+    holder_key: &Pubkey,
+    transaction_hash: &[u8; 32],
+) -> NeonResult<Vec<(usize, usize)>> {
+    let client = context
+        .rpc_client
+        .as_any()
+        .downcast_ref::<RpcClient>()
+        .expect("cast to solana_client::rpc_client::RpcClient error");
+
+    let mut ranges = Vec::new();
+    let mut before = None;
+
+    'history: loop {
+        let signatures = client
+            .get_signatures_for_address_with_config(
+                holder_key,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: None,
+                    commitment: Some(config.commitment),
+                },
+            )
+            .await?;
+
+        let last = match signatures.last() {
+            Some(last) => parse_signature(&last.signature),
+            None => break,
+        };
+
+        for status in signatures.into_iter().filter(|s| s.err.is_none()) {
+            let signature = parse_signature(&status.signature);
+            let transaction = client
+                .get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Base64),
+                        commitment: Some(config.commitment),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+                .await?;
+
+            let transaction = match transaction.transaction.transaction.decode() {
+                Some(transaction) => transaction,
+                None => {
+                    warn!("Skip {signature} - failed to decode transaction");
+                    continue;
+                }
+            };
+
+            let keys = transaction.message.static_account_keys();
+            // Instructions of the transaction are applied in order, scan them backwards as well
+            for instruction in transaction.message.instructions().iter().rev() {
+                let program_id = keys.get(usize::from(instruction.program_id_index));
+                let holder = instruction
+                    .accounts
+                    .first()
+                    .and_then(|index| keys.get(usize::from(*index)));
+
+                if (program_id != Some(&config.evm_loader)) || (holder != Some(holder_key)) {
+                    continue;
+                }
+
+                match holder_event(&instruction.data, transaction_hash) {
+                    Some(HolderEvent::Write(begin, end)) => ranges.push((begin, end)),
+                    Some(HolderEvent::Reset) => break 'history,
+                    None => {}
+                }
+            }
+        }
+
+        before = Some(last);
+    }
+
+    Ok(merge_ranges(ranges))
+}
+
+fn holder_event(data: &[u8], transaction_hash: &[u8; 32]) -> Option<HolderEvent> {
+    match data.first() {
+        Some(&HOLDER_CREATE_TAG) => Some(HolderEvent::Reset),
+        Some(&HOLDER_WRITE_TAG) if data.len() >= 1 + 32 + 8 => {
+            if &data[1..33] != transaction_hash {
+                return Some(HolderEvent::Reset);
+            }
+
+            let mut offset = [0_u8; 8];
+            offset.copy_from_slice(&data[33..41]);
+            let offset = usize::try_from(u64::from_le_bytes(offset)).ok()?;
+
+            Some(HolderEvent::Write(
+                offset,
+                offset.saturating_add(data.len() - 41),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn parse_signature(signature: &str) -> Signature {
+    Signature::from_str(signature).expect("RPC returned invalid signature")
+}
+
+fn merge_ranges(mut ranges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+    for (begin, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if begin <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((begin, end)),
+        }
+    }
+
+    merged
+}
+
+fn missing_ranges(filled: &[(usize, usize)], len: usize) -> Vec<(usize, usize)> {
+    let mut missing = Vec::new();
+    let mut position = 0;
+
+    for &(begin, end) in filled {
+        if begin > position {
+            missing.push((position, begin.min(len)));
+        }
+        position = position.max(end);
+    }
+
+    if position < len {
+        missing.push((position, len));
+    }
+
+    missing.retain(|(begin, end)| begin < end);
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(hash: &[u8; 32], offset: u64, len: usize) -> Vec<u8> {
+        let mut data = vec![HOLDER_WRITE_TAG];
+        data.extend_from_slice(hash);
+        data.extend_from_slice(&offset.to_le_bytes());
+        data.resize(data.len() + len, 0xFF);
+        data
+    }
+
+    #[test]
+    fn events() {
+        let hash = [1; 32];
+
+        assert_eq!(
+            holder_event(&write(&hash, 10, 5), &hash),
+            Some(HolderEvent::Write(10, 15))
+        );
+        assert_eq!(
+            holder_event(&write(&[2; 32], 10, 5), &hash),
+            Some(HolderEvent::Reset)
+        );
+        assert_eq!(
+            holder_event(&[HOLDER_CREATE_TAG], &hash),
+            Some(HolderEvent::Reset)
+        );
+        assert_eq!(holder_event(&[HOLDER_WRITE_TAG, 0, 0], &hash), None);
+        assert_eq!(holder_event(&[0x25], &hash), None);
+    }
+
+    #[test]
+    fn ranges() {
+        let filled = merge_ranges(vec![(10, 20), (0, 5), (15, 25)]);
+        assert_eq!(filled, vec![(0, 5), (10, 25)]);
+        assert_eq!(missing_ranges(&filled, 30), vec![(5, 10), (25, 30)]);
+    }
+}
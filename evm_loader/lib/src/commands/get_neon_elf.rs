@@ -5,9 +5,13 @@ use solana_sdk::{
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     pubkey::Pubkey,
 };
-use std::{collections::HashMap, convert::TryFrom, fs::File, io::Read};
+use std::{collections::HashMap, convert::TryFrom, fs::File, io::Read, str::FromStr};
 
-use crate::{context::Context, errors::NeonError, Config, NeonResult};
+use evm_loader::account::{parameters::NAMES, Parameters};
+
+use crate::{
+    account_storage::account_info, context::Context, errors::NeonError, Config, NeonResult,
+};
 
 pub type GetNeonElfReturn = HashMap<String, String>;
 
@@ -15,6 +19,12 @@ pub struct CachedElfParams {
     elf_params: GetNeonElfReturn,
 }
 
+impl From<GetNeonElfReturn> for CachedElfParams {
+    fn from(elf_params: GetNeonElfReturn) -> Self {
+        Self { elf_params }
+    }
+}
+
 impl CachedElfParams {
     pub async fn new(config: &Config, context: &Context<'_>) -> Self {
         Self {
@@ -26,6 +36,54 @@ impl CachedElfParams {
     pub fn get(&self, param_name: &str) -> Option<&String> {
         self.elf_params.get(param_name)
     }
+
+    /// Default chain, its accounts keep the legacy addresses
+    pub fn default_chain_id(&self) -> u64 {
+        self.get("NEON_CHAIN_ID")
+            .and_then(|chain_id| chain_id.parse().ok())
+            .expect("NEON_CHAIN_ID load error")
+    }
+
+    /// Additional chains and their gas token mints.
+    /// Format: `chain_id:token_mint:decimals` separated by `;`
+    fn extra_chains(&self) -> impl Iterator<Item = (u64, Pubkey)> + '_ {
+        self.get("NEON_EXTRA_CHAINS")
+            .into_iter()
+            .flat_map(|chains| chains.split(';'))
+            .filter_map(|chain| {
+                let mut parts = chain.split(':');
+                let id = parts.next()?.parse::<u64>().ok()?;
+                let mint = Pubkey::from_str(parts.next()?).ok()?;
+                Some((id, mint))
+            })
+    }
+
+    /// Chains hosted by the program, the default one first
+    pub fn chain_ids(&self) -> Vec<u64> {
+        std::iter::once(self.default_chain_id())
+            .chain(self.extra_chains().map(|(id, _)| id))
+            .collect()
+    }
+
+    /// Requested chain or the default one, if omitted
+    pub fn resolve_chain_id(&self, chain_id: Option<u64>) -> NeonResult<u64> {
+        let chain_id = chain_id.unwrap_or_else(|| self.default_chain_id());
+        if !self.chain_ids().contains(&chain_id) {
+            return Err(NeonError::UnknownChain(chain_id));
+        }
+
+        Ok(chain_id)
+    }
+
+    /// Gas token mint of the hosted chain
+    pub fn token_mint(&self, chain_id: u64) -> Option<Pubkey> {
+        if chain_id == self.default_chain_id() {
+            return Pubkey::from_str(self.get("NEON_TOKEN_MINT")?).ok();
+        }
+
+        self.extra_chains()
+            .find_map(|(id, mint)| (id == chain_id).then_some(mint))
+    }
 }
 
 pub fn read_elf_parameters(_config: &Config, program_data: &[u8]) -> GetNeonElfReturn {
@@ -224,11 +282,33 @@ fn read_program_params_from_file(
     Ok(read_elf_parameters(config, &program_data))
 }
 
+/// Compiled parameters with the runtime overrides from the parameters account applied
 async fn read_program_params_from_account(
     config: &Config,
     context: &Context<'_>,
 ) -> NeonResult<GetNeonElfReturn> {
-    read_elf_parameters_from_account(config, context).await
+    let mut result = read_elf_parameters_from_account(config, context).await?;
+
+    let (parameters_key, _) = Parameters::address(&config.evm_loader);
+    let account = context
+        .rpc_client
+        .get_account_with_commitment(&parameters_key, config.commitment)
+        .await?
+        .value;
+
+    if let Some(mut account) = account {
+        let info = account_info(&parameters_key, &mut account);
+        let parameters = Parameters::from_account(&config.evm_loader, &info)?;
+
+        for (name, value) in NAMES.iter().zip(parameters.overrides()?) {
+            if let Some(value) = value {
+                result.insert(format!("NEON_{name}"), value.to_string());
+            }
+        }
+        result.insert("NEON_PARAMETERS".to_string(), parameters_key.to_string());
+    }
+
+    Ok(result)
 }
 
 pub async fn execute(
@@ -242,3 +322,35 @@ pub async fn execute(
         read_program_params_from_account(config, context).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chains() {
+        let mint = Pubkey::new_unique();
+        let extra_mint = Pubkey::new_unique();
+        let params = CachedElfParams {
+            elf_params: HashMap::from([
+                ("NEON_CHAIN_ID".to_string(), "111".to_string()),
+                ("NEON_TOKEN_MINT".to_string(), mint.to_string()),
+                (
+                    "NEON_EXTRA_CHAINS".to_string(),
+                    format!("112:{extra_mint}:9"),
+                ),
+            ]),
+        };
+
+        assert_eq!(params.chain_ids(), vec![111, 112]);
+        assert_eq!(params.resolve_chain_id(None).unwrap(), 111);
+        assert_eq!(params.resolve_chain_id(Some(112)).unwrap(), 112);
+        assert!(matches!(
+            params.resolve_chain_id(Some(113)),
+            Err(NeonError::UnknownChain(113))
+        ));
+        assert_eq!(params.token_mint(111), Some(mint));
+        assert_eq!(params.token_mint(112), Some(extra_mint));
+        assert_eq!(params.token_mint(113), None);
+    }
+}
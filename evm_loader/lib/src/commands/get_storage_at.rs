@@ -32,10 +32,18 @@ pub async fn execute(
     evm_loader: &Pubkey,
     ether_address: Address,
     index: &U256,
+    chain_id: u64,
+    default_chain_id: u64,
 ) -> NeonResult<GetStorageAtReturn> {
     let value = if let (solana_address, Some(mut account)) =
-        EmulatorAccountStorage::get_account_from_solana(rpc_client, evm_loader, &ether_address)
-            .await
+        EmulatorAccountStorage::get_account_from_solana(
+            rpc_client,
+            evm_loader,
+            &ether_address,
+            chain_id,
+            default_chain_id,
+        )
+        .await
     {
         let info = account_info(&solana_address, &mut account);
 
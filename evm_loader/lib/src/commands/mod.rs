@@ -1,27 +1,38 @@
-use crate::rpc::Rpc;
+use crate::{rpc::Rpc, NeonResult};
 use solana_client::{
     client_error::Result as SolanaClientResult, rpc_config::RpcSendTransactionConfig,
 };
 use solana_sdk::{
     commitment_config::{CommitmentConfig, CommitmentLevel},
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     message::Message,
+    pubkey::Pubkey,
     signature::Signature,
     signer::Signer,
     transaction::Transaction,
 };
 
 pub mod cancel_trx;
+pub mod collect_storage;
 pub mod collect_treasury;
+pub mod create_access_list;
 pub mod create_ether_account;
 pub mod deposit;
+pub mod deposit_rates;
+pub mod deposit_spl;
 pub mod emulate;
+pub mod emulate_iterative;
+pub mod get_account_versions;
 pub mod get_ether_account_data;
+pub mod get_holder;
 pub mod get_neon_elf;
 pub mod get_storage_at;
 pub mod init_environment;
+pub mod operator_registry;
+pub mod parameters;
 pub mod trace;
 mod transaction_executor;
+pub mod treasury_status;
 
 pub async fn send_transaction(
     rpc_client: &dyn Rpc,
@@ -47,3 +58,23 @@ pub async fn send_transaction(
         )
         .await
 }
+
+/// Append the optional program account, e.g. Operator registry or Parameters PDA,
+/// to the instruction accounts if it is created
+pub async fn push_if_created(
+    rpc_client: &dyn Rpc,
+    evm_loader: &Pubkey,
+    key: Pubkey,
+    accounts: &mut Vec<AccountMeta>,
+) -> NeonResult<()> {
+    let account = rpc_client
+        .get_account_with_commitment(&key, rpc_client.commitment())
+        .await?
+        .value;
+
+    if account.map_or(false, |account| account.owner == *evm_loader) {
+        accounts.push(AccountMeta::new_readonly(key, false));
+    }
+
+    Ok(())
+}
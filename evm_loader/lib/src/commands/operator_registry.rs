@@ -0,0 +1,177 @@
+use evm_loader::account::{operator_registry::OperatorStatus, OperatorRegistry};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+};
+
+use crate::{
+    account_storage::account_info, commands::send_transaction, errors::NeonError, Config, Context,
+    NeonResult,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperatorRegistryUpdateReturn {
+    pub registry: String,
+    pub transaction: Signature,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperatorInfo {
+    pub operator: String,
+    pub suspended: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetOperatorsReturn {
+    pub registry: String,
+    pub authority: String,
+    pub operators: Vec<OperatorInfo>,
+}
+
+/// Create the registry. Signer must be the program upgrade authority.
+pub async fn init(
+    config: &Config,
+    context: &Context<'_>,
+    authority: &Pubkey,
+) -> NeonResult<OperatorRegistryUpdateReturn> {
+    let signer = context.signer()?;
+    let (registry, _) = OperatorRegistry::address(&config.evm_loader);
+    let (program_data, _) =
+        Pubkey::find_program_address(&[config.evm_loader.as_ref()], &bpf_loader_upgradeable::id());
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x32_u8, authority.to_bytes()),
+        vec![
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(OperatorRegistryUpdateReturn {
+        registry: registry.to_string(),
+        transaction,
+    })
+}
+
+/// Add operator to the registry. Signer must be the registry authority.
+pub async fn add(
+    config: &Config,
+    context: &Context<'_>,
+    operator: &Pubkey,
+) -> NeonResult<OperatorRegistryUpdateReturn> {
+    let signer = context.signer()?;
+    let (registry, _) = OperatorRegistry::address(&config.evm_loader);
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x33_u8, operator.to_bytes()),
+        vec![
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(OperatorRegistryUpdateReturn {
+        registry: registry.to_string(),
+        transaction,
+    })
+}
+
+/// Remove operator from the registry. Signer must be the registry authority.
+pub async fn remove(
+    config: &Config,
+    context: &Context<'_>,
+    operator: &Pubkey,
+) -> NeonResult<OperatorRegistryUpdateReturn> {
+    let signer = context.signer()?;
+    let (registry, _) = OperatorRegistry::address(&config.evm_loader);
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x34_u8, operator.to_bytes()),
+        vec![
+            AccountMeta::new(registry, false),
+            AccountMeta::new(signer.pubkey(), true),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(OperatorRegistryUpdateReturn {
+        registry: registry.to_string(),
+        transaction,
+    })
+}
+
+/// Suspend or resume registered operator. Signer must be the registry authority.
+pub async fn suspend(
+    config: &Config,
+    context: &Context<'_>,
+    operator: &Pubkey,
+    suspend: bool,
+) -> NeonResult<OperatorRegistryUpdateReturn> {
+    let signer = context.signer()?;
+    let (registry, _) = OperatorRegistry::address(&config.evm_loader);
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x35_u8, operator.to_bytes(), u8::from(suspend)),
+        vec![
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(OperatorRegistryUpdateReturn {
+        registry: registry.to_string(),
+        transaction,
+    })
+}
+
+pub async fn get_operators(
+    config: &Config,
+    context: &Context<'_>,
+) -> NeonResult<GetOperatorsReturn> {
+    let (registry_key, _) = OperatorRegistry::address(&config.evm_loader);
+
+    let mut account = context
+        .rpc_client
+        .get_account_with_commitment(&registry_key, config.commitment)
+        .await?
+        .value
+        .ok_or(NeonError::AccountNotFound(registry_key))?;
+
+    let info = account_info(&registry_key, &mut account);
+    let registry = OperatorRegistry::from_account(&config.evm_loader, &info)?;
+
+    let operators = registry
+        .operators()?
+        .into_iter()
+        .map(|(operator, status)| OperatorInfo {
+            operator: operator.to_string(),
+            suspended: status == OperatorStatus::Suspended,
+        })
+        .collect();
+
+    Ok(GetOperatorsReturn {
+        registry: registry_key.to_string(),
+        authority: registry.authority.to_string(),
+        operators,
+    })
+}
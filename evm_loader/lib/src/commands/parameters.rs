@@ -0,0 +1,87 @@
+use evm_loader::account::{parameters::NAMES, Parameters};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+};
+
+use crate::{commands::send_transaction, errors::NeonError, Config, Context, NeonResult};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParametersUpdateReturn {
+    pub parameters: String,
+    pub transaction: Signature,
+}
+
+/// Create the parameters account. Signer must be the program upgrade authority.
+pub async fn init(
+    config: &Config,
+    context: &Context<'_>,
+    authority: &Pubkey,
+) -> NeonResult<ParametersUpdateReturn> {
+    let signer = context.signer()?;
+    let (parameters, _) = Parameters::address(&config.evm_loader);
+    let (program_data, _) =
+        Pubkey::find_program_address(&[config.evm_loader.as_ref()], &bpf_loader_upgradeable::id());
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(0x36_u8, authority.to_bytes()),
+        vec![
+            AccountMeta::new(parameters, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(ParametersUpdateReturn {
+        parameters: parameters.to_string(),
+        transaction,
+    })
+}
+
+/// Override parameter, `None` restores compiled default. Signer must be the parameters authority.
+pub async fn set(
+    config: &Config,
+    context: &Context<'_>,
+    name: &str,
+    value: Option<u64>,
+) -> NeonResult<ParametersUpdateReturn> {
+    let name = name.to_uppercase();
+    let name = name.trim_start_matches("NEON_");
+    let index = NAMES
+        .iter()
+        .position(|n| *n == name)
+        .ok_or_else(|| NeonError::UnknownParameter(name.to_string()))?;
+
+    let signer = context.signer()?;
+    let (parameters, _) = Parameters::address(&config.evm_loader);
+
+    let instruction = Instruction::new_with_bincode(
+        config.evm_loader,
+        &(
+            0x37_u8,
+            u8::try_from(index).expect("parameters count fits u8"),
+            u8::from(value.is_some()),
+            value.unwrap_or_default(),
+        ),
+        vec![
+            AccountMeta::new(parameters, false),
+            AccountMeta::new_readonly(signer.pubkey(), true),
+        ],
+    );
+
+    let transaction = send_transaction(context.rpc_client, &*signer, &[instruction]).await?;
+
+    Ok(ParametersUpdateReturn {
+        parameters: parameters.to_string(),
+        transaction,
+    })
+}
@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
-use std::rc::Rc;
+use std::path::Path;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -11,9 +12,10 @@ use evm_loader::types::Address;
 
 use crate::{
     account_storage::EmulatorAccountStorage,
-    commands::emulate::{emulate_transaction, emulate_trx, setup_syscall_stubs},
+    commands::emulate::{check_step_limit, emulate_transaction, emulate_trx},
     errors::NeonError,
-    rpc::Rpc,
+    rpc::{Rpc, Snapshot},
+    syscall_stubs::with_syscall_stubs,
     types::TxParams,
 };
 
@@ -24,33 +26,44 @@ pub async fn trace_transaction(
     tx: TxParams,
     token: Pubkey,
     chain_id: u64,
+    default_chain_id: u64,
     steps: u64,
     commitment: CommitmentConfig,
     accounts: &[Address],
     solana_accounts: &[Pubkey],
     trace_call_config: TraceCallConfig,
+    snapshot_path: Option<&Path>,
 ) -> Result<Value, NeonError> {
     let tracer = new_tracer(&trace_call_config.trace_config)?;
 
-    let (emulation_result, _storage) = emulate_transaction(
+    let (emulation_result, storage) = emulate_transaction(
         rpc_client,
         evm_loader,
         tx,
         token,
         chain_id,
+        default_chain_id,
         steps,
         commitment,
         accounts,
         solana_accounts,
         &trace_call_config.block_overrides,
         trace_call_config.state_overrides,
-        Some(Rc::clone(&tracer)),
+        Some(Arc::clone(&tracer)),
     )
     .await?;
+    check_step_limit(&emulation_result)?;
 
-    Ok(Rc::try_unwrap(tracer)
+    if let Some(path) = snapshot_path {
+        Snapshot::collect(rpc_client, &evm_loader, &storage)
+            .await?
+            .save(path)?;
+    }
+
+    Ok(Arc::try_unwrap(tracer)
         .expect("There is must be only one reference")
         .into_inner()
+        .expect("tracer lock is poisoned")
         .into_traces(emulation_result))
 }
 
@@ -70,19 +83,19 @@ pub async fn trace_block(
     transactions: Vec<TxParams>,
     token: Pubkey,
     chain_id: u64,
+    default_chain_id: u64,
     steps: u64,
     commitment: CommitmentConfig,
     accounts: &[Address],
     solana_accounts: &[Pubkey],
     trace_config: &TraceConfig,
 ) -> Result<TraceBlockReturn, NeonError> {
-    setup_syscall_stubs(rpc_client).await?;
-
     let storage = EmulatorAccountStorage::with_accounts(
         rpc_client,
         evm_loader,
         token,
         chain_id,
+        default_chain_id,
         commitment,
         accounts,
         solana_accounts,
@@ -91,13 +104,17 @@ pub async fn trace_block(
     )
     .await?;
 
-    let mut results = vec![];
-    for tx_params in transactions {
-        let result = trace_trx(tx_params, &storage, chain_id, steps, trace_config).await?;
-        results.push(result);
-    }
+    let traces = async {
+        let mut results = vec![];
+        for tx_params in transactions {
+            let result = trace_trx(tx_params, &storage, chain_id, steps, trace_config).await?;
+            results.push(result);
+        }
+
+        Ok::<_, NeonError>(TraceBlockReturn(results))
+    };
 
-    Ok(TraceBlockReturn(results))
+    with_syscall_stubs(&storage, traces).await?
 }
 
 async fn trace_trx<'a>(
@@ -114,12 +131,14 @@ async fn trace_trx<'a>(
         storage,
         chain_id,
         steps,
-        Some(Rc::clone(&tracer)),
+        Some(Arc::clone(&tracer)),
     )
     .await?;
+    check_step_limit(&emulation_result)?;
 
-    Ok(Rc::try_unwrap(tracer)
+    Ok(Arc::try_unwrap(tracer)
         .expect("There is must be only one reference")
         .into_inner()
+        .expect("tracer lock is poisoned")
         .into_traces(emulation_result))
 }
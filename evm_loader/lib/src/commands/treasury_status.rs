@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use evm_loader::account::{MainTreasury, Treasury};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey};
+
+use crate::{
+    commands::get_neon_elf::{read_elf_parameters_from_account, CachedElfParams},
+    errors::NeonError,
+    Config, Context, NeonResult,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreasuryPoolStatus {
+    pub index: u32,
+    pub address: String,
+    pub exists: bool,
+    pub balance: u64,
+    pub rent_exempt_minimum: u64,
+    pub collectable: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MainTreasuryStatus {
+    pub address: String,
+    pub exists: bool,
+    pub balance: u64,
+    pub rent_exempt_minimum: u64,
+    /// Wrapped SOL amount, lamports transferred after the last `sync_native` are not included
+    pub token_amount: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreasuryStatusReturn {
+    pub chain_id: u64,
+    pub main_treasury: MainTreasuryStatus,
+    pub pools: Vec<TreasuryPoolStatus>,
+    pub total_balance: u64,
+    pub total_collectable: u64,
+}
+
+/// `get_multiple_accounts` RPC limit
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+pub async fn execute(
+    config: &Config,
+    context: &Context<'_>,
+    chain_id: Option<u64>,
+) -> NeonResult<TreasuryStatusReturn> {
+    let neon_params =
+        CachedElfParams::from(read_elf_parameters_from_account(config, context).await?);
+    let default_chain_id = neon_params.default_chain_id();
+    let chain_id = neon_params.resolve_chain_id(chain_id)?;
+
+    let pool_count: u32 = neon_params
+        .get("NEON_POOL_COUNT")
+        .and_then(|value| value.parse().ok())
+        .ok_or(NeonError::IncorrectProgram(config.evm_loader))?;
+
+    let mut rent_cache = HashMap::new();
+
+    let (main_address, _) = MainTreasury::address(&config.evm_loader);
+    let main_account = context
+        .rpc_client
+        .get_account_with_commitment(&main_address, config.commitment)
+        .await?
+        .value;
+    let main_treasury = MainTreasuryStatus {
+        address: main_address.to_string(),
+        exists: main_account.is_some(),
+        balance: main_account.as_ref().map_or(0, |account| account.lamports),
+        rent_exempt_minimum: match &main_account {
+            Some(account) => rent_exempt_minimum(context, &mut rent_cache, account).await?,
+            None => 0,
+        },
+        token_amount: main_account.as_ref().and_then(|account| {
+            spl_token::state::Account::unpack(&account.data)
+                .ok()
+                .map(|token| token.amount)
+        }),
+    };
+
+    let addresses: Vec<Pubkey> = (0..pool_count)
+        .map(|index| {
+            Treasury::address_in_chain(&config.evm_loader, chain_id, default_chain_id, index).0
+        })
+        .collect();
+
+    let mut pools = Vec::with_capacity(addresses.len());
+    for (chunk_index, chunk) in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST).enumerate() {
+        let accounts = context.rpc_client.get_multiple_accounts(chunk).await?;
+
+        for (offset, (address, account)) in chunk.iter().zip(accounts).enumerate() {
+            let index = u32::try_from(chunk_index * MAX_ACCOUNTS_PER_REQUEST + offset)
+                .expect("pool index fits u32");
+
+            let (balance, minimum) = match &account {
+                Some(account) => (
+                    account.lamports,
+                    rent_exempt_minimum(context, &mut rent_cache, account).await?,
+                ),
+                None => (0, 0),
+            };
+
+            pools.push(TreasuryPoolStatus {
+                index,
+                address: address.to_string(),
+                exists: account.is_some(),
+                balance,
+                rent_exempt_minimum: minimum,
+                collectable: balance.saturating_sub(minimum),
+            });
+        }
+    }
+
+    Ok(TreasuryStatusReturn {
+        chain_id,
+        main_treasury,
+        total_balance: pools.iter().map(|pool| pool.balance).sum(),
+        total_collectable: pools.iter().map(|pool| pool.collectable).sum(),
+        pools,
+    })
+}
+
+async fn rent_exempt_minimum(
+    context: &Context<'_>,
+    cache: &mut HashMap<usize, u64>,
+    account: &Account,
+) -> NeonResult<u64> {
+    let data_len = account.data.len();
+    if let Some(minimum) = cache.get(&data_len) {
+        return Ok(*minimum);
+    }
+
+    let minimum = context
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(data_len)
+        .await?;
+    cache.insert(data_len, minimum);
+
+    Ok(minimum)
+}
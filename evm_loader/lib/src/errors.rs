@@ -97,6 +97,12 @@ pub enum NeonError {
     ClickHouse(ChError),
     #[error("Slot {0} is less than earliest_rooted_slot={1}")]
     EarlySlot(u64, u64),
+    #[error("Unknown program parameter {0:?}.")]
+    UnknownParameter(String),
+    #[error("Chain {0} is not hosted by the program.")]
+    UnknownChain(u64),
+    #[error("Snapshot error. {0}")]
+    SnapshotError(String),
 }
 
 impl NeonError {
@@ -132,6 +138,9 @@ impl NeonError {
             NeonError::TxParametersParsingError(_) => 250,
             NeonError::ClickHouse(_) => 252,
             NeonError::EarlySlot(_, _) => 253,
+            NeonError::UnknownParameter(_) => 254,
+            NeonError::UnknownChain(_) => 255,
+            NeonError::SnapshotError(_) => 256,
         }
     }
 }
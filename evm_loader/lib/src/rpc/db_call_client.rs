@@ -50,7 +50,7 @@ impl CallDbClient {
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl Rpc for CallDbClient {
     fn commitment(&self) -> CommitmentConfig {
         CommitmentConfig::default()
@@ -1,7 +1,9 @@
 mod db_call_client;
+mod snapshot_client;
 mod validator_client;
 
 pub use db_call_client::CallDbClient;
+pub use snapshot_client::{Snapshot, SnapshotAccount, SnapshotClient};
 
 use crate::{NeonError, NeonResult};
 use async_trait::async_trait;
@@ -28,8 +30,9 @@ use solana_transaction_status::{
 };
 use std::any::Any;
 
-#[async_trait(?Send)]
-pub trait Rpc {
+/// Clients are shared between the emulation tasks of a multi-threaded runtime
+#[async_trait]
+pub trait Rpc: Send + Sync {
     fn commitment(&self) -> CommitmentConfig;
     async fn confirm_transaction_with_spinner(
         &self,
@@ -0,0 +1,308 @@
+use super::{e, Rpc};
+use crate::{account_storage::EmulatorAccountStorage, NeonError, NeonResult};
+use async_trait::async_trait;
+use evm_loader::account_storage::AccountStorage;
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    client_error::Result as ClientResult,
+    client_error::{ClientError, ClientErrorKind},
+    rpc_config::{RpcSendTransactionConfig, RpcTransactionConfig},
+    rpc_response::{Response, RpcResponseContext, RpcResult},
+};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable,
+    clock::{Slot, UnixTimestamp},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signature,
+    sysvar,
+    transaction::Transaction,
+};
+use solana_transaction_status::{
+    EncodedConfirmedBlock, EncodedConfirmedTransactionWithStatusMeta, TransactionStatus,
+};
+use std::{any::Any, collections::BTreeMap, fs::File, io::BufReader, io::BufWriter, path::Path};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotAccount {
+    #[serde(with = "pubkey_base58")]
+    pub pubkey: Pubkey,
+    pub account: Account,
+}
+
+/// Base58 string in both JSON and bincode, `PubkeyBase58` requires a self-describing format
+mod pubkey_base58 {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&pubkey.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Pubkey::from_str(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Cluster state required to repeat the emulation offline.
+/// Stored as JSON if the file has `.json` extension, as bincode otherwise.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub slot: Slot,
+    pub block_time: UnixTimestamp,
+    pub rent: Rent,
+    pub accounts: Vec<SnapshotAccount>,
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |extension| extension == "json")
+}
+
+impl Snapshot {
+    pub fn load(path: &Path) -> NeonResult<Self> {
+        let reader = BufReader::new(File::open(path)?);
+
+        if is_json(path) {
+            serde_json::from_reader(reader).map_err(|e| NeonError::SnapshotError(e.to_string()))
+        } else {
+            bincode::deserialize_from(reader).map_err(|e| NeonError::SnapshotError(e.to_string()))
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> NeonResult<()> {
+        let writer = BufWriter::new(File::create(path)?);
+
+        if is_json(path) {
+            serde_json::to_writer(writer, self).map_err(|e| NeonError::SnapshotError(e.to_string()))
+        } else {
+            bincode::serialize_into(writer, self)
+                .map_err(|e| NeonError::SnapshotError(e.to_string()))
+        }
+    }
+
+    /// Collect accounts loaded by the emulation, in the state they were loaded,
+    /// together with the sysvars, program accounts and runtime parameters required to repeat it.
+    pub async fn collect(
+        rpc_client: &dyn Rpc,
+        evm_loader: &Pubkey,
+        storage: &EmulatorAccountStorage<'_>,
+    ) -> NeonResult<Self> {
+        let loaded = storage.loaded_accounts();
+
+        let required: Vec<Pubkey> = [
+            sysvar::rent::id(),
+            sysvar::clock::id(),
+            *evm_loader,
+            Pubkey::find_program_address(&[evm_loader.as_ref()], &bpf_loader_upgradeable::id()).0,
+            evm_loader::account::Parameters::address(evm_loader).0,
+        ]
+        .into_iter()
+        .filter(|pubkey| !loaded.contains_key(pubkey))
+        .collect();
+        let required_accounts = rpc_client.get_multiple_accounts(&required).await?;
+
+        let accounts: BTreeMap<Pubkey, Account> = loaded
+            .into_iter()
+            .chain(required.into_iter().zip(required_accounts))
+            .filter_map(|(pubkey, account)| Some((pubkey, account?)))
+            .collect();
+
+        let rent_account = accounts
+            .get(&sysvar::rent::id())
+            .ok_or_else(|| NeonError::SnapshotError("Rent sysvar not found".to_string()))?;
+        let rent: Rent = bincode::deserialize(&rent_account.data)
+            .map_err(|e| NeonError::SnapshotError(e.to_string()))?;
+
+        Ok(Self {
+            slot: storage.block_number().as_u64(),
+            block_time: storage.block_timestamp().as_i64(),
+            rent,
+            accounts: accounts
+                .into_iter()
+                .map(|(pubkey, account)| SnapshotAccount { pubkey, account })
+                .collect(),
+        })
+    }
+}
+
+/// Serves accounts, slot, block time and rent from a `Snapshot`, without network access
+pub struct SnapshotClient {
+    slot: Slot,
+    block_time: UnixTimestamp,
+    rent: Rent,
+    accounts: BTreeMap<Pubkey, Account>,
+}
+
+impl SnapshotClient {
+    pub fn new(snapshot: Snapshot) -> Self {
+        Self {
+            slot: snapshot.slot,
+            block_time: snapshot.block_time,
+            rent: snapshot.rent,
+            accounts: snapshot
+                .accounts
+                .into_iter()
+                .map(|entry| (entry.pubkey, entry.account))
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> NeonResult<Self> {
+        Snapshot::load(path).map(Self::new)
+    }
+
+    fn account(&self, key: &Pubkey) -> Option<Account> {
+        self.accounts.get(key).cloned()
+    }
+}
+
+#[async_trait]
+impl Rpc for SnapshotClient {
+    fn commitment(&self) -> CommitmentConfig {
+        CommitmentConfig::default()
+    }
+
+    async fn confirm_transaction_with_spinner(
+        &self,
+        _signature: &Signature,
+        _recent_blockhash: &Hash,
+        _commitment_config: CommitmentConfig,
+    ) -> ClientResult<()> {
+        Err(e!(
+            "confirm_transaction_with_spinner() not implemented for snapshot_client"
+        ))
+    }
+
+    async fn get_account(&self, key: &Pubkey) -> ClientResult<Account> {
+        self.account(key)
+            .ok_or_else(|| e!("account not found", key))
+    }
+
+    async fn get_account_with_commitment(
+        &self,
+        key: &Pubkey,
+        _: CommitmentConfig,
+    ) -> RpcResult<Option<Account>> {
+        let context = RpcResponseContext {
+            slot: self.slot,
+            api_version: None,
+        };
+        Ok(Response {
+            context,
+            value: self.account(key),
+        })
+    }
+
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> ClientResult<Vec<Option<Account>>> {
+        Ok(pubkeys.iter().map(|key| self.account(key)).collect())
+    }
+
+    async fn get_account_data(&self, key: &Pubkey) -> ClientResult<Vec<u8>> {
+        if let Some(account) = self.accounts.get(key) {
+            return Ok(account.data.clone());
+        }
+
+        if sysvar::rent::check_id(key) {
+            return bincode::serialize(&self.rent).map_err(|e| e!("serialize rent error", e));
+        }
+
+        Err(e!("account not found", key))
+    }
+
+    async fn get_block(&self, _slot: Slot) -> ClientResult<EncodedConfirmedBlock> {
+        Err(e!("get_block() not implemented for snapshot_client"))
+    }
+
+    async fn get_block_time(&self, slot: Slot) -> ClientResult<UnixTimestamp> {
+        if slot != self.slot {
+            return Err(e!("block time is not in snapshot", slot));
+        }
+
+        Ok(self.block_time)
+    }
+
+    async fn get_latest_blockhash(&self) -> ClientResult<Hash> {
+        Err(e!(
+            "get_latest_blockhash() not implemented for snapshot_client"
+        ))
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        Ok(self.rent.minimum_balance(data_len))
+    }
+
+    async fn get_slot(&self) -> ClientResult<Slot> {
+        Ok(self.slot)
+    }
+
+    async fn get_signature_statuses(
+        &self,
+        _signatures: &[Signature],
+    ) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        Err(e!(
+            "get_signature_statuses() not implemented for snapshot_client"
+        ))
+    }
+
+    async fn get_transaction_with_config(
+        &self,
+        _signature: &Signature,
+        _config: RpcTransactionConfig,
+    ) -> ClientResult<EncodedConfirmedTransactionWithStatusMeta> {
+        Err(e!(
+            "get_transaction_with_config() not implemented for snapshot_client"
+        ))
+    }
+
+    async fn send_transaction(&self, _transaction: &Transaction) -> ClientResult<Signature> {
+        Err(e!("send_transaction() not implemented for snapshot_client"))
+    }
+
+    async fn send_and_confirm_transaction_with_spinner(
+        &self,
+        _transaction: &Transaction,
+    ) -> ClientResult<Signature> {
+        Err(e!(
+            "send_and_confirm_transaction_with_spinner() not implemented for snapshot_client"
+        ))
+    }
+
+    async fn send_and_confirm_transaction_with_spinner_and_commitment(
+        &self,
+        _transaction: &Transaction,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Signature> {
+        Err(e!("send_and_confirm_transaction_with_spinner_and_commitment() not implemented for snapshot_client"))
+    }
+
+    async fn send_and_confirm_transaction_with_spinner_and_config(
+        &self,
+        _transaction: &Transaction,
+        _commitment: CommitmentConfig,
+        _config: RpcSendTransactionConfig,
+    ) -> ClientResult<Signature> {
+        Err(e!("send_and_confirm_transaction_with_spinner_and_config() not implemented for snapshot_client"))
+    }
+
+    async fn get_latest_blockhash_with_commitment(
+        &self,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<(Hash, u64)> {
+        Err(e!(
+            "get_latest_blockhash_with_commitment() not implemented for snapshot_client"
+        ))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -20,7 +20,7 @@ use solana_transaction_status::{
 };
 use std::any::Any;
 
-#[async_trait(?Send)]
+#[async_trait]
 impl Rpc for RpcClient {
     fn commitment(&self) -> CommitmentConfig {
         self.commitment()
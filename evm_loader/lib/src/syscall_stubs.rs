@@ -1,28 +1,64 @@
-use log::info;
-use solana_sdk::{program_error::ProgramError, program_stubs::SyscallStubs, sysvar::rent::Rent};
+use std::future::Future;
+use std::sync::Once;
 
-use crate::{errors::NeonError, rpc::Rpc};
+use log::{error, info};
+use solana_sdk::{
+    program_error::{ProgramError, UNSUPPORTED_SYSVAR},
+    program_stubs::SyscallStubs,
+    sysvar::rent::Rent,
+};
 
-pub struct Stubs {
-    rent: Rent,
+use crate::{account_storage::EmulatorAccountStorage, errors::NeonError};
+
+tokio::task_local! {
+    /// Rent of the cluster the current emulation task runs against
+    static RENT: Rent;
 }
 
+/// Process-wide syscall stubs, sysvars are read from the state of the current task.
+/// Concurrent emulations against different clusters don't interfere with each other.
+pub struct Stubs;
+
 impl Stubs {
-    pub async fn new(rpc_client: &dyn Rpc) -> Result<Box<Stubs>, NeonError> {
-        let rent_pubkey = solana_sdk::sysvar::rent::id();
-        let data = rpc_client.get_account_data(&rent_pubkey).await?;
-        let rent = bincode::deserialize(&data).map_err(|_| ProgramError::InvalidArgument)?;
+    pub fn install() {
+        static INSTALL: Once = Once::new();
 
-        Ok(Box::new(Self { rent }))
+        INSTALL.call_once(|| {
+            solana_sdk::program_stubs::set_syscall_stubs(Box::new(Stubs));
+        });
     }
 }
 
+/// Run the `future` with syscall stubs serving sysvars of the `storage` cluster
+pub async fn with_syscall_stubs<F: Future>(
+    storage: &EmulatorAccountStorage<'_>,
+    future: F,
+) -> Result<F::Output, NeonError> {
+    Stubs::install();
+
+    let rent_pubkey = solana_sdk::sysvar::rent::id();
+    let data = storage
+        .get_account(&rent_pubkey)
+        .await?
+        .map(|account| account.data)
+        .unwrap_or_default();
+    let rent = bincode::deserialize(&data).map_err(|_| ProgramError::InvalidArgument)?;
+
+    Ok(RENT.scope(rent, future).await)
+}
+
 impl SyscallStubs for Stubs {
+    /// Rent is known only inside `with_syscall_stubs`, outside of it the request fails
     fn sol_get_rent_sysvar(&self, pointer: *mut u8) -> u64 {
+        let Ok(value) = RENT.try_with(|rent| *rent) else {
+            error!("Rent sysvar is requested outside of the emulation");
+            return UNSUPPORTED_SYSVAR;
+        };
+
         unsafe {
             #[allow(clippy::cast_ptr_alignment)]
             let rent = pointer.cast::<Rent>();
-            *rent = self.rent;
+            *rent = value;
         }
 
         0
@@ -46,3 +82,24 @@ impl SyscallStubs for Stubs {
         info!("Program Data: {}", messages.join(" "));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::sysvar::Sysvar;
+
+    #[tokio::test]
+    async fn rent_is_scoped_to_the_task() {
+        Stubs::install();
+
+        let rent = Rent {
+            lamports_per_byte_year: 1,
+            exemption_threshold: 2.0,
+            burn_percent: 0,
+        };
+        let scoped = RENT.scope(rent, async { Rent::get() }).await.unwrap();
+        assert_eq!(scoped, rent);
+
+        assert_eq!(Rent::get(), Err(ProgramError::UnsupportedSysvar));
+    }
+}
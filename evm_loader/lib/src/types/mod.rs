@@ -35,9 +35,35 @@ pub struct TxParams {
     pub data: Option<Vec<u8>>,
     pub value: Option<U256>,
     pub gas_limit: Option<U256>,
+    pub gas_price: Option<U256>,
+    /// EIP-1559 fee fields, exclusive with `gas_price`. There is no base fee: the priority fee
+    /// capped by the max fee is the price, the max fee is the price without the priority fee.
+    /// The sender balance is checked against the max fee.
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
     pub access_list: Option<Vec<AccessListItem>>,
 }
 
+impl TxParams {
+    /// Price the sender pays for the used gas
+    #[must_use]
+    pub fn gas_price(&self) -> U256 {
+        match (self.gas_price, self.max_fee_per_gas) {
+            (Some(gas_price), _) => gas_price,
+            (None, Some(max_fee)) => self
+                .max_priority_fee_per_gas
+                .map_or(max_fee, |priority_fee| priority_fee.min(max_fee)),
+            (None, None) => U256::ZERO,
+        }
+    }
+
+    /// Price the sender balance is checked against
+    #[must_use]
+    pub fn max_gas_price(&self) -> U256 {
+        self.gas_price.or(self.max_fee_per_gas).unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionParams {
     pub data: Option<HexBytes>,
@@ -13,6 +13,7 @@ use super::AccessListItem;
 pub struct GetEtherRequest {
     pub ether: Address,
     pub slot: Option<u64>,
+    pub chain_id: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -20,6 +21,7 @@ pub struct GetStorageAtRequest {
     pub contract_id: Address,
     pub index: U256,
     pub slot: Option<u64>,
+    pub chain_id: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Default)]
@@ -29,6 +31,9 @@ pub struct TxParamsRequestModel {
     pub data: Option<Vec<u8>>,
     pub value: Option<U256>,
     pub gas_limit: Option<U256>,
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
     pub access_list: Option<Vec<AccessListItem>>,
 }
 
@@ -45,6 +50,9 @@ impl fmt::Debug for TxParamsRequestModel {
 
         f.field("value", &self.value)
             .field("gas_limit", &self.gas_limit)
+            .field("gas_price", &self.gas_price)
+            .field("max_fee_per_gas", &self.max_fee_per_gas)
+            .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
             .field("access_list", &self.access_list)
             .finish_non_exhaustive()
     }
@@ -59,6 +67,9 @@ impl From<TxParamsRequestModel> for TxParams {
             data: model.data,
             value: model.value,
             gas_limit: model.gas_limit,
+            gas_price: model.gas_price,
+            max_fee_per_gas: model.max_fee_per_gas,
+            max_priority_fee_per_gas: model.max_priority_fee_per_gas,
             access_list: model.access_list,
         }
     }
@@ -103,6 +114,9 @@ pub struct EmulateRequestModel {
     pub emulation_params: EmulationParamsRequestModel,
     pub slot: Option<u64>,
     pub tx_index_in_block: Option<u64>,
+    /// Return the partial result instead of the error when `max_steps_to_execute` is reached
+    #[serde(default)]
+    pub partial_on_step_limit: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
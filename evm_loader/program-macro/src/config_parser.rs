@@ -14,6 +14,8 @@ pub struct NetSpecificConfig {
     pub chain_id: u64,
     pub operators_whitelist: Vec<String>,
     pub token_mint: TokenMint,
+    #[serde(default)]
+    pub extra_chains: Vec<ExtraChain>,
 }
 
 impl Parse for NetSpecificConfig {
@@ -43,6 +45,13 @@ pub struct TokenMint {
     pub decimals: u8,
 }
 
+#[derive(Deserialize)]
+pub struct ExtraChain {
+    pub chain_id: u64,
+    pub neon_token_mint: String,
+    pub decimals: u8,
+}
+
 pub struct CommonConfig {
     pub token_stream: TokenStream,
 }
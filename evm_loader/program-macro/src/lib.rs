@@ -3,7 +3,7 @@
 
 mod config_parser;
 
-use config_parser::{CommonConfig, ElfParams, NetSpecificConfig, TokenMint};
+use config_parser::{CommonConfig, ElfParams, ExtraChain, NetSpecificConfig, TokenMint};
 use proc_macro::TokenStream;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
@@ -143,12 +143,71 @@ pub fn net_specific_config_parser(tokens: TokenStream) -> TokenStream {
             neon_token_mint,
             decimals,
         },
+        extra_chains,
     } = parse_macro_input!(tokens as NetSpecificConfig);
 
+    let mut chain_ids = vec![chain_id];
+    for chain in &extra_chains {
+        assert!(
+            !chain_ids.contains(&chain.chain_id),
+            "Duplicate chain id {}",
+            chain.chain_id
+        );
+        assert!(
+            chain.decimals <= 18,
+            "Chain {} token decimals must not exceed 18",
+            chain.chain_id
+        );
+        chain_ids.push(chain.chain_id);
+    }
+
+    let extra_chains_param = extra_chains
+        .iter()
+        .map(|chain| {
+            format!(
+                "{}:{}:{}",
+                chain.chain_id, chain.neon_token_mint, chain.decimals
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let extra_chains = extra_chains.iter().map(
+        |ExtraChain {
+             chain_id,
+             neon_token_mint,
+             decimals,
+         }| {
+            let mint = bs58::decode(neon_token_mint)
+                .into_vec()
+                .expect("Pubkey is base58 encoded");
+
+            quote! {
+                ChainInfo {
+                    id: #chain_id,
+                    token_mint: ::solana_program::pubkey::Pubkey::new_from_array([#((#mint),)*]),
+                    decimals: #decimals,
+                }
+            }
+        },
+    );
+
     quote! {
         /// Supported CHAIN_ID value for transactions
         pub const CHAIN_ID: u64 = #chain_id;
 
+        /// Chains hosted by the program, the first one is the default `CHAIN_ID` chain
+        pub const CHAINS: &[ChainInfo] = &[
+            ChainInfo {
+                id: CHAIN_ID,
+                token_mint: token_mint::ID,
+                decimals: token_mint::DECIMALS,
+            },
+            #(#extra_chains,)*
+        ];
+
+        neon_elf_param!(NEON_EXTRA_CHAINS, #extra_chains_param);
+
         operators_whitelist![#(#operators_whitelist),*];
 
         /// Token Mint ID
@@ -0,0 +1,258 @@
+use arrayref::{array_ref, array_refs, mut_array_refs};
+use ethnum::U256;
+use solana_program::account_info::AccountInfo;
+use solana_program::pubkey::Pubkey;
+
+use crate::config::DEPOSIT_RATES_SEED;
+use crate::error::{Error, Result};
+
+use super::{managed, program, DepositRates, Operator};
+
+/// Serialized size of the rate entry: mint, chain id, numerator and denominator
+const ENTRY_SIZE: usize = 32 + 8 + 8 + 8;
+
+/// Conversion rate of the SPL token into the chain gas token.
+/// One base unit of `mint` is worth `numerator / denominator` base units of the gas token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    pub mint: Pubkey,
+    pub chain_id: u64,
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+impl Rate {
+    /// Gas token balance in 18 decimals credited for the `amount` base units of the mint,
+    /// `decimals` are the chain gas token decimals
+    #[must_use]
+    pub fn deposit_amount(&self, amount: u64, decimals: u8) -> U256 {
+        let additional_decimals: u32 = (18 - decimals).into();
+
+        U256::from(amount) * U256::from(self.numerator) * 10_u128.pow(additional_decimals)
+            / U256::from(self.denominator)
+    }
+
+    fn unpack(entry: &[u8; ENTRY_SIZE]) -> Self {
+        let (mint, chain_id, numerator, denominator) = array_refs![entry, 32, 8, 8, 8];
+
+        Self {
+            mint: Pubkey::new_from_array(*mint),
+            chain_id: u64::from_le_bytes(*chain_id),
+            numerator: u64::from_le_bytes(*numerator),
+            denominator: u64::from_le_bytes(*denominator),
+        }
+    }
+
+    fn pack(&self, entry: &mut [u8; ENTRY_SIZE]) {
+        let (mint, chain_id, numerator, denominator) = mut_array_refs![entry, 32, 8, 8, 8];
+
+        mint.copy_from_slice(self.mint.as_ref());
+        *chain_id = self.chain_id.to_le_bytes();
+        *numerator = self.numerator.to_le_bytes();
+        *denominator = self.denominator.to_le_bytes();
+    }
+}
+
+#[derive(Debug)]
+pub struct Rates;
+
+impl managed::Kind for Rates {
+    const TAG: u8 = super::TAG_DEPOSIT_RATES;
+    const SEED: &'static str = DEPOSIT_RATES_SEED;
+    const NAME: &'static str = "Deposit Rates";
+    const ENTRY_SIZE: usize = ENTRY_SIZE;
+
+    fn invalid_authority(authority: Pubkey, expected: Pubkey) -> Error {
+        Error::DepositRatesInvalidAuthority(authority, expected)
+    }
+}
+
+/// Deposit conversion rates data account, entries are the rates
+pub type Data = managed::Data<Rates>;
+
+impl<'a> DepositRates<'a> {
+    pub fn rates(&self) -> Result<Vec<Rate>> {
+        let rates = self
+            .entries()?
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| Rate::unpack(array_ref![entry, 0, ENTRY_SIZE]))
+            .collect();
+
+        Ok(rates)
+    }
+
+    fn position(&self, mint: &Pubkey, chain_id: u64) -> Result<Option<usize>> {
+        self.find_entry(|entry| {
+            let rate = Rate::unpack(array_ref![entry, 0, ENTRY_SIZE]);
+            (rate.mint == *mint) && (rate.chain_id == chain_id)
+        })
+    }
+
+    pub fn rate(&self, mint: &Pubkey, chain_id: u64) -> Result<Option<Rate>> {
+        let rate = self
+            .rates()?
+            .into_iter()
+            .find(|rate| (rate.mint == *mint) && (rate.chain_id == chain_id));
+
+        Ok(rate)
+    }
+
+    /// Add the rate or update the existing one of the same mint and chain
+    pub fn set(
+        &mut self,
+        rate: &Rate,
+        payer: &Operator<'a>,
+        system_program: &program::System<'a>,
+    ) -> Result<()> {
+        if (rate.numerator == 0) || (rate.denominator == 0) {
+            return Err(Error::Custom(
+                "Deposit rate numerator and denominator must be positive".to_string(),
+            ));
+        }
+
+        let mut entry = [0_u8; ENTRY_SIZE];
+        rate.pack(&mut entry);
+
+        match self.position(&rate.mint, rate.chain_id)? {
+            Some(index) => self.entry_mut(index)?.copy_from_slice(&entry),
+            None => {
+                self.push_entry(&entry, payer, system_program)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the rate and refund the rent of the released space to the `refund` account
+    pub fn remove(&mut self, mint: &Pubkey, chain_id: u64, refund: &AccountInfo<'a>) -> Result<()> {
+        let index = self
+            .position(mint, chain_id)?
+            .ok_or(Error::DepositMintNotSupported(*mint, chain_id))?;
+
+        self.swap_remove_entry(index, refund)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::managed::tests::{serialize, set_rent_stubs, TestAccount};
+    use solana_program::rent::Rent;
+
+    fn accounts(program_id: &Pubkey, authority: &Pubkey) -> Vec<TestAccount> {
+        vec![
+            TestAccount::pda::<Rates>(program_id, *authority, &[]),
+            TestAccount::system(*authority, true),
+            TestAccount::system(Pubkey::new_unique(), true),
+            TestAccount::system_program(),
+        ]
+    }
+
+    fn rate(chain_id: u64, numerator: u64) -> Rate {
+        Rate {
+            mint: Pubkey::new_from_array([1; 32]),
+            chain_id,
+            numerator,
+            denominator: 3,
+        }
+    }
+
+    #[test]
+    fn set_and_remove() {
+        set_rent_stubs();
+
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut input = serialize(&accounts(&program_id, &authority));
+        let (_, infos, _) =
+            unsafe { solana_program::entrypoint::deserialize(input.as_mut_ptr().cast()) };
+
+        let payer = unsafe { Operator::from_account_not_whitelisted(&infos[2]).unwrap() };
+        let system_program = program::System::from_account(&infos[3]).unwrap();
+
+        {
+            let mut rates = DepositRates::from_pda(&program_id, &infos[0]).unwrap();
+            rates.validate_authority(&infos[1]).unwrap();
+            assert!(rates.validate_authority(&infos[2]).is_err());
+            assert!(rates.set(&rate(111, 0), &payer, &system_program).is_err());
+
+            rates.set(&rate(111, 2), &payer, &system_program).unwrap();
+            rates.set(&rate(112, 5), &payer, &system_program).unwrap();
+            rates.set(&rate(111, 7), &payer, &system_program).unwrap();
+        }
+
+        assert_eq!(infos[0].data_len(), DepositRates::required_size(2));
+        {
+            let rates = DepositRates::from_pda(&program_id, &infos[0]).unwrap();
+            assert_eq!(rates.count, 2);
+            assert_eq!(
+                rates.rate(&rate(0, 0).mint, 111).unwrap(),
+                Some(rate(111, 7))
+            );
+            assert_eq!(
+                rates.rate(&rate(0, 0).mint, 112).unwrap(),
+                Some(rate(112, 5))
+            );
+            assert_eq!(rates.rate(&rate(0, 0).mint, 113).unwrap(), None);
+        }
+
+        // The transfer is not executed off-chain, fund the grown account directly
+        let rent = Rent::default();
+        **infos[0].lamports.borrow_mut() = rent.minimum_balance(DepositRates::required_size(2));
+
+        {
+            let mut rates = DepositRates::from_pda(&program_id, &infos[0]).unwrap();
+            rates.remove(&rate(0, 0).mint, 111, &infos[2]).unwrap();
+            assert!(matches!(
+                rates.remove(&rate(0, 0).mint, 111, &infos[2]),
+                Err(Error::DepositMintNotSupported(_, 111))
+            ));
+        }
+
+        let new_len = DepositRates::required_size(1);
+        assert_eq!(infos[0].data_len(), new_len);
+        assert_eq!(infos[0].lamports(), rent.minimum_balance(new_len));
+        assert_eq!(
+            infos[2].lamports(),
+            rent.minimum_balance(DepositRates::required_size(2)) - rent.minimum_balance(new_len)
+        );
+
+        let rates = DepositRates::from_pda(&program_id, &infos[0]).unwrap();
+        assert_eq!(rates.rates().unwrap(), vec![rate(112, 5)]);
+    }
+
+    #[test]
+    fn pda_required() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = accounts(&program_id, &Pubkey::new_unique());
+        accounts[0].key = Pubkey::new_unique();
+
+        let mut input = serialize(&accounts);
+        let (_, infos, _) =
+            unsafe { solana_program::entrypoint::deserialize(input.as_mut_ptr().cast()) };
+
+        assert!(matches!(
+            DepositRates::from_pda(&program_id, &infos[0]),
+            Err(Error::AccountInvalidKey(..))
+        ));
+    }
+
+    #[test]
+    fn deposit_amount() {
+        // 1 token unit is worth 2/3 of the gas token unit with 9 decimals
+        let rate = Rate {
+            mint: Pubkey::new_unique(),
+            chain_id: 111,
+            numerator: 2,
+            denominator: 3,
+        };
+
+        assert_eq!(rate.deposit_amount(3, 9), U256::new(2_000_000_000));
+        assert_eq!(rate.deposit_amount(1, 18), U256::ZERO);
+        assert_eq!(rate.deposit_amount(0, 9), U256::ZERO);
+        assert_eq!(
+            rate.deposit_amount(u64::MAX, 9),
+            U256::from(u64::MAX) * 2 * 1_000_000_000 / 3
+        );
+    }
+}
@@ -8,10 +8,10 @@ use solana_program::account_info::AccountInfo;
 use solana_program::program_error::ProgramError;
 use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
 
+use crate::config::CHAIN_ID;
 use crate::types::Address;
 
-use super::{program::System, EthereumAccount, Packable};
-use super::{Operator, ACCOUNT_SEED_VERSION};
+use super::{program::System, EthereumAccount, Operator, Packable};
 
 /// Ethereum account data v3
 #[derive(Debug, Default)]
@@ -117,11 +117,29 @@ impl<'a> EthereumAccount<'a> {
         Ok(())
     }
 
+    /// Check that the account belongs to the chain balance namespace.
+    /// Always true if the program hosts a single chain.
+    #[must_use]
+    pub fn is_in_chain(&self, chain_id: u64) -> bool {
+        if !crate::config::is_multichain() {
+            return true;
+        }
+
+        let mut seeds = self.address.solana_address_seeds(chain_id, CHAIN_ID);
+        seeds.push(vec![self.bump_seed]);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+
+        Pubkey::create_program_address(&seeds, self.info.owner)
+            .map_or(false, |key| key == *self.info.key)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create_account(
         system_program: &System<'a>,
         program_id: &Pubkey,
         operator: &Operator<'a>,
         address: &Address,
+        chain_id: u64,
         info: &'a AccountInfo<'a>,
         bump_seed: u8,
         space: usize,
@@ -135,17 +153,21 @@ impl<'a> EthereumAccount<'a> {
             );
         }
 
-        let program_seeds: &[&[u8]] = &[&[ACCOUNT_SEED_VERSION], address.as_bytes(), &[bump_seed]];
-        system_program.create_pda_account(program_id, operator, info, program_seeds, space)?;
+        let mut program_seeds = address.solana_address_seeds(chain_id, CHAIN_ID);
+        program_seeds.push(vec![bump_seed]);
+        let program_seeds: Vec<&[u8]> = program_seeds.iter().map(Vec::as_slice).collect();
+        system_program.create_pda_account(program_id, operator, info, &program_seeds, space)?;
 
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_and_init_account(
         system_program: &System<'a>,
         program_id: &Pubkey,
         operator: &Operator<'a>,
         address: Address,
+        chain_id: u64,
         info: &'a AccountInfo<'a>,
         bump_seed: u8,
         space: usize,
@@ -155,6 +177,7 @@ impl<'a> EthereumAccount<'a> {
             program_id,
             operator,
             &address,
+            chain_id,
             info,
             bump_seed,
             space,
@@ -170,6 +170,7 @@ impl<'a> EthereumStorage<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn create(
         contract: &EthereumAccount<'a>,
+        chain_id: u64,
         storage_account: &'a AccountInfo<'a>,
         storage_address: &EthereumStorageAddress,
         index: U256,
@@ -183,6 +184,7 @@ impl<'a> EthereumStorage<'a> {
         system.create_account_with_seed(
             operator,
             contract,
+            chain_id,
             contract.info.owner,
             storage_account,
             storage_address.seed(),
@@ -223,4 +225,66 @@ impl<'a> EthereumStorage<'a> {
 
         Ok(())
     }
+
+    /// All values in the account are zero
+    #[must_use]
+    pub fn is_zeroed(&self) -> bool {
+        let data = self.info.data.borrow();
+        let data = &data[Self::SIZE..];
+
+        data.chunks_exact(1 + 32)
+            .all(|chunk| chunk[1..].iter().all(|&b| b == 0))
+    }
+
+    /// Close the account and return lamports to the operator.
+    /// The account becomes system owned, so it can be created again later.
+    pub fn close(self, operator: &Operator<'a>) -> Result<(), ProgramError> {
+        let info = self.info;
+
+        unsafe { self.suicide(operator) };
+
+        info.realloc(0, false)?;
+        info.assign(&solana_program::system_program::ID);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_zeroed(values: &[(u8, [u8; 32])]) -> bool {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let mut data = vec![Data::TAG];
+        data.resize(EthereumStorage::SIZE, 0);
+        for (subindex, value) in values {
+            data.push(*subindex);
+            data.extend_from_slice(value);
+        }
+
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        let storage = EthereumStorage::from_account(&program_id, &info).unwrap();
+
+        storage.is_zeroed()
+    }
+
+    #[test]
+    fn zeroed_storage() {
+        assert!(is_zeroed(&[]));
+        assert!(is_zeroed(&[(1, [0; 32]), (2, [0; 32])]));
+        assert!(!is_zeroed(&[(1, [0; 32]), (2, [1; 32])]));
+    }
 }
@@ -92,6 +92,33 @@ impl<'a> Holder<'a> {
         Ok(())
     }
 
+    /// Check that all transaction chunks are written, the hash is checked by `validate_transaction`
+    pub fn validate_complete(&self) -> Result<()> {
+        let transaction = self.transaction();
+
+        let expected_len = Self::expected_transaction_len(&transaction)?;
+        if transaction.len() < expected_len {
+            return Err(Error::HolderIncomplete(expected_len, transaction.len()));
+        }
+
+        Ok(())
+    }
+
+    /// Transaction length from the type envelope and RLP header
+    pub fn expected_transaction_len(transaction: &[u8]) -> Result<usize> {
+        let (envelope_len, rlp) = match transaction.first() {
+            None => return Err(Error::HolderIncomplete(0, 0)),
+            Some(&transaction_type) if transaction_type < 0x80 => (1, &transaction[1..]),
+            Some(_) => (0, transaction),
+        };
+
+        // Parses only the header, payload may be incomplete
+        let info = rlp::PayloadInfo::from(rlp)
+            .map_err(|_| Error::HolderIncomplete(0, transaction.len()))?;
+
+        Ok(envelope_len + info.header_len + info.value_len)
+    }
+
     pub fn validate_transaction(&self, trx: &Transaction) -> Result<()> {
         if self.transaction_hash != trx.hash() {
             return Err(Error::HolderInvalidHash(self.transaction_hash, trx.hash()));
@@ -100,3 +127,28 @@ impl<'a> Holder<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_transaction_len() {
+        // Legacy transaction: list header with 2 bytes of length, payload is not written yet
+        assert_eq!(
+            Holder::expected_transaction_len(&[0xF9, 0x01, 0x00]).unwrap(),
+            3 + 256
+        );
+        // Typed transaction envelope
+        assert_eq!(
+            Holder::expected_transaction_len(&[0x02, 0xC3, 0x01]).unwrap(),
+            1 + 1 + 3
+        );
+
+        assert!(matches!(
+            Holder::expected_transaction_len(&[]),
+            Err(Error::HolderIncomplete(0, 0))
+        ));
+        assert!(Holder::expected_transaction_len(&[0xF9]).is_err());
+    }
+}
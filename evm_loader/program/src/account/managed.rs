@@ -0,0 +1,461 @@
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::account_info::AccountInfo;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+use crate::error::{Error, Result};
+
+use super::{program, AccountData, Operator, Packable};
+
+/// Serialized size of the header: authority, bump seed and entries count
+const HEADER_SIZE: usize = 32 + 1 + 8;
+
+/// Program PDA managed by the authority key: operator registry, runtime parameters, deposit rates.
+/// The header is followed by `count` entries of `ENTRY_SIZE`.
+pub trait Kind: Debug {
+    /// Account struct tag
+    const TAG: u8;
+    /// Seed of the PDA
+    const SEED: &'static str;
+    /// Name used in the instruction logs
+    const NAME: &'static str;
+    /// Serialized size of the entry
+    const ENTRY_SIZE: usize;
+    /// Entries allocated by the account creation
+    const INITIAL_COUNT: usize = 0;
+
+    fn invalid_authority(authority: Pubkey, expected: Pubkey) -> Error;
+}
+
+/// Authority-managed PDA data account
+#[derive(Debug)]
+pub struct Data<K> {
+    pub authority: Pubkey,
+    pub bump_seed: u8,
+    pub count: usize,
+    kind: PhantomData<K>,
+}
+
+impl<K> Data<K> {
+    #[must_use]
+    pub const fn new(authority: Pubkey, bump_seed: u8, count: usize) -> Self {
+        Self {
+            authority,
+            bump_seed,
+            count,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K: Kind> Packable for Data<K> {
+    const TAG: u8 = K::TAG;
+    const SIZE: usize = HEADER_SIZE;
+
+    fn unpack(input: &[u8]) -> Self {
+        let data = array_ref![input, 0, HEADER_SIZE];
+        let (authority, bump_seed, count) = array_refs![data, 32, 1, 8];
+
+        Self::new(
+            Pubkey::new_from_array(*authority),
+            bump_seed[0],
+            usize::from_le_bytes(*count),
+        )
+    }
+
+    fn pack(&self, output: &mut [u8]) {
+        let data = array_mut_ref![output, 0, HEADER_SIZE];
+        let (authority, bump_seed, count) = mut_array_refs![data, 32, 1, 8];
+
+        authority.copy_from_slice(self.authority.as_ref());
+        bump_seed[0] = self.bump_seed;
+        *count = self.count.to_le_bytes();
+    }
+}
+
+impl<'a, K: Kind> AccountData<'a, Data<K>> {
+    #[must_use]
+    pub fn address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[K::SEED.as_bytes()], program_id)
+    }
+
+    #[must_use]
+    pub fn required_size(count: usize) -> usize {
+        Self::SIZE + count * K::ENTRY_SIZE
+    }
+
+    /// Load the account, the key must be the PDA
+    pub fn from_pda(program_id: &Pubkey, info: &'a AccountInfo<'a>) -> Result<Self> {
+        let account = Self::from_account(program_id, info)?;
+
+        // Only the PDA can be initialized with this tag, the stored bump seed
+        // derives its key without the `find_program_address` search
+        let seeds: &[&[u8]] = &[K::SEED.as_bytes(), &[account.bump_seed]];
+        let expected_key = Pubkey::create_program_address(seeds, program_id)
+            .map_err(|_| Error::AccountInvalidKey(*info.key, Pubkey::default()))?;
+        if expected_key != *info.key {
+            return Err(Error::AccountInvalidKey(*info.key, expected_key));
+        }
+
+        Ok(account)
+    }
+
+    /// Split the optional PDA, passed as the last instruction account, from the rest of accounts.
+    /// Returns `None` and the accounts untouched if the last account is not the created PDA.
+    pub fn split_last(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<(&'a [AccountInfo<'a>], Option<Self>)> {
+        let (info, rest) = match accounts.split_last() {
+            Some(split) => split,
+            None => return Ok((accounts, None)),
+        };
+
+        if super::tag(program_id, info).map_or(true, |tag| tag != K::TAG) {
+            return Ok((accounts, None));
+        }
+
+        let account = Self::from_pda(program_id, info)?;
+        Ok((rest, Some(account)))
+    }
+
+    pub fn validate_authority(&self, authority: &AccountInfo) -> Result<()> {
+        if self.authority != *authority.key {
+            return Err(K::invalid_authority(*authority.key, self.authority));
+        }
+
+        if !authority.is_signer {
+            return Err(Error::AccountNotSigner(*authority.key));
+        }
+
+        Ok(())
+    }
+
+    fn entry_offset(index: usize) -> usize {
+        Self::SIZE + index * K::ENTRY_SIZE
+    }
+
+    /// Serialized entries following the header
+    pub fn entries(&self) -> Result<Ref<'_, [u8]>> {
+        let data = self.info.try_borrow_data()?;
+        if data.len() < Self::required_size(self.count) {
+            return Err(Error::AccountInvalidData(*self.info.key));
+        }
+
+        let range = Self::entry_offset(0)..Self::entry_offset(self.count);
+        Ok(Ref::map(data, |data| &data[range]))
+    }
+
+    /// Index of the first entry matching the `predicate`
+    pub fn find_entry(&self, predicate: impl Fn(&[u8]) -> bool) -> Result<Option<usize>> {
+        let entries = self.entries()?;
+        let position = entries.chunks_exact(K::ENTRY_SIZE).position(predicate);
+
+        Ok(position)
+    }
+
+    pub fn entry_mut(&mut self, index: usize) -> Result<RefMut<'_, [u8]>> {
+        if index >= self.count {
+            return Err(Error::AccountInvalidData(*self.info.key));
+        }
+
+        let data = self.info.try_borrow_mut_data()?;
+        let range = Self::entry_offset(index)..Self::entry_offset(index + 1);
+        Ok(RefMut::map(data, |data| &mut data[range]))
+    }
+
+    /// Append the entry, the account grows and is topped up to the rent exemption by the `payer`
+    pub fn push_entry(
+        &mut self,
+        entry: &[u8],
+        payer: &Operator<'a>,
+        system_program: &program::System<'a>,
+    ) -> Result<usize> {
+        let index = self.count;
+        let new_len = Self::required_size(index + 1);
+
+        if self.info.data_len() < new_len {
+            let rent = Rent::get()?;
+            let required_lamports = rent
+                .minimum_balance(new_len)
+                .saturating_sub(self.info.lamports());
+            if required_lamports > 0 {
+                system_program.transfer(payer, self.info, required_lamports)?;
+            }
+
+            self.info.realloc(new_len, false)?;
+        }
+
+        self.count += 1;
+        self.entry_mut(index)?.copy_from_slice(entry);
+
+        Ok(index)
+    }
+
+    /// Remove the entry, the last one takes its place.
+    /// The account shrinks and the rent of the released space is refunded to the `refund` account.
+    pub fn swap_remove_entry(&mut self, index: usize, refund: &AccountInfo<'a>) -> Result<()> {
+        if index >= self.count {
+            return Err(Error::AccountInvalidData(*self.info.key));
+        }
+
+        let last = self.count - 1;
+        if index != last {
+            let last_offset = Self::entry_offset(last);
+            self.info.try_borrow_mut_data()?.copy_within(
+                last_offset..last_offset + K::ENTRY_SIZE,
+                Self::entry_offset(index),
+            );
+        }
+
+        self.count = last;
+
+        let new_len = Self::required_size(last);
+        self.info.realloc(new_len, false)?;
+
+        let minimum_balance = Rent::get()?.minimum_balance(new_len);
+        let excessive_lamports = self.info.lamports().saturating_sub(minimum_balance);
+        if excessive_lamports > 0 {
+            **self.info.lamports.borrow_mut() -= excessive_lamports;
+            **refund.lamports.borrow_mut() += excessive_lamports;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use solana_program::entrypoint::{MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER, SUCCESS};
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use solana_program::system_program;
+
+    struct RentStubs;
+
+    impl SyscallStubs for RentStubs {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { var_addr.cast::<Rent>().write_unaligned(Rent::default()) };
+            SUCCESS
+        }
+    }
+
+    /// Default rent for the account resizing, the system program transfer is not executed off-chain
+    pub fn set_rent_stubs() {
+        set_syscall_stubs(Box::new(RentStubs));
+    }
+
+    pub struct TestAccount {
+        pub key: Pubkey,
+        pub owner: Pubkey,
+        pub lamports: u64,
+        pub data: Vec<u8>,
+        pub is_signer: bool,
+    }
+
+    impl TestAccount {
+        pub fn system(key: Pubkey, is_signer: bool) -> Self {
+            Self {
+                key,
+                owner: system_program::id(),
+                lamports: 0,
+                data: Vec::new(),
+                is_signer,
+            }
+        }
+
+        pub fn system_program() -> Self {
+            Self {
+                key: system_program::id(),
+                owner: Pubkey::default(),
+                lamports: 0,
+                data: Vec::new(),
+                is_signer: false,
+            }
+        }
+
+        /// Created rent-exempt PDA of the kind `K` with the serialized `entries`
+        pub fn pda<K: Kind>(program_id: &Pubkey, authority: Pubkey, entries: &[&[u8]]) -> Self {
+            let (key, bump_seed) = AccountData::<Data<K>>::address(program_id);
+
+            let mut data = vec![K::TAG];
+            data.resize(AccountData::<Data<K>>::SIZE, 0);
+            Data::<K>::new(authority, bump_seed, entries.len()).pack(&mut data[1..]);
+            for entry in entries {
+                assert_eq!(entry.len(), K::ENTRY_SIZE);
+                data.extend_from_slice(entry);
+            }
+
+            Self {
+                key,
+                owner: *program_id,
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                is_signer: false,
+            }
+        }
+
+        pub fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    /// Serialize accounts in the program input format, `AccountInfo::realloc` relies on it.
+    /// Deserialize with `solana_program::entrypoint::deserialize`.
+    pub fn serialize(accounts: &[TestAccount]) -> Vec<u64> {
+        let mut input = Vec::new();
+        input.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+        for account in accounts {
+            input.extend_from_slice(&[NON_DUP_MARKER, account.is_signer.into(), 1, 0]);
+            input.extend_from_slice(&u32::try_from(account.data.len()).unwrap().to_le_bytes());
+            input.extend_from_slice(account.key.as_ref());
+            input.extend_from_slice(account.owner.as_ref());
+            input.extend_from_slice(&account.lamports.to_le_bytes());
+            input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            input.extend_from_slice(&account.data);
+            input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            input.resize((input.len() + 7) / 8 * 8, 0);
+            input.extend_from_slice(&0_u64.to_le_bytes());
+        }
+        input.extend_from_slice(&0_u64.to_le_bytes());
+        input.extend_from_slice(Pubkey::default().as_ref());
+
+        input
+            .chunks(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[derive(Debug)]
+    struct Numbers;
+
+    impl Kind for Numbers {
+        const TAG: u8 = 0xFF;
+        const SEED: &'static str = "numbers";
+        const NAME: &'static str = "Numbers";
+        const ENTRY_SIZE: usize = 4;
+
+        fn invalid_authority(authority: Pubkey, expected: Pubkey) -> Error {
+            Error::AccountInvalidKey(authority, expected)
+        }
+    }
+
+    type NumbersAccount<'a> = AccountData<'a, Data<Numbers>>;
+
+    fn numbers(account: &NumbersAccount) -> Vec<u32> {
+        account
+            .entries()
+            .unwrap()
+            .chunks_exact(Numbers::ENTRY_SIZE)
+            .map(|entry| u32::from_le_bytes(entry.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn push_and_swap_remove() {
+        set_rent_stubs();
+
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let accounts = [
+            TestAccount::pda::<Numbers>(&program_id, authority, &[&1_u32.to_le_bytes()]),
+            TestAccount::system(authority, true),
+            TestAccount::system(Pubkey::new_unique(), true),
+            TestAccount::system_program(),
+        ];
+        let mut input = serialize(&accounts);
+        let (_, infos, _) =
+            unsafe { solana_program::entrypoint::deserialize(input.as_mut_ptr().cast()) };
+
+        let payer = unsafe { Operator::from_account_not_whitelisted(&infos[2]).unwrap() };
+        let system_program = program::System::from_account(&infos[3]).unwrap();
+
+        {
+            let mut account = NumbersAccount::from_pda(&program_id, &infos[0]).unwrap();
+            account.validate_authority(&infos[1]).unwrap();
+            assert!(account.validate_authority(&infos[2]).is_err());
+
+            for number in [2_u32, 3] {
+                account
+                    .push_entry(&number.to_le_bytes(), &payer, &system_program)
+                    .unwrap();
+            }
+            assert_eq!(numbers(&account), vec![1, 2, 3]);
+            let two = 2_u32.to_le_bytes();
+            assert_eq!(account.find_entry(|entry| entry == two).unwrap(), Some(1));
+        }
+
+        let full_len = NumbersAccount::required_size(3);
+        assert_eq!(infos[0].data_len(), full_len);
+
+        // The transfer is not executed off-chain, fund the grown account directly
+        let rent = Rent::default();
+        **infos[0].lamports.borrow_mut() = rent.minimum_balance(full_len);
+
+        {
+            let mut account = NumbersAccount::from_pda(&program_id, &infos[0]).unwrap();
+            account.swap_remove_entry(0, &infos[2]).unwrap();
+            assert_eq!(numbers(&account), vec![3, 2]);
+            assert!(account.swap_remove_entry(2, &infos[2]).is_err());
+        }
+
+        let new_len = NumbersAccount::required_size(2);
+        assert_eq!(infos[0].data_len(), new_len);
+        assert_eq!(infos[0].lamports(), rent.minimum_balance(new_len));
+        assert_eq!(
+            infos[2].lamports(),
+            rent.minimum_balance(full_len) - rent.minimum_balance(new_len)
+        );
+
+        let account = NumbersAccount::from_pda(&program_id, &infos[0]).unwrap();
+        assert_eq!(account.count, 2);
+    }
+
+    #[test]
+    fn split_last() {
+        let program_id = Pubkey::new_unique();
+
+        let split = |last: &mut TestAccount| -> Result<(usize, bool)> {
+            let mut operator = TestAccount::system(Pubkey::new_unique(), true);
+            let accounts = [operator.info(), last.info()];
+            let (rest, account) = NumbersAccount::split_last(&program_id, &accounts)?;
+            Ok((rest.len(), account.is_some()))
+        };
+
+        let mut created = TestAccount::pda::<Numbers>(&program_id, Pubkey::new_unique(), &[]);
+        assert_eq!(split(&mut created).unwrap(), (1, true));
+
+        // Not created PDA or any other account is left to the instruction
+        let mut uncreated = TestAccount::system(NumbersAccount::address(&program_id).0, false);
+        assert_eq!(split(&mut uncreated).unwrap(), (2, false));
+        let mut other = TestAccount::system(Pubkey::new_unique(), false);
+        assert_eq!(split(&mut other).unwrap(), (2, false));
+
+        let accounts: &[AccountInfo] = &[];
+        assert!(NumbersAccount::split_last(&program_id, accounts)
+            .unwrap()
+            .1
+            .is_none());
+
+        // Data of the kind at other key
+        created.key = Pubkey::new_unique();
+        assert!(matches!(
+            split(&mut created),
+            Err(Error::AccountInvalidKey(..))
+        ));
+    }
+}
@@ -14,12 +14,16 @@ pub use incinerator::Incinerator;
 pub use operator::Operator;
 pub use treasury::{MainTreasury, Treasury};
 
+pub mod deposit_rates;
 pub mod ether_account;
 pub mod ether_contract;
 pub mod ether_storage;
 pub mod holder;
 mod incinerator;
+pub mod managed;
 mod operator;
+pub mod operator_registry;
+pub mod parameters;
 pub mod program;
 pub mod state;
 pub mod sysvar;
@@ -44,23 +48,42 @@ const TAG_HOLDER: u8 = 6;
 
 pub const TAG_EMPTY: u8 = 0;
 const TAG_ACCOUNT_V3: u8 = 12;
-const TAG_STATE: u8 = 22;
+const TAG_STATE_V4: u8 = 22;
+const TAG_STATE: u8 = 23;
 const TAG_FINALIZED_STATE: u8 = 31;
 const TAG_CONTRACT_STORAGE: u8 = 42;
 const TAG_HOLDER: u8 = 51;
+const TAG_OPERATOR_REGISTRY: u8 = 61;
+const TAG_PARAMETERS: u8 = 62;
+const TAG_DEPOSIT_RATES: u8 = 63;
 
 pub type EthereumAccount<'a> = AccountData<'a, ether_account::Data>;
 pub type EthereumStorage<'a> = AccountData<'a, ether_storage::Data>;
 pub type State<'a> = AccountData<'a, state::Data>;
 pub type FinalizedState<'a> = AccountData<'a, state::FinalizedData>;
 pub type Holder<'a> = AccountData<'a, holder::Data>;
+pub type OperatorRegistry<'a> = AccountData<'a, operator_registry::Data>;
+pub type Parameters<'a> = AccountData<'a, parameters::Data>;
+pub type DepositRates<'a> = AccountData<'a, deposit_rates::Data>;
 
 pub trait Packable {
     const TAG: u8;
     const SIZE: usize;
+    /// Tags and data sizes of the previous layouts, which can be upgraded to the current one
+    const DEPRECATED: &'static [(u8, usize)] = &[];
 
     fn unpack(data: &[u8]) -> Self;
     fn pack(&self, data: &mut [u8]);
+
+    /// Convert data of the previous layout into the current one
+    fn upgrade(tag: u8, _data: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Err(Error::Custom(format!(
+            "Account tag {tag} can't be upgraded"
+        )))
+    }
 }
 
 struct AccountParts<'a> {
@@ -105,15 +128,16 @@ where
     pub const TAG: u8 = T::TAG;
 
     pub fn from_account(program_id: &Pubkey, info: &'a AccountInfo<'a>) -> Result<Self> {
-        if info.owner != program_id {
-            return Err(Error::AccountInvalidOwner(*info.key, *program_id));
+        // Previous layouts may be shorter than the current one, check the tag first
+        let tag = tag(program_id, info)?;
+        if Self::deprecated_size(tag).is_some() {
+            return Err(Error::AccountOutdated(*info.key, tag));
         }
-
-        let parts = split_account_data(info, T::SIZE)?;
-        if *parts.tag != T::TAG {
+        if tag != T::TAG {
             return Err(Error::AccountInvalidTag(*info.key, T::TAG));
         }
 
+        let parts = split_account_data(info, T::SIZE)?;
         let data = T::unpack(&parts.data);
 
         Ok(Self {
@@ -154,6 +178,125 @@ where
         })
     }
 
+    /// Data size of the previous layout with the `tag`
+    #[must_use]
+    pub fn deprecated_size(tag: u8) -> Option<usize> {
+        T::DEPRECATED
+            .iter()
+            .find(|(deprecated_tag, _)| *deprecated_tag == tag)
+            .map(|(_, size)| *size)
+    }
+
+    /// Upgrade account data from the previous layout to the current one.
+    /// Data following the header is preserved. Returns `false` if the account is not in a previous layout of `T`.
+    pub fn migrate(
+        program_id: &Pubkey,
+        info: &'a AccountInfo<'a>,
+        operator: &Operator<'a>,
+        system_program: &program::System<'a>,
+    ) -> Result<bool> {
+        let tag = tag(program_id, info)?;
+        let old_size = match Self::deprecated_size(tag) {
+            Some(size) => size,
+            None => return Ok(false),
+        };
+
+        if !info.is_writable {
+            return Err(Error::AccountNotWritable(*info.key));
+        }
+
+        let old_len = info.data_len();
+        if old_len < 1 + old_size {
+            return Err(Error::AccountInvalidData(*info.key));
+        }
+        let new_len = old_len - old_size + T::SIZE;
+
+        debug_print!(
+            "migrate account {} from tag {} to {}",
+            info.key,
+            tag,
+            T::TAG
+        );
+
+        let data = {
+            let account_data = info.try_borrow_data()?;
+            T::upgrade(tag, &account_data[1..1 + old_size])?
+        };
+
+        if new_len > old_len {
+            let rent = Rent::get()?;
+            let required_lamports = rent
+                .minimum_balance(new_len)
+                .saturating_sub(info.lamports());
+            if required_lamports > 0 {
+                system_program.transfer(operator, info, required_lamports)?;
+            }
+
+            info.realloc(new_len, false)?;
+        }
+
+        info.try_borrow_mut_data()?
+            .copy_within(1 + old_size..old_len, 1 + T::SIZE);
+
+        if new_len < old_len {
+            info.realloc(new_len, false)?;
+        }
+
+        let mut parts = split_account_data(info, T::SIZE)?;
+        *parts.tag = T::TAG;
+        data.pack(&mut parts.data);
+
+        Ok(true)
+    }
+
+    /// Upgrade account data from the previous layout without reallocation, for instructions
+    /// without the system program. Only `used_len` bytes following the header are preserved,
+    /// the header grows into the unused end of the account.
+    pub fn migrate_within(
+        program_id: &Pubkey,
+        info: &'a AccountInfo<'a>,
+        used_len: impl FnOnce(&T) -> usize,
+    ) -> Result<bool> {
+        let tag = tag(program_id, info)?;
+        let old_size = match Self::deprecated_size(tag) {
+            Some(size) => size,
+            None => return Ok(false),
+        };
+
+        if !info.is_writable {
+            return Err(Error::AccountNotWritable(*info.key));
+        }
+
+        let data = {
+            let account_data = info.try_borrow_data()?;
+            if account_data.len() < 1 + old_size {
+                return Err(Error::AccountInvalidData(*info.key));
+            }
+            T::upgrade(tag, &account_data[1..1 + old_size])?
+        };
+
+        let used_len = used_len(&data);
+        if info.data_len() < 1 + old_size.max(T::SIZE) + used_len {
+            return Err(Error::AccountInvalidData(*info.key));
+        }
+
+        debug_print!(
+            "migrate account {} from tag {} to {} in place",
+            info.key,
+            tag,
+            T::TAG
+        );
+
+        info.try_borrow_mut_data()?
+            .copy_within(1 + old_size..1 + old_size + used_len, 1 + T::SIZE);
+
+        let mut parts = split_account_data(info, T::SIZE)?;
+        *parts.tag = T::TAG;
+        data.pack(&mut parts.data);
+
+        Ok(true)
+    }
+
     /// # Safety
     /// *Delete account*. Transfer lamports to the operator.
     /// All data stored in the account will be lost
@@ -250,6 +393,23 @@ pub fn tag(program_id: &Pubkey, info: &AccountInfo) -> Result<u8> {
     Ok(data[0])
 }
 
+/// Upgrade account of any type from the previous layout to the current one.
+/// Returns `false` if the account layout is up to date.
+pub fn migrate<'a>(
+    program_id: &Pubkey,
+    info: &'a AccountInfo<'a>,
+    operator: &Operator<'a>,
+    system_program: &program::System<'a>,
+) -> Result<bool> {
+    let migrated = EthereumAccount::migrate(program_id, info, operator, system_program)?
+        || EthereumStorage::migrate(program_id, info, operator, system_program)?
+        || State::migrate(program_id, info, operator, system_program)?
+        || FinalizedState::migrate(program_id, info, operator, system_program)?
+        || Holder::migrate(program_id, info, operator, system_program)?;
+
+    Ok(migrated)
+}
+
 /// # Safety
 /// *Permanently delete all data* in the account. Transfer lamports to the operator.
 pub unsafe fn delete(account: &AccountInfo, operator: &Operator) {
@@ -1,3 +1,5 @@
+use super::operator_registry::OperatorStatus;
+use super::OperatorRegistry;
 use crate::error::Error;
 use solana_program::account_info::AccountInfo;
 use solana_program::program_error::ProgramError;
@@ -8,10 +10,24 @@ pub struct Operator<'a> {
 }
 
 impl<'a> Operator<'a> {
-    pub fn from_account(info: &'a AccountInfo<'a>) -> Result<Self, ProgramError> {
-        let is_authorized = crate::config::AUTHORIZED_OPERATOR_LIST
-            .binary_search(info.key)
-            .is_ok();
+    /// Operator status is taken from the operator registry, if it is passed to the instruction.
+    /// Otherwise operators are checked against the compiled-in whitelist.
+    pub fn from_account(
+        info: &'a AccountInfo<'a>,
+        registry: Option<&OperatorRegistry>,
+    ) -> Result<Self, ProgramError> {
+        let is_authorized = match registry {
+            Some(registry) => match registry.status(info.key)? {
+                Some(OperatorStatus::Active) => true,
+                Some(OperatorStatus::Suspended) => {
+                    return Err!(Error::OperatorSuspended(*info.key).into(); "Account {} - operator is suspended", info.key);
+                }
+                None => false,
+            },
+            None => crate::config::AUTHORIZED_OPERATOR_LIST
+                .binary_search(info.key)
+                .is_ok(),
+        };
 
         if !is_authorized {
             return Err!(Error::UnauthorizedOperator.into(); "Account {} - expected authorized operator", info.key);
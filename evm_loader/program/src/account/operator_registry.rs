@@ -0,0 +1,277 @@
+use arrayref::{array_ref, array_refs};
+use solana_program::account_info::AccountInfo;
+use solana_program::pubkey::Pubkey;
+
+use crate::config::OPERATOR_REGISTRY_SEED;
+use crate::error::{Error, Result};
+
+use super::{managed, parameters, program, Operator, OperatorRegistry, Parameters};
+
+/// Serialized size of the registry entry: operator key and status
+const ENTRY_SIZE: usize = 32 + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OperatorStatus {
+    Active = 1,
+    Suspended = 2,
+}
+
+impl OperatorStatus {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Active),
+            2 => Some(Self::Suspended),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Registry;
+
+impl managed::Kind for Registry {
+    const TAG: u8 = super::TAG_OPERATOR_REGISTRY;
+    const SEED: &'static str = OPERATOR_REGISTRY_SEED;
+    const NAME: &'static str = "Operator Registry";
+    const ENTRY_SIZE: usize = ENTRY_SIZE;
+
+    fn invalid_authority(authority: Pubkey, expected: Pubkey) -> Error {
+        Error::OperatorRegistryInvalidAuthority(authority, expected)
+    }
+}
+
+/// Operator registry data account, entries are operator keys with their status
+pub type Data = managed::Data<Registry>;
+
+impl<'a> OperatorRegistry<'a> {
+    /// Split the optional registry account, passed as the last instruction account,
+    /// from the rest of accounts. Returns `None` and the accounts untouched if the last account
+    /// is not the created registry PDA, then operators are checked against the compiled-in whitelist.
+    pub fn split(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<(&'a [AccountInfo<'a>], Option<Self>)> {
+        Self::split_last(program_id, accounts)
+    }
+
+    /// Split the optional registry and parameters accounts, passed in any order
+    /// as the last instruction accounts
+    pub fn split_with_parameters(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<(&'a [AccountInfo<'a>], Option<Self>, parameters::Values)> {
+        let (accounts, registry) = Self::split(program_id, accounts)?;
+        let (accounts, parameters) = Parameters::split(program_id, accounts)?;
+        if registry.is_some() {
+            return Ok((accounts, registry, parameters));
+        }
+
+        let (accounts, registry) = Self::split(program_id, accounts)?;
+        Ok((accounts, registry, parameters))
+    }
+
+    fn unpack_entry(&self, entry: &[u8]) -> Result<(Pubkey, OperatorStatus)> {
+        let (key, status) = array_refs![array_ref![entry, 0, ENTRY_SIZE], 32, 1];
+        let status =
+            OperatorStatus::from_u8(status[0]).ok_or(Error::AccountInvalidData(*self.info.key))?;
+
+        Ok((Pubkey::new_from_array(*key), status))
+    }
+
+    fn position(&self, operator: &Pubkey) -> Result<Option<usize>> {
+        self.find_entry(|entry| entry[..32] == *operator.as_ref())
+    }
+
+    pub fn status(&self, operator: &Pubkey) -> Result<Option<OperatorStatus>> {
+        for entry in self.entries()?.chunks_exact(ENTRY_SIZE) {
+            let (key, status) = self.unpack_entry(entry)?;
+            if key == *operator {
+                return Ok(Some(status));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn operators(&self) -> Result<Vec<(Pubkey, OperatorStatus)>> {
+        self.entries()?
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| self.unpack_entry(entry))
+            .collect()
+    }
+
+    pub fn add(
+        &mut self,
+        operator: &Pubkey,
+        payer: &Operator<'a>,
+        system_program: &program::System<'a>,
+    ) -> Result<()> {
+        if self.position(operator)?.is_some() {
+            return Err(Error::OperatorAlreadyRegistered(*operator));
+        }
+
+        let mut entry = [0_u8; ENTRY_SIZE];
+        entry[..32].copy_from_slice(operator.as_ref());
+        entry[32] = OperatorStatus::Active as u8;
+
+        self.push_entry(&entry, payer, system_program)?;
+
+        Ok(())
+    }
+
+    /// Remove the operator and refund the rent of the released space to the `refund` account
+    pub fn remove(&mut self, operator: &Pubkey, refund: &AccountInfo<'a>) -> Result<()> {
+        let index = self
+            .position(operator)?
+            .ok_or(Error::OperatorNotRegistered(*operator))?;
+
+        self.swap_remove_entry(index, refund)
+    }
+
+    pub fn set_status(&mut self, operator: &Pubkey, status: OperatorStatus) -> Result<()> {
+        let index = self
+            .position(operator)?
+            .ok_or(Error::OperatorNotRegistered(*operator))?;
+
+        self.entry_mut(index)?[32] = status as u8;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::managed::tests::TestAccount;
+
+    fn registry_account(
+        program_id: &Pubkey,
+        operators: &[(Pubkey, OperatorStatus)],
+    ) -> TestAccount {
+        let entries: Vec<[u8; ENTRY_SIZE]> = operators
+            .iter()
+            .map(|(operator, status)| {
+                let mut entry = [*status as u8; ENTRY_SIZE];
+                entry[..32].copy_from_slice(operator.as_ref());
+                entry
+            })
+            .collect();
+        let entries: Vec<&[u8]> = entries.iter().map(|entry| &entry[..]).collect();
+
+        TestAccount::pda::<Registry>(program_id, Pubkey::new_unique(), &entries)
+    }
+
+    fn operator_account(key: Pubkey) -> TestAccount {
+        TestAccount::system(key, true)
+    }
+
+    fn is_authorized(registry: &mut TestAccount, operator: Pubkey) -> Result<bool> {
+        let program_id = registry.owner;
+        let mut operator = operator_account(operator);
+        let accounts = [operator.info(), registry.info()];
+
+        let (rest, registry) = OperatorRegistry::split(&program_id, &accounts)?;
+        assert_eq!(rest.len(), 1);
+
+        Ok(Operator::from_account(&rest[0], registry.as_ref()).is_ok())
+    }
+
+    #[test]
+    fn registry_is_authoritative() {
+        let program_id = Pubkey::new_unique();
+        let active = Pubkey::new_unique();
+        let suspended = Pubkey::new_unique();
+        let whitelisted = crate::config::AUTHORIZED_OPERATOR_LIST[0];
+
+        let mut registry = registry_account(
+            &program_id,
+            &[
+                (active, OperatorStatus::Active),
+                (suspended, OperatorStatus::Suspended),
+            ],
+        );
+
+        assert!(is_authorized(&mut registry, active).unwrap());
+        assert!(!is_authorized(&mut registry, suspended).unwrap());
+        assert!(!is_authorized(&mut registry, whitelisted).unwrap());
+        assert!(!is_authorized(&mut registry, Pubkey::new_unique()).unwrap());
+    }
+
+    #[test]
+    fn whitelist_without_registry() {
+        let program_id = Pubkey::new_unique();
+        let whitelisted = crate::config::AUTHORIZED_OPERATOR_LIST[0];
+
+        // Not created registry PDA or any other account is left to the instruction
+        let mut uncreated = operator_account(OperatorRegistry::address(&program_id).0);
+        uncreated.is_signer = false;
+        let mut other = operator_account(Pubkey::new_unique());
+        other.is_signer = false;
+
+        for last in [&mut uncreated, &mut other] {
+            let is_authorized = |last: &mut TestAccount, operator| {
+                let mut operator = operator_account(operator);
+                let accounts = [operator.info(), last.info()];
+                let (rest, registry) = OperatorRegistry::split(&program_id, &accounts).unwrap();
+                assert_eq!(rest.len(), 2);
+                assert!(registry.is_none());
+                Operator::from_account(&rest[0], None).is_ok()
+            };
+
+            assert!(is_authorized(last, whitelisted));
+            assert!(!is_authorized(last, Pubkey::new_unique()));
+        }
+
+        let accounts: &[AccountInfo] = &[];
+        assert!(OperatorRegistry::split(&program_id, accounts)
+            .unwrap()
+            .1
+            .is_none());
+    }
+
+    #[test]
+    fn registry_at_other_key() {
+        let program_id = Pubkey::new_unique();
+
+        let mut operator = operator_account(Pubkey::new_unique());
+        let mut registry = registry_account(&program_id, &[]);
+        registry.key = Pubkey::new_unique();
+
+        let accounts = [operator.info(), registry.info()];
+        assert!(matches!(
+            OperatorRegistry::split(&program_id, &accounts),
+            Err(Error::AccountInvalidKey(..))
+        ));
+    }
+
+    #[test]
+    fn registry_and_parameters_in_any_order() {
+        let program_id = Pubkey::new_unique();
+        let mut operator = operator_account(Pubkey::new_unique());
+        let mut registry = registry_account(&program_id, &[]);
+        let mut parameters = parameters::tests::parameters_account(&program_id, &[(0, 42)]);
+
+        let split = |accounts: &mut [&mut TestAccount]| {
+            let infos: Vec<AccountInfo> =
+                accounts.iter_mut().map(|account| account.info()).collect();
+            let (rest, registry, values) =
+                OperatorRegistry::split_with_parameters(&program_id, &infos).unwrap();
+            (rest.len(), registry.is_some(), values.payment_to_treasure)
+        };
+
+        assert_eq!(
+            split(&mut [&mut operator, &mut registry, &mut parameters]),
+            (1, true, 42)
+        );
+        assert_eq!(
+            split(&mut [&mut operator, &mut parameters, &mut registry]),
+            (1, true, 42)
+        );
+        assert_eq!(
+            split(&mut [&mut operator, &mut registry]),
+            (1, true, crate::config::PAYMENT_TO_TREASURE)
+        );
+        assert_eq!(split(&mut [&mut operator, &mut parameters]), (1, false, 42));
+    }
+}
@@ -0,0 +1,247 @@
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::account_info::AccountInfo;
+use solana_program::pubkey::Pubkey;
+
+use crate::config::{
+    CANCEL_TIMEOUT_SLOTS, EVM_STEPS_MIN, GAS_LIMIT_MULTIPLIER_NO_CHAINID, HOLDER_MSG_SIZE,
+    OPERATOR_PRIORITY_SLOTS, PARAMETERS_SEED, PAYMENT_TO_TREASURE,
+};
+use crate::error::{Error, Result};
+
+use super::{managed, Parameters};
+
+/// Effective values of the runtime parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Values {
+    pub payment_to_treasure: u64,
+    pub evm_steps_min: u64,
+    pub holder_msg_size: u64,
+    pub operator_priority_slots: u64,
+    pub gas_limit_multiplier_no_chainid: u64,
+    pub cancel_timeout_slots: u64,
+}
+
+impl Default for Values {
+    fn default() -> Self {
+        Self {
+            payment_to_treasure: PAYMENT_TO_TREASURE,
+            evm_steps_min: EVM_STEPS_MIN,
+            holder_msg_size: HOLDER_MSG_SIZE,
+            operator_priority_slots: OPERATOR_PRIORITY_SLOTS,
+            gas_limit_multiplier_no_chainid: GAS_LIMIT_MULTIPLIER_NO_CHAINID,
+            cancel_timeout_slots: CANCEL_TIMEOUT_SLOTS,
+        }
+    }
+}
+
+/// Names of the overridable parameters, index is used in the update instruction
+pub const NAMES: [&str; 6] = [
+    "PAYMENT_TO_TREASURE",
+    "EVM_STEPS_MIN",
+    "HOLDER_MSG_SIZE",
+    "OPERATOR_PRIORITY_SLOTS",
+    "GAS_LIMIT_MULTIPLIER_NO_CHAINID",
+    "CANCEL_TIMEOUT_SLOTS",
+];
+
+/// Serialized size of the override entry: set flag and value
+const ENTRY_SIZE: usize = 1 + 8;
+
+#[derive(Debug)]
+pub struct Overrides;
+
+impl managed::Kind for Overrides {
+    const TAG: u8 = super::TAG_PARAMETERS;
+    const SEED: &'static str = PARAMETERS_SEED;
+    const NAME: &'static str = "Parameters";
+    const ENTRY_SIZE: usize = ENTRY_SIZE;
+    const INITIAL_COUNT: usize = NAMES.len();
+
+    fn invalid_authority(authority: Pubkey, expected: Pubkey) -> Error {
+        Error::ParametersInvalidAuthority(authority, expected)
+    }
+}
+
+/// Runtime parameters data account, entries are the overrides in the `NAMES` order.
+/// Parameters added after the account creation have no entry and use compiled defaults.
+pub type Data = managed::Data<Overrides>;
+
+fn unpack_option(data: &[u8; ENTRY_SIZE]) -> Option<u64> {
+    let (flag, value) = array_refs![data, 1, 8];
+    (flag[0] != 0).then_some(u64::from_le_bytes(*value))
+}
+
+fn pack_option(value: Option<u64>, data: &mut [u8; ENTRY_SIZE]) {
+    let (flag, bytes) = mut_array_refs![data, 1, 8];
+    flag[0] = u8::from(value.is_some());
+    *bytes = value.unwrap_or_default().to_le_bytes();
+}
+
+impl<'a> Parameters<'a> {
+    /// Split the optional parameters account, passed as the last instruction account,
+    /// from the rest of accounts. Returns compiled defaults and the accounts untouched
+    /// if the last account is not the created parameters PDA.
+    pub fn split(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<(&'a [AccountInfo<'a>], Values)> {
+        let (accounts, parameters) = Self::split_last(program_id, accounts)?;
+        let values = match parameters {
+            Some(parameters) => parameters.values()?,
+            None => Values::default(),
+        };
+
+        Ok((accounts, values))
+    }
+
+    /// Overridden values in the `NAMES` order, `None` - compiled default is used
+    pub fn overrides(&self) -> Result<[Option<u64>; NAMES.len()]> {
+        let mut overrides = [None; NAMES.len()];
+
+        let entries = self.entries()?;
+        for (value, entry) in overrides.iter_mut().zip(entries.chunks_exact(ENTRY_SIZE)) {
+            *value = unpack_option(array_ref![entry, 0, ENTRY_SIZE]);
+        }
+
+        Ok(overrides)
+    }
+
+    pub fn values(&self) -> Result<Values> {
+        let overrides = self.overrides()?;
+        let defaults = Values::default();
+
+        Ok(Values {
+            payment_to_treasure: overrides[0].unwrap_or(defaults.payment_to_treasure),
+            evm_steps_min: overrides[1].unwrap_or(defaults.evm_steps_min),
+            holder_msg_size: overrides[2].unwrap_or(defaults.holder_msg_size),
+            operator_priority_slots: overrides[3].unwrap_or(defaults.operator_priority_slots),
+            gas_limit_multiplier_no_chainid: overrides[4]
+                .unwrap_or(defaults.gas_limit_multiplier_no_chainid),
+            cancel_timeout_slots: overrides[5].unwrap_or(defaults.cancel_timeout_slots),
+        })
+    }
+
+    /// Override parameter by index in `NAMES`, `None` restores compiled default
+    pub fn set(&mut self, index: usize, value: Option<u64>) -> Result<()> {
+        if index >= NAMES.len() {
+            return Err(Error::Custom(format!("Unknown parameter index {index}")));
+        }
+
+        // Used as divisors or multipliers, zero breaks gas calculation
+        if (value == Some(0)) && matches!(NAMES[index], "EVM_STEPS_MIN" | "HOLDER_MSG_SIZE") {
+            return Err(Error::Custom(format!(
+                "Parameter {} can't be zero",
+                NAMES[index]
+            )));
+        }
+
+        if index >= self.count {
+            return Err(Error::Custom(format!(
+                "Parameter {} has no entry in the parameters account",
+                NAMES[index]
+            )));
+        }
+
+        let mut entry = self.entry_mut(index)?;
+        pack_option(value, array_mut_ref![entry, 0, ENTRY_SIZE]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::account::managed::tests::TestAccount;
+
+    /// Created parameters account with the `(index, value)` overrides
+    pub fn parameters_account(program_id: &Pubkey, overrides: &[(usize, u64)]) -> TestAccount {
+        let mut entries = [[0_u8; ENTRY_SIZE]; NAMES.len()];
+        for (index, value) in overrides {
+            pack_option(Some(*value), &mut entries[*index]);
+        }
+        let entries: Vec<&[u8]> = entries.iter().map(|entry| &entry[..]).collect();
+
+        TestAccount::pda::<Overrides>(program_id, Pubkey::new_unique(), &entries)
+    }
+
+    fn split(program_id: &Pubkey, account: &mut TestAccount) -> Result<(usize, Values)> {
+        let accounts = [account.info()];
+
+        let (rest, values) = Parameters::split(program_id, &accounts)?;
+        Ok((rest.len(), values))
+    }
+
+    #[test]
+    fn split_parameters() {
+        let program_id = Pubkey::new_unique();
+
+        // Not created yet, the account is left to the instruction
+        let mut uncreated = TestAccount::system(Parameters::address(&program_id).0, false);
+        assert_eq!(
+            split(&program_id, &mut uncreated).unwrap(),
+            (1, Values::default())
+        );
+
+        let mut parameters = parameters_account(&program_id, &[(0, 42), (5, 10)]);
+        let (rest, values) = split(&program_id, &mut parameters).unwrap();
+        assert_eq!(rest, 0);
+        assert_eq!(values.payment_to_treasure, 42);
+        assert_eq!(values.evm_steps_min, EVM_STEPS_MIN);
+        assert_eq!(values.cancel_timeout_slots, 10);
+
+        // Not passed
+        let mut other = TestAccount::system(Pubkey::new_unique(), false);
+        assert_eq!(
+            split(&program_id, &mut other).unwrap(),
+            (1, Values::default())
+        );
+
+        // Parameters data at other key
+        parameters.key = Pubkey::new_unique();
+        assert!(matches!(
+            split(&program_id, &mut parameters),
+            Err(Error::AccountInvalidKey(..))
+        ));
+    }
+
+    #[test]
+    fn parameter_without_entry() {
+        let program_id = Pubkey::new_unique();
+
+        // Created before the rest of parameters were added
+        let mut entry = [0_u8; ENTRY_SIZE];
+        pack_option(Some(42), &mut entry);
+        let mut account =
+            TestAccount::pda::<Overrides>(&program_id, Pubkey::new_unique(), &[&entry]);
+
+        let info = account.info();
+        let mut parameters = Parameters::from_pda(&program_id, &info).unwrap();
+        let values = parameters.values().unwrap();
+        assert_eq!(values.payment_to_treasure, 42);
+        assert_eq!(values.cancel_timeout_slots, CANCEL_TIMEOUT_SLOTS);
+
+        assert!(parameters.set(5, Some(10)).is_err());
+    }
+
+    #[test]
+    fn zero_divisor() {
+        let program_id = Pubkey::new_unique();
+        let mut account = parameters_account(&program_id, &[]);
+
+        let info = account.info();
+        let mut parameters = Parameters::from_pda(&program_id, &info).unwrap();
+
+        assert!(parameters.set(1, Some(0)).is_err());
+        assert!(parameters.set(2, Some(0)).is_err());
+        assert!(parameters.set(NAMES.len(), Some(1)).is_err());
+
+        parameters.set(2, Some(100)).unwrap();
+        assert_eq!(parameters.values().unwrap().holder_msg_size, 100);
+        parameters.set(2, None).unwrap();
+        assert_eq!(
+            parameters.values().unwrap().holder_msg_size,
+            HOLDER_MSG_SIZE
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use super::{EthereumAccount, Operator, ACCOUNT_SEED_VERSION};
+use super::{EthereumAccount, Operator};
+use crate::config::CHAIN_ID;
 use solana_program::account_info::AccountInfo;
 use solana_program::program::{invoke_signed_unchecked, invoke_unchecked};
 use solana_program::program_error::ProgramError;
@@ -105,21 +106,21 @@ impl<'a> System<'a> {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_account_with_seed(
         &self,
         payer: &Operator<'a>,
         base: &EthereumAccount<'a>,
+        chain_id: u64,
         owner: &Pubkey,
         new_account: &AccountInfo<'a>,
         seed: &str,
         space: usize,
     ) -> Result<(), ProgramError> {
         let minimum_balance = Rent::get()?.minimum_balance(space).max(1);
-        let signer_seeds: &[&[u8]] = &[
-            &[ACCOUNT_SEED_VERSION],
-            base.address.as_bytes(),
-            &[base.bump_seed],
-        ];
+        let mut signer_seeds = base.address.solana_address_seeds(chain_id, CHAIN_ID);
+        signer_seeds.push(vec![base.bump_seed]);
+        let signer_seeds: &[&[u8]] = &signer_seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
 
         if new_account.lamports() > 0 {
             let required_lamports = minimum_balance.saturating_sub(new_account.lamports());
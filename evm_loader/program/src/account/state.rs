@@ -1,4 +1,6 @@
 use super::Packable;
+use crate::config::CHAIN_ID;
+use crate::error::{Error, Result};
 use crate::types::Address;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use ethnum::U256;
@@ -21,6 +23,10 @@ pub struct Data {
     pub operator: Pubkey,
     /// Starting slot for this operator
     pub slot: u64,
+    /// Last slot the transaction was progressed by any operator
+    pub last_progress_slot: u64,
+    /// Chain id of the transaction
+    pub chain_id: u64,
     /// Stored accounts length
     pub accounts_len: usize,
     /// Stored EVM State length
@@ -40,7 +46,9 @@ impl Packable for Data {
     /// Storage struct tag
     const TAG: u8 = super::TAG_STATE;
     /// Storage struct serialized size
-    const SIZE: usize = 32 + 32 + 20 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8;
+    const SIZE: usize = 32 + 32 + 20 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8;
+    /// Layout without the last progress slot and chain id
+    const DEPRECATED: &'static [(u8, usize)] = &[(super::TAG_STATE_V4, Self::SIZE - 16)];
 
     /// Deserialize `Storage` struct from input data
     #[must_use]
@@ -56,10 +64,12 @@ impl Packable for Data {
             gas_used,
             operator,
             slot,
+            last_progress_slot,
+            chain_id,
             accounts_len,
             evm_state_len,
             evm_machine_len,
-        ) = array_refs![data, 32, 32, 20, 32, 32, 32, 32, 8, 8, 8, 8];
+        ) = array_refs![data, 32, 32, 20, 32, 32, 32, 32, 8, 8, 8, 8, 8, 8];
 
         Self {
             owner: Pubkey::new_from_array(*owner),
@@ -70,6 +80,8 @@ impl Packable for Data {
             gas_used: U256::from_le_bytes(*gas_used),
             operator: Pubkey::new_from_array(*operator),
             slot: u64::from_le_bytes(*slot),
+            last_progress_slot: u64::from_le_bytes(*last_progress_slot),
+            chain_id: u64::from_le_bytes(*chain_id),
             accounts_len: usize::from_le_bytes(*accounts_len),
             evm_state_len: usize::from_le_bytes(*evm_state_len),
             evm_machine_len: usize::from_le_bytes(*evm_machine_len),
@@ -89,10 +101,12 @@ impl Packable for Data {
             gas_used,
             operator,
             slot,
+            last_progress_slot,
+            chain_id,
             accounts_len,
             evm_state_len,
             evm_machine_len,
-        ) = mut_array_refs![data, 32, 32, 20, 32, 32, 32, 32, 8, 8, 8, 8];
+        ) = mut_array_refs![data, 32, 32, 20, 32, 32, 32, 32, 8, 8, 8, 8, 8, 8];
 
         owner.copy_from_slice(self.owner.as_ref());
         hash.copy_from_slice(&self.transaction_hash);
@@ -102,10 +116,39 @@ impl Packable for Data {
         *gas_used = self.gas_used.to_le_bytes();
         operator.copy_from_slice(self.operator.as_ref());
         *slot = self.slot.to_le_bytes();
+        *last_progress_slot = self.last_progress_slot.to_le_bytes();
+        *chain_id = self.chain_id.to_le_bytes();
         *accounts_len = self.accounts_len.to_le_bytes();
         *evm_state_len = self.evm_state_len.to_le_bytes();
         *evm_machine_len = self.evm_machine_len.to_le_bytes();
     }
+
+    /// The transaction was last progressed in the operator starting slot,
+    /// transactions of the previous layout are executed in the default chain
+    fn upgrade(tag: u8, src: &[u8]) -> Result<Self> {
+        if tag != super::TAG_STATE_V4 {
+            return Err(Error::Custom(format!(
+                "Account tag {tag} can't be upgraded"
+            )));
+        }
+
+        #[allow(clippy::use_self)]
+        let data = array_ref![src, 0, Data::SIZE - 16];
+        let (head, slot, tail) = array_refs![data, 212, 8, 24];
+
+        let mut current = [0_u8; Data::SIZE];
+        {
+            let (current_head, current_slot, last_progress_slot, chain_id, current_tail) =
+                mut_array_refs![&mut current, 212, 8, 8, 8, 24];
+            current_head.copy_from_slice(head);
+            *current_slot = *slot;
+            *last_progress_slot = *slot;
+            *chain_id = CHAIN_ID.to_le_bytes();
+            current_tail.copy_from_slice(tail);
+        }
+
+        Ok(Self::unpack(&current))
+    }
 }
 
 impl Packable for FinalizedData {
@@ -137,3 +180,108 @@ impl Packable for FinalizedData {
         hash.copy_from_slice(&self.transaction_hash);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{State, TAG_STATE_V4};
+    use solana_program::account_info::AccountInfo;
+
+    #[test]
+    fn pack_unpack() {
+        let data = Data {
+            owner: Pubkey::new_unique(),
+            transaction_hash: [1; 32],
+            caller: Address::from([2; 20]),
+            gas_limit: U256::new(3),
+            gas_price: U256::new(4),
+            gas_used: U256::new(5),
+            operator: Pubkey::new_unique(),
+            slot: 6,
+            last_progress_slot: 7,
+            chain_id: 11,
+            accounts_len: 8,
+            evm_state_len: 9,
+            evm_machine_len: 10,
+        };
+
+        let mut buffer = [0_u8; Data::SIZE];
+        data.pack(&mut buffer);
+        let unpacked = Data::unpack(&buffer);
+
+        assert_eq!(unpacked.owner, data.owner);
+        assert_eq!(unpacked.caller, data.caller);
+        assert_eq!(unpacked.gas_used, data.gas_used);
+        assert_eq!(unpacked.operator, data.operator);
+        assert_eq!(unpacked.slot, 6);
+        assert_eq!(unpacked.last_progress_slot, 7);
+        assert_eq!(unpacked.chain_id, 11);
+        assert_eq!(unpacked.accounts_len, 8);
+        assert_eq!(unpacked.evm_machine_len, 10);
+    }
+
+    #[test]
+    fn upgrade_v4() {
+        let data = Data {
+            owner: Pubkey::new_unique(),
+            transaction_hash: [1; 32],
+            caller: Address::from([2; 20]),
+            gas_limit: U256::new(3),
+            gas_price: U256::new(4),
+            gas_used: U256::new(5),
+            operator: Pubkey::new_unique(),
+            slot: 6,
+            last_progress_slot: 0,
+            chain_id: 0,
+            accounts_len: 8,
+            evm_state_len: 9,
+            evm_machine_len: 10,
+        };
+
+        let mut current = [0_u8; Data::SIZE];
+        data.pack(&mut current);
+        // Previous layout has no last progress slot and chain id
+        let mut previous = current[..212 + 8].to_vec();
+        previous.extend_from_slice(&current[212 + 24..]);
+
+        let upgraded = Data::upgrade(TAG_STATE_V4, &previous).unwrap();
+        assert_eq!(upgraded.owner, data.owner);
+        assert_eq!(upgraded.operator, data.operator);
+        assert_eq!(upgraded.slot, 6);
+        assert_eq!(upgraded.last_progress_slot, 6);
+        assert_eq!(upgraded.chain_id, CHAIN_ID);
+        assert_eq!(upgraded.accounts_len, 8);
+        assert_eq!(upgraded.evm_state_len, 9);
+        assert_eq!(upgraded.evm_machine_len, 10);
+
+        assert!(Data::upgrade(Data::TAG, &current).is_err());
+    }
+
+    #[test]
+    fn outdated_account() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        // Shorter than the current layout
+        let mut data = vec![0_u8; 1 + Data::SIZE - 16];
+        data[0] = TAG_STATE_V4;
+
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        assert_eq!(State::deprecated_size(TAG_STATE_V4), Some(Data::SIZE - 16));
+        assert!(matches!(
+            State::from_account(&program_id, &info),
+            Err(Error::AccountOutdated(_, tag)) if tag == TAG_STATE_V4
+        ));
+    }
+}
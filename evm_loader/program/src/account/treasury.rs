@@ -1,4 +1,4 @@
-use crate::config::TREASURY_POOL_SEED;
+use crate::config::{CHAIN_ID, TREASURY_POOL_SEED};
 use solana_program::{
     account_info::AccountInfo, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
 };
@@ -20,7 +20,17 @@ impl<'a> Treasury<'a> {
         index: u32,
         info: &'a AccountInfo<'a>,
     ) -> Result<Self, ProgramError> {
-        let (expected_key, bump_seed) = Treasury::address(program_id, index);
+        Self::from_account_in_chain(program_id, CHAIN_ID, index, info)
+    }
+
+    pub fn from_account_in_chain(
+        program_id: &Pubkey,
+        chain_id: u64,
+        index: u32,
+        info: &'a AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        let (expected_key, bump_seed) =
+            Treasury::address_in_chain(program_id, chain_id, CHAIN_ID, index);
         if *info.key != expected_key {
             return Err!(ProgramError::InvalidArgument; "Account {} - invalid treasure account", info.key);
         }
@@ -30,10 +40,34 @@ impl<'a> Treasury<'a> {
 
     #[must_use]
     pub fn address(program_id: &Pubkey, index: u32) -> (Pubkey, u8) {
-        Pubkey::find_program_address(
-            &[TREASURY_POOL_SEED.as_bytes(), &index.to_le_bytes()],
-            program_id,
-        )
+        Self::address_in_chain(program_id, CHAIN_ID, CHAIN_ID, index)
+    }
+
+    /// Treasury pool of the chain. The default chain keeps the legacy seeds.
+    #[must_use]
+    pub fn address_in_chain(
+        program_id: &Pubkey,
+        chain_id: u64,
+        default_chain_id: u64,
+        index: u32,
+    ) -> (Pubkey, u8) {
+        let seeds = Self::seeds(chain_id, default_chain_id, index);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        Pubkey::find_program_address(&seeds, program_id)
+    }
+
+    /// Treasury pool PDA seeds, without bump seed
+    #[must_use]
+    pub fn seeds(chain_id: u64, default_chain_id: u64, index: u32) -> Vec<Vec<u8>> {
+        let mut seeds = vec![
+            TREASURY_POOL_SEED.as_bytes().to_vec(),
+            index.to_le_bytes().to_vec(),
+        ];
+        if chain_id != default_chain_id {
+            seeds.push(chain_id.to_le_bytes().to_vec());
+        }
+
+        seeds
     }
 
     #[must_use]
@@ -90,3 +124,38 @@ impl<'a> Deref for MainTreasury<'a> {
         self.info
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn treasury_address_in_chain() {
+        let program_id = Pubkey::new_unique();
+
+        assert_eq!(
+            Treasury::address_in_chain(&program_id, 111, 111, 5),
+            Treasury::address(&program_id, 5)
+        );
+        assert_eq!(
+            Treasury::seeds(111, 111, 5),
+            vec![
+                TREASURY_POOL_SEED.as_bytes().to_vec(),
+                5_u32.to_le_bytes().to_vec()
+            ]
+        );
+
+        let (key, bump_seed) = Treasury::address_in_chain(&program_id, 112, 111, 5);
+        assert_ne!(key, Treasury::address(&program_id, 5).0);
+        assert_ne!(key, Treasury::address_in_chain(&program_id, 112, 111, 6).0);
+
+        let mut seeds = Treasury::seeds(112, 111, 5);
+        assert_eq!(seeds[2], 112_u64.to_le_bytes().to_vec());
+        seeds.push(vec![bump_seed]);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            Pubkey::create_program_address(&seeds, &program_id).unwrap(),
+            key
+        );
+    }
+}
@@ -16,7 +16,7 @@ use crate::account::{ether_account, program, EthereumAccount, EthereumStorage, O
 use crate::account_storage::{
     AccountOperation, AccountStorage, AccountsOperations, AccountsReadiness, ProgramAccountStorage,
 };
-use crate::config::STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT;
+use crate::config::{CHAIN_ID, STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT};
 use crate::executor::Action;
 use crate::types::Address;
 
@@ -32,6 +32,15 @@ impl<'a> ProgramAccountStorage<'a> {
             return Err!(ProgramError::InsufficientFunds; "Account {} - insufficient funds", origin);
         }
 
+        if !operator.is_in_chain(self.chain_id) {
+            return Err!(
+                ProgramError::InvalidArgument;
+                "Account {} - operator ether account doesn't belong to chain {}",
+                operator.info.key,
+                self.chain_id
+            );
+        }
+
         if operator.address == origin {
             return Ok(());
         }
@@ -218,6 +227,7 @@ impl<'a> ProgramAccountStorage<'a> {
         const STATIC_STORAGE_LIMIT: U256 = U256::new(STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT as u128);
 
         let mut required_account_transfers = std::collections::HashMap::new();
+        let mut zeroed_storage = std::collections::HashSet::new();
 
         for (address, storage) in storage {
             let contract: &EthereumAccount<'a> = &self.ethereum_accounts[&address];
@@ -253,6 +263,7 @@ impl<'a> ProgramAccountStorage<'a> {
 
                             let storage = EthereumStorage::create(
                                 contract,
+                                self.chain_id,
                                 storage_account,
                                 &storage_address,
                                 index,
@@ -267,6 +278,10 @@ impl<'a> ProgramAccountStorage<'a> {
                         Entry::Occupied(mut entry) => {
                             let storage = entry.get_mut();
                             storage.set(subindex, &value, &mut required_account_transfers)?;
+
+                            if value == [0_u8; 32] {
+                                zeroed_storage.insert(*entry.key());
+                            }
                         }
                     }
                 }
@@ -277,6 +292,19 @@ impl<'a> ProgramAccountStorage<'a> {
             system_program.transfer(operator, &info, required_lamports)?;
         }
 
+        // Return rent of the storage accounts which don't hold any value anymore
+        for key in zeroed_storage {
+            let is_zeroed = self
+                .storage_accounts
+                .get(&key)
+                .map_or(false, EthereumStorage::is_zeroed);
+
+            if is_zeroed {
+                let storage = self.storage_accounts.remove(&key).expect("storage exists");
+                storage.close(operator)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -289,7 +317,8 @@ impl<'a> ProgramAccountStorage<'a> {
     ) -> Result<AccountsReadiness, ProgramError> {
         let mut accounts_readiness = AccountsReadiness::Ready;
         for (address, operation) in accounts_operations {
-            let (solana_address, bump_seed) = address.find_solana_address(self.program_id);
+            let (solana_address, bump_seed) =
+                address.find_solana_address_in_chain(self.program_id, self.chain_id, CHAIN_ID);
             let solana_account = self.solana_account(&solana_address).ok_or_else(|| {
                 E!(
                     ProgramError::UninitializedAccount;
@@ -305,6 +334,7 @@ impl<'a> ProgramAccountStorage<'a> {
                         neon_program.key,
                         operator,
                         &address,
+                        self.chain_id,
                         solana_account,
                         bump_seed,
                         MAX_PERMITTED_DATA_INCREASE.min(space),
@@ -441,7 +471,8 @@ impl<'a> ProgramAccountStorage<'a> {
             return Ok(());
         }
 
-        let (solana_address, bump_seed) = address.find_solana_address(self.program_id);
+        let (solana_address, bump_seed) =
+            address.find_solana_address_in_chain(self.program_id, self.chain_id, CHAIN_ID);
         let info = self.solana_account(&solana_address).ok_or_else(|| {
             E!(
                 ProgramError::InvalidArgument;
@@ -1,10 +1,12 @@
 use crate::account::EthereumAccount;
 use crate::account_storage::{AccountStorage, ProgramAccountStorage};
-use crate::config::STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT;
+use crate::config::{CHAIN_ID, STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT};
+use crate::error::Result;
 use crate::executor::OwnedAccountInfo;
 use crate::types::Address;
 use ethnum::U256;
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
 use solana_program::{pubkey::Pubkey, sysvar::slot_hashes};
 use std::convert::TryInto;
 
@@ -12,7 +14,7 @@ use super::find_slot_hash;
 
 impl<'a> AccountStorage for ProgramAccountStorage<'a> {
     fn neon_token_mint(&self) -> &Pubkey {
-        &crate::config::token_mint::ID
+        &self.token_mint
     }
 
     fn program_id(&self) -> &Pubkey {
@@ -49,6 +51,10 @@ impl<'a> AccountStorage for ProgramAccountStorage<'a> {
         find_slot_hash(slot, &slot_hashes_data[..])
     }
 
+    fn clock(&self) -> Result<Clock> {
+        Ok(self.clock.clone())
+    }
+
     fn exists(&self, address: &Address) -> bool {
         self.ethereum_accounts.contains_key(address)
     }
@@ -133,7 +139,7 @@ impl<'a> AccountStorage for ProgramAccountStorage<'a> {
 
     fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
     where
-        F: FnOnce(&AccountInfo) -> R,
+        F: FnOnce(&AccountInfo) -> R + Send,
     {
         let info = self.solana_accounts[address];
         action(info)
@@ -153,12 +159,16 @@ impl<'a> AccountStorage for ProgramAccountStorage<'a> {
 
     fn solana_address(&self, address: &Address) -> (Pubkey, u8) {
         self.ethereum_accounts.get(address).map_or_else(
-            || address.find_solana_address(self.program_id),
+            || address.find_solana_address_in_chain(self.program_id, self.chain_id, CHAIN_ID),
             |a| (*a.info.key, a.bump_seed),
         )
     }
 
     fn chain_id(&self) -> u64 {
-        crate::config::CHAIN_ID
+        self.chain_id
+    }
+
+    fn default_chain_id(&self) -> u64 {
+        CHAIN_ID
     }
 }
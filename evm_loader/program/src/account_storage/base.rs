@@ -1,6 +1,8 @@
 use crate::account::ether_storage::EthereumStorageAddress;
 use crate::account::{program, EthereumAccount, EthereumStorage, Operator, TAG_EMPTY};
 use crate::account_storage::{AccountStorage, ProgramAccountStorage};
+use crate::config::CHAIN_ID;
+use crate::error::Error;
 use crate::types::Address;
 use ethnum::U256;
 use solana_program::account_info::AccountInfo;
@@ -19,7 +21,24 @@ impl<'a> ProgramAccountStorage<'a> {
         system_program: Option<&program::System<'a>>,
         accounts: &'a [AccountInfo<'a>],
     ) -> Result<Self, ProgramError> {
-        debug_print!("ProgramAccountStorage::new");
+        Self::new_in_chain(program_id, operator, system_program, accounts, CHAIN_ID)
+    }
+
+    /// Account storage in the balance namespace of the chain.
+    /// Ethereum accounts of other chains are ignored, chains not hosted by the program are rejected.
+    pub fn new_in_chain(
+        program_id: &'a Pubkey,
+        operator: &Operator<'a>,
+        system_program: Option<&program::System<'a>>,
+        accounts: &'a [AccountInfo<'a>],
+        chain_id: u64,
+    ) -> Result<Self, ProgramError> {
+        debug_print!("ProgramAccountStorage::new_in_chain");
+
+        let token_mint = match crate::config::chain_info(chain_id) {
+            Some(chain) => chain.token_mint,
+            None => return Err(Error::InvalidChainId(chain_id.into()).into()),
+        };
 
         let mut solana_accounts = accounts
             .iter()
@@ -42,6 +61,10 @@ impl<'a> ProgramAccountStorage<'a> {
             match crate::account::tag(program_id, account_info) {
                 Ok(EthereumAccount::TAG) => {
                     let account = EthereumAccount::from_account(program_id, account_info)?;
+                    if !account.is_in_chain(chain_id) {
+                        continue;
+                    }
+
                     ethereum_accounts.insert(account.address, account);
                 }
                 Ok(EthereumStorage::TAG) => {
@@ -52,6 +75,19 @@ impl<'a> ProgramAccountStorage<'a> {
             }
         }
 
+        if crate::config::is_multichain() {
+            storage_accounts.retain(|_, storage| {
+                ethereum_accounts
+                    .get(&storage.address)
+                    .map_or(false, |owner| {
+                        let expected =
+                            EthereumStorageAddress::new(program_id, owner.info.key, &storage.index);
+                        expected.pubkey() == storage.info.key
+                    })
+            });
+        }
+
+        // Outdated accounts are closed by the collect-storage sweep, here they are only reset
         for storage in storage_accounts.values_mut() {
             let owner = &ethereum_accounts[&storage.address];
             if storage.generation != owner.generation {
@@ -61,6 +97,8 @@ impl<'a> ProgramAccountStorage<'a> {
 
         Ok(Self {
             program_id,
+            chain_id,
+            token_mint,
             operator: operator.key,
             clock: Clock::get()?,
             solana_accounts,
@@ -114,7 +152,8 @@ impl<'a> ProgramAccountStorage<'a> {
             return None;
         }
 
-        let (solana_address, _bump_seed) = address.find_solana_address(self.program_id);
+        let (solana_address, _bump_seed) =
+            address.find_solana_address_in_chain(self.program_id, self.chain_id, CHAIN_ID);
         if let Some(account) = self.solana_accounts.get(&solana_address) {
             assert!(
                 self.is_account_empty(account),
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use ethnum::U256;
+use solana_program::account_info::{AccountInfo, IntoAccountInfo};
+use solana_program::clock::Clock;
+use solana_program::keccak::hash;
+use solana_program::pubkey::Pubkey;
+
+use crate::account::EthereumAccount;
+use crate::config::{chain_info, CHAIN_ID};
+use crate::error::{Error, Result};
+use crate::evm::{Buffer, ExitStatus, Machine};
+use crate::executor::{Action, ExecutorState, OwnedAccountInfo};
+use crate::types::{Address, LegacyTx, Transaction, TransactionPayload};
+
+use super::{generate_fake_slot_hash, AccountStorage};
+
+/// Ethereum account kept in memory
+#[derive(Debug, Default, Clone)]
+pub struct MemoryAccount {
+    pub nonce: u64,
+    pub balance: U256,
+    pub code: Vec<u8>,
+    pub generation: u32,
+    pub storage: HashMap<U256, [u8; 32]>,
+}
+
+/// Result of the transaction executed against `MemoryAccountStorage`
+#[derive(Debug)]
+pub struct MemoryExecutionResult {
+    pub exit_status: ExitStatus,
+    pub steps_executed: u64,
+    pub actions: Vec<Action>,
+}
+
+/// In-memory `AccountStorage`, runs the EVM without Solana accounts.
+/// External instructions (Solana CPI from precompiles) are not executed.
+pub struct MemoryAccountStorage {
+    program_id: Pubkey,
+    operator: Pubkey,
+    neon_token_mint: Pubkey,
+    chain_id: u64,
+    block_number: u64,
+    block_timestamp: i64,
+    accounts: HashMap<Address, MemoryAccount>,
+    solana_accounts: HashMap<Pubkey, OwnedAccountInfo>,
+}
+
+impl MemoryAccountStorage {
+    /// # Panics
+    /// Will panic if the chain is not hosted by the program
+    #[must_use]
+    pub fn new(program_id: Pubkey, chain_id: u64) -> Self {
+        let chain = chain_info(chain_id).expect("chain is hosted by the program");
+
+        Self {
+            program_id,
+            operator: Pubkey::default(),
+            neon_token_mint: chain.token_mint,
+            chain_id,
+            block_number: 0,
+            block_timestamp: 0,
+            accounts: HashMap::new(),
+            solana_accounts: HashMap::new(),
+        }
+    }
+
+    pub fn set_block(&mut self, number: u64, timestamp: i64) {
+        self.block_number = number;
+        self.block_timestamp = timestamp;
+    }
+
+    #[must_use]
+    pub fn account(&self, address: &Address) -> Option<&MemoryAccount> {
+        self.accounts.get(address)
+    }
+
+    pub fn account_mut(&mut self, address: Address) -> &mut MemoryAccount {
+        self.accounts.entry(address).or_default()
+    }
+
+    /// Add Solana account, visible to precompiles through `clone_solana_account`
+    pub fn add_solana_account(&mut self, account: OwnedAccountInfo) {
+        self.solana_accounts.insert(account.key, account);
+    }
+
+    pub fn fund(&mut self, address: Address, value: U256) {
+        let account = self.account_mut(address);
+        account.balance = account.balance.saturating_add(value);
+    }
+
+    /// Place runtime bytecode at the address, without running the constructor
+    pub fn set_code(&mut self, address: Address, code: Vec<u8>) {
+        self.account_mut(address).code = code;
+    }
+
+    /// Deploy the contract, `init_code` is the creation bytecode with constructor arguments
+    pub async fn deploy(
+        &mut self,
+        from: Address,
+        init_code: &[u8],
+        value: U256,
+    ) -> Result<(Address, MemoryExecutionResult)> {
+        let nonce = self.nonce(&from).await;
+        let address = Address::from_create(&from, nonce);
+
+        let result = self.execute(from, None, init_code, value).await?;
+        Ok((address, result))
+    }
+
+    pub async fn call(
+        &mut self,
+        from: Address,
+        to: Address,
+        data: &[u8],
+        value: U256,
+    ) -> Result<MemoryExecutionResult> {
+        self.execute(from, Some(to), data, value).await
+    }
+
+    /// Execute the transaction with zero gas price and commit its actions
+    pub async fn execute(
+        &mut self,
+        from: Address,
+        to: Option<Address>,
+        data: &[u8],
+        value: U256,
+    ) -> Result<MemoryExecutionResult> {
+        let mut trx = Transaction {
+            transaction: TransactionPayload::Legacy(LegacyTx {
+                nonce: self.nonce(&from).await,
+                gas_price: U256::ZERO,
+                gas_limit: U256::MAX,
+                target: to,
+                value,
+                call_data: Buffer::from_slice(data),
+                v: U256::default(),
+                r: U256::default(),
+                s: U256::default(),
+                chain_id: Some(self.chain_id.into()),
+                recovery_id: u8::default(),
+            }),
+            byte_len: usize::default(),
+            hash: <[u8; 32]>::default(),
+            signed_hash: <[u8; 32]>::default(),
+        };
+
+        let (exit_status, steps_executed, actions) = {
+            let mut backend = ExecutorState::new(&*self);
+
+            let mut evm = Machine::new(&mut trx, from, &mut backend, None).await?;
+            let (exit_status, steps_executed) = evm.execute(u64::MAX, &mut backend).await?;
+
+            (exit_status, steps_executed, backend.into_actions())
+        };
+
+        self.apply_actions(&actions)?;
+
+        Ok(MemoryExecutionResult {
+            exit_status,
+            steps_executed,
+            actions,
+        })
+    }
+
+    /// Commit actions of the executed transaction
+    pub fn apply_actions(&mut self, actions: &[Action]) -> Result<()> {
+        for action in actions {
+            match action {
+                Action::NeonTransfer {
+                    source,
+                    target,
+                    value,
+                } => {
+                    self.withdraw(source, *value)?;
+                    self.fund(*target, *value);
+                }
+                Action::NeonWithdraw { source, value } => {
+                    self.withdraw(source, *value)?;
+                }
+                Action::EvmSetStorage {
+                    address,
+                    index,
+                    value,
+                } => {
+                    let storage = &mut self.account_mut(*address).storage;
+                    if *value == [0_u8; 32] {
+                        storage.remove(index);
+                    } else {
+                        storage.insert(*index, *value);
+                    }
+                }
+                Action::EvmIncrementNonce { address } => {
+                    let account = self.account_mut(*address);
+                    account.nonce = account
+                        .nonce
+                        .checked_add(1)
+                        .ok_or(Error::NonceOverflow(*address))?;
+                }
+                Action::EvmSetCode { address, code } => {
+                    self.account_mut(*address).code = code.to_vec();
+                }
+                Action::EvmSelfDestruct { address } => {
+                    let account = self.account_mut(*address);
+                    account.nonce = 0;
+                    account.generation = account.generation.wrapping_add(1);
+                    account.code.clear();
+                    account.storage.clear();
+                }
+                Action::ExternalInstruction { .. } => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn withdraw(&mut self, source: &Address, value: U256) -> Result<()> {
+        let account = self.account_mut(*source);
+        if account.balance < value {
+            return Err(Error::InsufficientBalance(*source, value));
+        }
+
+        account.balance -= value;
+        Ok(())
+    }
+
+    fn account_map_or<R>(
+        &self,
+        address: &Address,
+        default: R,
+        f: impl FnOnce(&MemoryAccount) -> R,
+    ) -> R {
+        self.accounts.get(address).map_or(default, f)
+    }
+}
+
+#[async_trait]
+impl AccountStorage for MemoryAccountStorage {
+    fn neon_token_mint(&self) -> &Pubkey {
+        &self.neon_token_mint
+    }
+
+    fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    fn operator(&self) -> &Pubkey {
+        &self.operator
+    }
+
+    fn block_number(&self) -> U256 {
+        self.block_number.into()
+    }
+
+    fn block_timestamp(&self) -> U256 {
+        self.block_timestamp.try_into().unwrap_or_default()
+    }
+
+    async fn block_hash(&self, number: u64) -> [u8; 32] {
+        generate_fake_slot_hash(number)
+    }
+
+    async fn clock(&self) -> Result<Clock> {
+        Ok(Clock {
+            slot: self.block_number,
+            unix_timestamp: self.block_timestamp,
+            ..Clock::default()
+        })
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn default_chain_id(&self) -> u64 {
+        CHAIN_ID
+    }
+
+    async fn exists(&self, address: &Address) -> bool {
+        self.accounts.contains_key(address)
+    }
+
+    async fn nonce(&self, address: &Address) -> u64 {
+        self.account_map_or(address, 0, |a| a.nonce)
+    }
+
+    async fn balance(&self, address: &Address) -> U256 {
+        self.account_map_or(address, U256::ZERO, |a| a.balance)
+    }
+
+    async fn code_size(&self, address: &Address) -> usize {
+        self.account_map_or(address, 0, |a| a.code.len())
+    }
+
+    async fn code_hash(&self, address: &Address) -> [u8; 32] {
+        // https://eips.ethereum.org/EIPS/eip-1052
+        // https://eips.ethereum.org/EIPS/eip-161
+        self.account_map_or(address, <[u8; 32]>::default(), |a| {
+            if a.nonce == 0 && a.balance == 0 && a.code.is_empty() {
+                <[u8; 32]>::default()
+            } else {
+                hash(&a.code).to_bytes()
+            }
+        })
+    }
+
+    async fn code(&self, address: &Address) -> Buffer {
+        self.account_map_or(address, Buffer::empty(), |a| Buffer::from_slice(&a.code))
+    }
+
+    async fn generation(&self, address: &Address) -> u32 {
+        self.account_map_or(address, 0, |a| a.generation)
+    }
+
+    async fn storage(&self, address: &Address, index: &U256) -> [u8; 32] {
+        self.account_map_or(address, <[u8; 32]>::default(), |a| {
+            a.storage.get(index).copied().unwrap_or_default()
+        })
+    }
+
+    async fn clone_solana_account(&self, address: &Pubkey) -> OwnedAccountInfo {
+        self.solana_accounts
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| OwnedAccountInfo {
+                key: *address,
+                is_signer: false,
+                is_writable: false,
+                lamports: 0,
+                data: vec![],
+                owner: solana_program::system_program::ID,
+                executable: false,
+                rent_epoch: 0,
+            })
+    }
+
+    async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
+    where
+        F: FnOnce(&AccountInfo) -> R + Send,
+    {
+        let mut account = self.clone_solana_account(address).await;
+        let info = (&mut account).into_account_info();
+
+        action(&info)
+    }
+
+    async fn solana_account_space(&self, address: &Address) -> Option<usize> {
+        self.accounts
+            .get(address)
+            .map(|a| EthereumAccount::space_needed(a.code.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runtime code: empty call data returns slot 0, otherwise the first word is stored to slot 0
+    const RUNTIME: &[u8] = &[
+        0x36, 0x15, 0x60, 0x0c, 0x57, // CALLDATASIZE ISZERO PUSH1 12 JUMPI
+        0x60, 0x00, 0x35, 0x60, 0x00, 0x55, 0x00, // SSTORE(0, CALLDATALOAD(0)) STOP
+        0x5b, 0x60, 0x00, 0x54, 0x60, 0x00, 0x52, // JUMPDEST MSTORE(0, SLOAD(0))
+        0x60, 0x20, 0x60, 0x00, 0xf3, // RETURN(0, 32)
+    ];
+
+    /// Init code: copy the runtime code to memory and return it
+    const INIT: &[u8] = &[
+        0x60, 0x18, 0x60, 0x0c, 0x60, 0x00, 0x39, // CODECOPY(0, 12, 24)
+        0x60, 0x18, 0x60, 0x00, 0xf3, // RETURN(0, 24)
+    ];
+
+    #[tokio::test]
+    async fn deploy_store_load() {
+        let mut storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        let from = Address::from([1; 20]);
+        let value = [0x42; 32];
+
+        let init_code = [INIT, RUNTIME].concat();
+        let (contract, result) = storage.deploy(from, &init_code, U256::ZERO).await.unwrap();
+        assert!(matches!(result.exit_status, ExitStatus::Return(_)));
+        assert_eq!(storage.account(&contract).unwrap().code, RUNTIME);
+        assert_eq!(storage.nonce(&from).await, 1);
+
+        let result = storage
+            .call(from, contract, &value, U256::ZERO)
+            .await
+            .unwrap();
+        assert!(matches!(result.exit_status, ExitStatus::Stop));
+        assert_eq!(storage.storage(&contract, &U256::ZERO).await, value);
+
+        let result = storage.call(from, contract, &[], U256::ZERO).await.unwrap();
+        assert!(matches!(result.exit_status, ExitStatus::Return(ref data) if *data == value));
+        assert_eq!(storage.nonce(&from).await, 3);
+
+        let result = storage
+            .call(from, contract, &[0; 32], U256::ZERO)
+            .await
+            .unwrap();
+        assert!(matches!(result.exit_status, ExitStatus::Stop));
+        assert!(storage.account(&contract).unwrap().storage.is_empty());
+    }
+
+    #[tokio::test]
+    async fn execution_is_send() {
+        let from = Address::from([1; 20]);
+        let init_code = [INIT, RUNTIME].concat();
+
+        // Spawned tasks may move between the runtime threads
+        let task = tokio::spawn(async move {
+            let mut storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+            let (contract, _) = storage.deploy(from, &init_code, U256::ZERO).await?;
+            storage
+                .call(from, contract, &[0x42; 32], U256::ZERO)
+                .await?;
+
+            Ok::<_, crate::error::Error>(storage.storage(&contract, &U256::ZERO).await)
+        });
+
+        assert_eq!(task.await.unwrap().unwrap(), [0x42; 32]);
+    }
+}
@@ -1,15 +1,14 @@
 use crate::account::EthereumAccount;
+use crate::error::Result;
 use crate::executor::{Action, OwnedAccountInfo};
 use crate::types::Address;
 use ethnum::U256;
 use maybe_async::maybe_async;
 use solana_program::account_info::AccountInfo;
 #[cfg(target_os = "solana")]
-use {
-    crate::account::EthereumStorage, solana_program::clock::Clock, std::cell::RefCell,
-    std::collections::HashSet,
-};
+use {crate::account::EthereumStorage, std::cell::RefCell, std::collections::HashSet};
 
+use solana_program::clock::Clock;
 use solana_program::pubkey::Pubkey;
 use solana_program::slot_history::Slot;
 use std::cmp::Ordering;
@@ -21,6 +20,11 @@ mod apply;
 mod backend;
 #[cfg(target_os = "solana")]
 mod base;
+#[cfg(not(target_os = "solana"))]
+mod memory;
+
+#[cfg(not(target_os = "solana"))]
+pub use memory::{MemoryAccount, MemoryAccountStorage, MemoryExecutionResult};
 
 #[derive(Debug)]
 pub enum AccountOperation {
@@ -40,6 +44,8 @@ pub enum AccountsReadiness {
 #[cfg(target_os = "solana")]
 pub struct ProgramAccountStorage<'a> {
     program_id: &'a Pubkey,
+    chain_id: u64,
+    token_mint: Pubkey,
     operator: &'a Pubkey,
     clock: Clock,
 
@@ -52,11 +58,23 @@ pub struct ProgramAccountStorage<'a> {
     empty_storage_accounts: RefCell<HashSet<(Address, U256)>>,
 }
 
+/// Off-chain storages are shared by the emulation threads.
+/// On-chain storage holds `AccountInfo`, which is not `Sync`, and the program is single-threaded.
+#[cfg(not(target_os = "solana"))]
+pub trait SyncStorage: Sync {}
+#[cfg(not(target_os = "solana"))]
+impl<T: Sync> SyncStorage for T {}
+
+#[cfg(target_os = "solana")]
+pub trait SyncStorage {}
+#[cfg(target_os = "solana")]
+impl<T> SyncStorage for T {}
+
 /// Account storage
 /// Trait to access account info
-#[maybe_async(?Send)]
-pub trait AccountStorage {
-    /// Get `NEON` token mint
+#[maybe_async]
+pub trait AccountStorage: SyncStorage {
+    /// Get gas token mint of the chain
     fn neon_token_mint(&self) -> &Pubkey;
 
     /// Get `NeonEVM` program id
@@ -71,8 +89,12 @@ pub trait AccountStorage {
     fn block_timestamp(&self) -> U256;
     /// Get block hash
     async fn block_hash(&self, number: u64) -> [u8; 32];
+    /// Get solana clock sysvar
+    async fn clock(&self) -> Result<Clock>;
     /// Get chain id
     fn chain_id(&self) -> u64;
+    /// Get default chain id, its accounts keep the legacy addresses
+    fn default_chain_id(&self) -> u64;
 
     /// Check if ethereum account exists
     async fn exists(&self, address: &Address) -> bool;
@@ -99,11 +121,40 @@ pub trait AccountStorage {
     /// Map existing solana account
     async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
     where
-        F: FnOnce(&AccountInfo) -> R;
+        F: FnOnce(&AccountInfo) -> R + Send;
 
     /// Resolve account solana address and bump seed
     fn solana_address(&self, address: &Address) -> (Pubkey, u8) {
-        address.find_solana_address(self.program_id())
+        address.find_solana_address_in_chain(
+            self.program_id(),
+            self.chain_id(),
+            self.default_chain_id(),
+        )
+    }
+
+    /// Resolve account solana address and signer seeds
+    fn solana_address_with_seeds(&self, address: &Address) -> (Pubkey, Vec<Vec<u8>>) {
+        let (pubkey, bump_seed) = self.solana_address(address);
+
+        let mut seeds = address.solana_address_seeds(self.chain_id(), self.default_chain_id());
+        seeds.push(vec![bump_seed]);
+
+        (pubkey, seeds)
+    }
+
+    /// Find program address in the chain namespace and its signer seeds.
+    /// Chains other than the default one add chain id to the seeds.
+    fn find_program_address(&self, seeds: &[&[u8]]) -> (Pubkey, Vec<Vec<u8>>) {
+        let mut seeds: Vec<Vec<u8>> = seeds.iter().map(|seed| seed.to_vec()).collect();
+        if self.chain_id() != self.default_chain_id() {
+            seeds.push(self.chain_id().to_le_bytes().to_vec());
+        }
+
+        let seeds_ref: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        let (pubkey, bump_seed) = Pubkey::find_program_address(&seeds_ref, self.program_id());
+        seeds.push(vec![bump_seed]);
+
+        (pubkey, seeds)
     }
 
     /// Solana account data len
@@ -218,3 +269,29 @@ fn test_generate_fake_slot_hash() {
     expected[31] = 0xe8;
     assert_eq!(generate_fake_slot_hash(slot), expected);
 }
+
+#[test]
+fn test_find_program_address() {
+    use crate::account::ACCOUNT_SEED_VERSION;
+    use crate::config::CHAIN_ID;
+
+    let program_id = Pubkey::new_unique();
+    let storage = MemoryAccountStorage::new(program_id, CHAIN_ID);
+    let address = Address::from([3; 20]);
+
+    // Default chain keeps the legacy seeds
+    let (key, seeds) = storage.find_program_address(&[b"PAYER", address.as_bytes()]);
+    assert_eq!(seeds.len(), 3);
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    assert_eq!(
+        Pubkey::create_program_address(&seeds, &program_id).unwrap(),
+        key
+    );
+
+    let (key, seeds) = storage.solana_address_with_seeds(&address);
+    assert_eq!(key, address.find_solana_address(&program_id).0);
+    assert_eq!(
+        seeds[..2],
+        [vec![ACCOUNT_SEED_VERSION], address.as_bytes().to_vec()]
+    );
+}
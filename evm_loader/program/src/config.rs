@@ -7,8 +7,18 @@ use evm_loader_macro::{
     common_config_parser, declare_param_id, elf_config_parser, neon_elf_param,
     net_specific_config_parser, operators_whitelist,
 };
+use solana_program::pubkey::Pubkey;
 use static_assertions::const_assert;
 
+/// EVM chain hosted by the program and its gas token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainInfo {
+    pub id: u64,
+    pub token_mint: Pubkey,
+    /// Number of base 10 digits to the right of the decimal place
+    pub decimals: u8,
+}
+
 cfg_if! {
     if #[cfg(feature = "mainnet")] {
         net_specific_config_parser!("config/mainnet.toml");
@@ -36,3 +46,15 @@ cfg_if! {
 elf_config_parser!("config/elf_params.toml");
 
 const_assert!(token_mint::decimals() <= 18);
+
+/// Find the hosted chain by id
+#[must_use]
+pub fn chain_info(chain_id: u64) -> Option<&'static ChainInfo> {
+    CHAINS.iter().find(|chain| chain.id == chain_id)
+}
+
+/// Several chains are hosted, ethereum accounts must be checked against the chain namespace
+#[must_use]
+pub const fn is_multichain() -> bool {
+    CHAINS.len() > 1
+}
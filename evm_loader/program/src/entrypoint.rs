@@ -8,7 +8,11 @@ use solana_program::{
 };
 
 #[cfg(not(feature = "emergency"))]
-use crate::{instruction, instruction::EvmInstruction};
+use crate::{
+    account::{deposit_rates::Rates, operator_registry::Registry, parameters::Overrides},
+    instruction,
+    instruction::EvmInstruction,
+};
 
 entrypoint!(process_instruction);
 
@@ -50,6 +54,9 @@ fn process_instruction<'a>(
         EvmInstruction::Cancel => {
             instruction::transaction_cancel::process(program_id, accounts, instruction)
         }
+        EvmInstruction::CancelAbandoned => {
+            instruction::transaction_cancel_abandoned::process(program_id, accounts, instruction)
+        }
         EvmInstruction::TransactionExecuteFromInstruction => {
             instruction::transaction_execute_from_instruction::process(
                 program_id,
@@ -66,6 +73,10 @@ fn process_instruction<'a>(
             )
             .map_err(ProgramError::from)
         }
+        EvmInstruction::TransactionExecuteFromCpi => {
+            instruction::transaction_execute_from_cpi::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
         EvmInstruction::TransactionStepFromInstruction => {
             instruction::transaction_step_from_instruction::process(
                 program_id,
@@ -89,12 +100,71 @@ fn process_instruction<'a>(
         EvmInstruction::CreateAccountV03 => {
             instruction::account_create::process(program_id, accounts, instruction)
         }
+        EvmInstruction::CollectStorage => {
+            instruction::collect_storage::process(program_id, accounts, instruction)
+        }
         EvmInstruction::CollectTreasure => {
             instruction::collect_treasury::process(program_id, accounts, instruction)
         }
+        EvmInstruction::CollectTreasureBatch => {
+            instruction::collect_treasury_batch::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
         EvmInstruction::CreateMainTreasury => {
             instruction::create_main_treasury::process(program_id, accounts, instruction)
         }
+        EvmInstruction::TransactionExecuteBatch => {
+            instruction::transaction_execute_batch::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::TransactionExecuteBatchFromAccount => {
+            instruction::transaction_execute_batch_from_account::process(
+                program_id,
+                accounts,
+                instruction,
+            )
+            .map_err(ProgramError::from)
+        }
+        EvmInstruction::OperatorRegistryInit => {
+            instruction::managed_init::process::<Registry>(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::OperatorRegistryAdd => {
+            instruction::operator_registry_add::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::OperatorRegistryRemove => {
+            instruction::operator_registry_remove::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::OperatorRegistrySuspend => {
+            instruction::operator_registry_suspend::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::ParametersInit => {
+            instruction::managed_init::process::<Overrides>(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::ParametersUpdate => {
+            instruction::parameters_update::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::DepositRatesInit => {
+            instruction::managed_init::process::<Rates>(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::DepositRatesSet => {
+            instruction::deposit_rates_set::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::DepositSplToken => {
+            instruction::deposit_spl_token::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
+        EvmInstruction::AccountMigrate => {
+            instruction::account_migrate::process(program_id, accounts, instruction)
+                .map_err(ProgramError::from)
+        }
         EvmInstruction::AccountBlockAdd => {
             instruction::account_block_add::process(program_id, accounts, instruction)
                 .map_err(ProgramError::from)
@@ -47,6 +47,9 @@ pub enum Error {
     #[error("Account {0} - invalid tag, expected {1}")]
     AccountInvalidTag(Pubkey, u8),
 
+    #[error("Account {0} - outdated layout with tag {1}, migration required")]
+    AccountOutdated(Pubkey, u8),
+
     #[error("Account {0} - invalid owner, expected {1}")]
     AccountInvalidOwner(Pubkey, Pubkey),
 
@@ -149,6 +152,30 @@ pub enum Error {
     #[error("Holder Account - invalid transaction hash {}, expected = {}", hex::encode(.0), hex::encode(.1))]
     HolderInvalidHash([u8; 32], [u8; 32]),
 
+    #[error("Holder Account - incomplete transaction, {1} bytes of {0} written")]
+    HolderIncomplete(usize, usize),
+
+    #[error("Operator Registry - invalid authority {0}, expected = {1}")]
+    OperatorRegistryInvalidAuthority(Pubkey, Pubkey),
+
+    #[error("Operator Registry - operator {0} is already registered")]
+    OperatorAlreadyRegistered(Pubkey),
+
+    #[error("Operator Registry - operator {0} is not registered")]
+    OperatorNotRegistered(Pubkey),
+
+    #[error("Operator {0} is suspended")]
+    OperatorSuspended(Pubkey),
+
+    #[error("Parameters - invalid authority {0}, expected = {1}")]
+    ParametersInvalidAuthority(Pubkey, Pubkey),
+
+    #[error("Deposit Rates - invalid authority {0}, expected = {1}")]
+    DepositRatesInvalidAuthority(Pubkey, Pubkey),
+
+    #[error("Deposit Rates - mint {0} is not accepted for chain {1}")]
+    DepositMintNotSupported(Pubkey, u64),
+
     #[error("Validation: undefined instruction: op {0:X}, pos {1}")]
     ValidationUndefinedInstruction(u8, usize),
 
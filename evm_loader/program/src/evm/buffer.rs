@@ -24,6 +24,13 @@ enum Inner {
         key: Pubkey,
         range: Range<usize>,
     },
+    /// Off-chain copy of the account data, serialized as the account reference
+    #[cfg(not(target_os = "solana"))]
+    AccountCopy {
+        key: Pubkey,
+        ptr: NonNull<u8>,
+        range: Range<usize>,
+    },
 }
 
 #[derive(Debug)]
@@ -33,6 +40,13 @@ pub struct Buffer {
     inner: Inner,
 }
 
+// Off-chain buffers own their data, account references are created on-chain only
+#[cfg(not(target_os = "solana"))]
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Send for Buffer {}
+#[cfg(not(target_os = "solana"))]
+unsafe impl Sync for Buffer {}
+
 impl Buffer {
     fn new(inner: Inner) -> Self {
         let (ptr, len) = match &inner {
@@ -43,6 +57,8 @@ impl Buffer {
                 (ptr, range.len())
             }
             Inner::AccountUninit { .. } => (std::ptr::null_mut(), 0),
+            #[cfg(not(target_os = "solana"))]
+            Inner::AccountCopy { ptr, range, .. } => (ptr.as_ptr(), range.len()),
         };
 
         Buffer { ptr, len, inner }
@@ -54,6 +70,7 @@ impl Buffer {
     /// At the moment, `Buffer` may outlive `account`, since no lifetimes has been set,
     /// so they are not checked by the compiler and it's the user's responsibility to take
     /// care of them.
+    #[cfg(target_os = "solana")]
     #[must_use]
     pub unsafe fn from_account(account: &AccountInfo, range: Range<usize>) -> Self {
         // todo cell_leak #69099
@@ -67,12 +84,41 @@ impl Buffer {
         })
     }
 
+    /// Emulator account data is not kept in place, the range is copied.
+    /// The buffer is still serialized as the account reference, the same way as on-chain.
+    ///
+    /// # Safety
+    ///
+    /// Safe off-chain, marked as unsafe to match the on-chain signature.
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub unsafe fn from_account(account: &AccountInfo, range: Range<usize>) -> Self {
+        let data = account.data.borrow();
+
+        Buffer::new(Inner::AccountCopy {
+            key: *account.key,
+            ptr: Self::allocate_copy(&data[range.clone()]),
+            range,
+        })
+    }
+
     #[must_use]
     pub fn from_slice(v: &[u8]) -> Self {
         if v.is_empty() {
             return Self::empty();
         }
 
+        Buffer::new(Inner::Owned {
+            ptr: Self::allocate_copy(v),
+            len: v.len(),
+        })
+    }
+
+    fn allocate_copy(v: &[u8]) -> NonNull<u8> {
+        if v.is_empty() {
+            return NonNull::dangling();
+        }
+
         unsafe {
             let len = v.len();
 
@@ -90,10 +136,7 @@ impl Buffer {
                 }
             }
 
-            Buffer::new(Inner::Owned {
-                ptr: NonNull::new_unchecked(ptr),
-                len,
-            })
+            NonNull::new_unchecked(ptr)
         }
     }
 
@@ -102,6 +145,17 @@ impl Buffer {
         Buffer::new(Inner::Empty)
     }
 
+    /// Size allocated on the heap, account data is not counted
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        if let Inner::Owned { len, .. } = self.inner {
+            len
+        } else {
+            0
+        }
+    }
+
     #[must_use]
     pub fn is_initialized(&self) -> bool {
         !matches!(self.inner, Inner::AccountUninit { .. })
@@ -163,11 +217,16 @@ impl PartialEq for Buffer {
 
 impl Drop for Buffer {
     fn drop(&mut self) {
-        if let Inner::Owned { ptr, len } = self.inner {
-            unsafe {
-                let layout = Layout::from_size_align_unchecked(len, BUFFER_ALIGN);
-                crate::allocator::EVM.dealloc(ptr.as_ptr(), layout);
-            }
+        let (ptr, len) = match &self.inner {
+            Inner::Owned { ptr, len } => (*ptr, *len),
+            #[cfg(not(target_os = "solana"))]
+            Inner::AccountCopy { ptr, range, .. } if !range.is_empty() => (*ptr, range.len()),
+            _ => return,
+        };
+
+        unsafe {
+            let layout = Layout::from_size_align_unchecked(len, BUFFER_ALIGN);
+            crate::allocator::EVM.dealloc(ptr.as_ptr(), layout);
         }
     }
 }
@@ -198,6 +257,12 @@ impl Clone for Buffer {
                 key: *key,
                 range: range.clone(),
             }),
+            #[cfg(not(target_os = "solana"))]
+            Inner::AccountCopy { key, range, .. } => Self::new(Inner::AccountCopy {
+                key: *key,
+                ptr: Self::allocate_copy(self),
+                range: range.clone(),
+            }),
         }
     }
 }
@@ -228,6 +293,13 @@ impl serde::Serialize for Buffer {
                 sv.serialize_field("range", range)?;
                 sv.end()
             }
+            #[cfg(not(target_os = "solana"))]
+            Inner::AccountCopy { key, range, .. } => {
+                let mut sv = serializer.serialize_struct_variant("evm_buffer", 2, "account", 2)?;
+                sv.serialize_field("key", key)?;
+                sv.serialize_field("range", range)?;
+                sv.end()
+            }
             Inner::AccountUninit { .. } => {
                 unreachable!()
             }
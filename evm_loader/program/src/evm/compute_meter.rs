@@ -0,0 +1,227 @@
+//! Off-chain estimation of the compute units and heap consumed by the program.
+//! Syscall costs are the values of `ComputeBudget::default()` of the Solana 1.16 runtime
+//! (`program-runtime/src/compute_budget.rs`). Interpreter costs (opcode dispatch, arithmetic,
+//! account and storage access, frame allocation) are estimates, not calibrated against
+//! on-chain execution.
+
+use std::sync::{Arc, Mutex};
+
+use super::stack::Stack;
+
+/// Compute units limit of a Solana transaction, `MAX_COMPUTE_UNIT_LIMIT`
+pub const MAX_COMPUTE_UNITS: u64 = 1_400_000;
+/// Heap frame available to the EVM allocator
+pub const MAX_HEAP_SIZE: usize = crate::allocator::HEAP_SIZE;
+
+/// Instruction overhead: accounts validation, state (de)serialization and gas payment
+const INSTRUCTION_UNITS: u64 = 30_000;
+/// Opcode dispatch and simple stack manipulations
+const OPCODE_UNITS: u64 = 40;
+/// Loading Ethereum account from the Solana account
+const ACCOUNT_UNITS: u64 = 1_000;
+const SLOAD_UNITS: u64 = 1_500;
+const SSTORE_UNITS: u64 = 2_000;
+/// Frame allocation, the `ENTER` log is counted separately
+const CALL_UNITS: u64 = 4_500;
+/// `syscall_base_cost`, charged for every syscall and for every `sol_log_data` field
+const SYSCALL_BASE_UNITS: u64 = 100;
+/// `sha256_base_cost`, `sol_keccak256` is charged the same
+const HASH_BASE_UNITS: u64 = 85;
+
+pub type ComputeMeterType = Arc<Mutex<ComputeMeter>>;
+
+#[derive(Debug, Default)]
+pub struct ComputeMeter {
+    iteration_steps: u64,
+    steps: u64,
+    units: u64,
+    iterations: Vec<u64>,
+    peak_heap: usize,
+}
+
+impl ComputeMeter {
+    /// Split the estimation into iterations of `steps` EVM steps, zero for a single iteration
+    pub fn set_iteration_steps(&mut self, steps: u64) {
+        self.iteration_steps = steps;
+    }
+
+    pub fn record_units(&mut self, units: u64) {
+        self.units = self.units.saturating_add(units);
+    }
+
+    pub fn record_step(&mut self, units: u64) {
+        self.record_units(units);
+        self.steps += 1;
+
+        if (self.iteration_steps != 0) && (self.steps % self.iteration_steps == 0) {
+            self.iterations.push(INSTRUCTION_UNITS + self.units);
+            self.units = 0;
+        }
+    }
+
+    pub fn record_heap(&mut self, size: usize) {
+        self.peak_heap = self.peak_heap.max(size);
+    }
+
+    /// Estimated compute units of each iteration, including the unfinished one
+    #[must_use]
+    pub fn iterations(&self) -> Vec<u64> {
+        let mut iterations = self.iterations.clone();
+        if (self.units > 0) || iterations.is_empty() {
+            iterations.push(INSTRUCTION_UNITS + self.units);
+        }
+
+        iterations
+    }
+
+    #[must_use]
+    pub fn peak_heap(&self) -> usize {
+        self.peak_heap
+    }
+}
+
+fn copy_units(len: usize) -> u64 {
+    (len as u64).saturating_add(31) / 32
+}
+
+/// `sol_keccak256` and `sol_sha256` syscalls cost, `sha256_byte_cost` is 1 unit per 2 bytes
+#[must_use]
+pub fn hash_units(len: usize) -> u64 {
+    HASH_BASE_UNITS.saturating_add(len as u64 / 2)
+}
+
+/// `sol_log_data` syscall cost: base cost, base cost per field and 1 unit per byte
+fn log_data_units(fields: u64, len: u64) -> u64 {
+    SYSCALL_BASE_UNITS
+        .saturating_add(SYSCALL_BASE_UNITS.saturating_mul(fields))
+        .saturating_add(len)
+}
+
+/// Frame allocation and the `ENTER` log: kind and address fields
+fn call_units() -> u64 {
+    CALL_UNITS + log_data_units(3, 5 + 12 + 20)
+}
+
+/// Estimated units of the opcode, `stack` is the state before the opcode execution
+#[must_use]
+pub fn opcode_units(opcode: u8, stack: &Stack) -> u64 {
+    let units = match opcode {
+        // MUL, DIV, SDIV, MOD, SMOD
+        0x02 | 0x04..=0x07 => 100,
+        // ADDMOD, MULMOD
+        0x08 | 0x09 => 250,
+        // EXP, every exponent byte is a multiplication
+        0x0A => 100 * u64::from((256 - stack.peek_u256(1).leading_zeros() + 7) / 8),
+        // KECCAK256
+        0x20 => hash_units(stack.peek_usize(1)),
+        // BALANCE, EXTCODESIZE, EXTCODEHASH, SELFDESTRUCT
+        0x31 | 0x3B | 0x3F | 0xFF => ACCOUNT_UNITS,
+        // CALLDATACOPY, CODECOPY, RETURNDATACOPY, MCOPY
+        0x37 | 0x39 | 0x3E | 0x5E => copy_units(stack.peek_usize(2)),
+        // EXTCODECOPY
+        0x3C => ACCOUNT_UNITS + copy_units(stack.peek_usize(3)),
+        0x54 => SLOAD_UNITS,
+        0x55 => SSTORE_UNITS,
+        // LOG0 - LOG4: name, address, topics count, topics and data fields
+        0xA0..=0xA4 => {
+            let topics = u64::from(opcode - 0xA0);
+            log_data_units(4 + topics, 4 + 20 + 1 + 32 * topics)
+                .saturating_add(stack.peek_usize(1) as u64)
+        }
+        // CREATE, CREATE2
+        0xF0 | 0xF5 => call_units() + ACCOUNT_UNITS + copy_units(stack.peek_usize(2)),
+        // CALL, CALLCODE
+        0xF1 | 0xF2 => call_units() + ACCOUNT_UNITS + copy_units(stack.peek_usize(4)),
+        // DELEGATECALL, STATICCALL
+        0xF4 | 0xFA => call_units() + ACCOUNT_UNITS + copy_units(stack.peek_usize(3)),
+        // RETURN, REVERT: the `EXIT` log and the return data copy
+        0xF3 | 0xFD => log_data_units(2, 4 + 6) + copy_units(stack.peek_usize(1)),
+        _ => 0,
+    };
+
+    OPCODE_UNITS.saturating_add(units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethnum::U256;
+
+    fn stack(values: &[usize]) -> Stack {
+        let mut stack = Stack::new();
+        for value in values.iter().rev() {
+            stack.push_usize(*value).unwrap();
+        }
+        stack
+    }
+
+    #[test]
+    fn syscall_units() {
+        assert_eq!(hash_units(0), 85);
+        assert_eq!(hash_units(64), 85 + 32);
+
+        // KECCAK256 of 64 bytes at offset 0
+        assert_eq!(opcode_units(0x20, &stack(&[0, 64])), OPCODE_UNITS + 85 + 32);
+
+        // LOG2 of 10 bytes: 6 fields of 4 + 20 + 1 + 64 + 10 bytes
+        assert_eq!(
+            opcode_units(0xA2, &stack(&[0, 10])),
+            OPCODE_UNITS + 100 + 6 * 100 + 99
+        );
+    }
+
+    #[test]
+    fn interpreter_units() {
+        assert_eq!(opcode_units(0x01, &stack(&[1, 2])), OPCODE_UNITS);
+        assert_eq!(opcode_units(0x54, &stack(&[0])), OPCODE_UNITS + SLOAD_UNITS);
+
+        // EXP is charged per exponent byte
+        let exp = |exponent| opcode_units(0x0A, &stack(&[2, exponent]));
+        assert_eq!(exp(0), OPCODE_UNITS);
+        assert_eq!(exp(0xFF), OPCODE_UNITS + 100);
+        assert_eq!(exp(0x1_0000), OPCODE_UNITS + 300);
+
+        // CALL copies the arguments
+        let call = |len| opcode_units(0xF1, &stack(&[0, 0, 0, 0, len, 0, 0]));
+        assert_eq!(call(64) - call(0), 2);
+        assert!(call(0) > CALL_UNITS + ACCOUNT_UNITS);
+    }
+
+    #[test]
+    fn stack_peek() {
+        let mut values = stack(&[1, 2]);
+        assert_eq!(values.peek_usize(0), 1);
+        assert_eq!(values.peek_usize(1), 2);
+        assert_eq!(values.peek_u256(2), U256::ZERO);
+
+        values.push_u256(U256::MAX).unwrap();
+        assert_eq!(values.peek_u256(0), U256::MAX);
+        assert_eq!(values.peek_usize(0), usize::MAX);
+        assert_eq!(values.peek_usize(1), 1);
+    }
+
+    #[test]
+    fn iterations() {
+        let mut meter = ComputeMeter::default();
+        assert_eq!(meter.iterations(), vec![INSTRUCTION_UNITS]);
+
+        meter.set_iteration_steps(2);
+        for _ in 0..5 {
+            meter.record_step(10);
+        }
+        meter.record_units(5);
+
+        assert_eq!(
+            meter.iterations(),
+            vec![
+                INSTRUCTION_UNITS + 20,
+                INSTRUCTION_UNITS + 20,
+                INSTRUCTION_UNITS + 15
+            ]
+        );
+
+        meter.record_heap(100);
+        meter.record_heap(50);
+        assert_eq!(meter.peak_heap(), 100);
+    }
+}
@@ -4,7 +4,7 @@ use ethnum::U256;
 use maybe_async::maybe_async;
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 
-#[maybe_async(?Send)]
+#[maybe_async]
 pub trait Database {
     fn chain_id(&self) -> U256;
 
@@ -29,7 +29,7 @@ pub trait Database {
 
     async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
     where
-        F: FnOnce(&AccountInfo) -> R;
+        F: FnOnce(&AccountInfo) -> R + Send;
 
     fn snapshot(&mut self);
     fn revert_snapshot(&mut self);
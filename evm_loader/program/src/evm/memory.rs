@@ -20,6 +20,10 @@ pub struct Memory {
     size: usize,
 }
 
+// The memory exclusively owns its allocation
+unsafe impl Send for Memory {}
+unsafe impl Sync for Memory {}
+
 impl Memory {
     pub fn new() -> Self {
         Self::with_capacity(MEMORY_CAPACITY)
@@ -115,6 +119,12 @@ impl Memory {
         self.size
     }
 
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn read(&mut self, offset: usize, length: usize) -> Result<&[u8], Error> {
         if length == 0_usize {
             return Ok(&[]);
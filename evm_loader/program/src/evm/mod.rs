@@ -10,18 +10,21 @@ use serde::{Deserialize, Serialize};
 use solana_program::log::sol_log_data;
 
 pub use buffer::Buffer;
+pub use precompile::is_precompile_address;
 
 use self::{database::Database, memory::Memory, stack::Stack};
 #[cfg(not(target_os = "solana"))]
-use crate::evm::tracing::TracerTypeOpt;
+use crate::evm::{compute_meter::ComputeMeterType, tracing::TracerTypeOpt};
 use crate::{
     error::{build_revert_message, Error, Result},
-    evm::{opcode::Action, precompile::is_precompile_address},
+    evm::opcode::Action,
     types::{Address, Transaction},
 };
 
 mod analysis;
 mod buffer;
+#[cfg(not(target_os = "solana"))]
+pub mod compute_meter;
 pub mod database;
 mod eof;
 mod memory;
@@ -38,14 +41,14 @@ macro_rules! tracing_event {
     ($self:ident, $x:expr) => {
         #[cfg(not(target_os = "solana"))]
         if let Some(tracer) = &$self.tracer {
-            tracer.borrow_mut().event($x);
+            tracer.lock().expect("tracer lock is poisoned").event($x);
         }
     };
     ($self:ident, $condition:expr, $x:expr) => {
         #[cfg(not(target_os = "solana"))]
         if let Some(tracer) = &$self.tracer {
             if $condition {
-                tracer.borrow_mut().event($x);
+                tracer.lock().expect("tracer lock is poisoned").event($x);
             }
         }
     };
@@ -55,12 +58,12 @@ macro_rules! trace_end_step {
     ($self:ident, $return_data:expr) => {
         #[cfg(not(target_os = "solana"))]
         if let Some(tracer) = &$self.tracer {
-            tracer
-                .borrow_mut()
-                .event(crate::evm::tracing::Event::EndStep {
+            tracer.lock().expect("tracer lock is poisoned").event(
+                crate::evm::tracing::Event::EndStep {
                     gas_used: 0_u64,
                     return_data: $return_data,
-                })
+                },
+            )
         }
     };
     ($self:ident, $condition:expr; $return_data_getter:expr) => {
@@ -158,11 +161,20 @@ pub struct Machine<B: Database> {
     parent: Option<Box<Self>>,
 
     #[serde(skip)]
-    phantom: PhantomData<*const B>,
+    phantom: PhantomData<fn() -> B>,
 
     #[cfg(not(target_os = "solana"))]
     #[serde(skip)]
     tracer: TracerTypeOpt,
+
+    #[cfg(not(target_os = "solana"))]
+    #[serde(skip)]
+    compute_meter: ComputeMeterType,
+
+    /// Heap used by the parent frames, they don't change while this frame executes
+    #[cfg(not(target_os = "solana"))]
+    #[serde(skip)]
+    parent_heap: usize,
 }
 
 impl<B: Database> Machine<B> {
@@ -174,41 +186,115 @@ impl<B: Database> Machine<B> {
         cursor.position().try_into().map_err(Error::from)
     }
 
-    #[cfg(target_os = "solana")]
-    pub fn deserialize_from(buffer: &[u8], backend: &B) -> Result<Self> {
-        fn reinit_buffer<B: Database>(buffer: &mut Buffer, backend: &B) {
-            if let Some((key, range)) = buffer.uninit_data() {
-                *buffer =
-                    backend.map_solana_account(&key, |i| unsafe { Buffer::from_account(i, range) });
-            }
+    #[maybe_async]
+    async fn reinit_buffer(buffer: &mut Buffer, backend: &B) {
+        if let Some((key, range)) = buffer.uninit_data() {
+            *buffer = backend
+                .map_solana_account(&key, |i| unsafe { Buffer::from_account(i, range) })
+                .await;
         }
+    }
 
-        fn reinit_machine<B: Database>(mut machine: &mut Machine<B>, backend: &B) {
-            loop {
-                reinit_buffer(&mut machine.call_data, backend);
-                reinit_buffer(&mut machine.execution_code, backend);
-                reinit_buffer(&mut machine.return_data, backend);
+    /// Account buffers are restored from the backend accounts, the same way on-chain and off-chain.
+    /// Off-chain tracer is not restored, all frames share a new compute meter.
+    #[maybe_async]
+    pub async fn deserialize_from(buffer: &[u8], backend: &B) -> Result<Self> {
+        let mut evm: Self = bincode::deserialize(buffer)?;
 
-                if let Some(container) = &mut machine.container {
-                    for code in &mut container.code {
-                        reinit_buffer(code, backend);
-                    }
-                    reinit_buffer(&mut container.data, backend);
-                }
+        let mut machine = &mut evm;
+        loop {
+            Self::reinit_buffer(&mut machine.call_data, backend).await;
+            Self::reinit_buffer(&mut machine.execution_code, backend).await;
+            Self::reinit_buffer(&mut machine.return_data, backend).await;
 
-                match &mut machine.parent {
-                    None => break,
-                    Some(parent) => machine = parent,
+            if let Some(container) = &mut machine.container {
+                for code in &mut container.code {
+                    Self::reinit_buffer(code, backend).await;
                 }
+                Self::reinit_buffer(&mut container.data, backend).await;
+            }
+
+            match &mut machine.parent {
+                None => break,
+                Some(parent) => machine = parent,
             }
         }
 
-        let mut evm: Self = bincode::deserialize(buffer)?;
-        reinit_machine(&mut evm, backend);
+        #[cfg(not(target_os = "solana"))]
+        {
+            let compute_meter = evm.compute_meter.clone();
+            let mut parent = evm.parent.as_deref_mut();
+            while let Some(machine) = parent {
+                machine.compute_meter = compute_meter.clone();
+                parent = machine.parent.as_deref_mut();
+            }
+
+            evm.restore_parent_heap();
+        }
 
         Ok(evm)
     }
 
+    /// Recompute `parent_heap` of the frame and its parents, returns the total heap size
+    #[cfg(not(target_os = "solana"))]
+    fn restore_parent_heap(&mut self) -> usize {
+        self.parent_heap = self
+            .parent
+            .as_deref_mut()
+            .map_or(0, Self::restore_parent_heap);
+
+        self.heap_size()
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn compute_meter(&self) -> ComputeMeterType {
+        self.compute_meter.clone()
+    }
+
+    /// Number of parent frames of the executing one
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn call_depth(&self) -> usize {
+        let mut depth = 0;
+
+        let mut parent = self.parent.as_deref();
+        while let Some(machine) = parent {
+            depth += 1;
+            parent = machine.parent.as_deref();
+        }
+
+        depth
+    }
+
+    /// Program counter of the executing frame
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Heap used by stacks, memories and buffers of all frames.
+    #[cfg(not(target_os = "solana"))]
+    fn heap_size(&self) -> usize {
+        self.parent_heap + self.frame_heap()
+    }
+
+    /// Heap used by the stack, memory and buffers of this frame.
+    /// Contract code and the transaction call data are read from accounts on-chain.
+    #[cfg(not(target_os = "solana"))]
+    fn frame_heap(&self) -> usize {
+        let mut size = stack::STACK_SIZE + self.memory.capacity() + self.return_data.heap_size();
+        if self.parent.is_some() {
+            size += self.call_data.heap_size();
+            if self.reason == Reason::Create {
+                size += self.execution_code.heap_size();
+            }
+        }
+
+        size
+    }
+
     #[maybe_async]
     pub async fn new(
         trx: &mut Transaction,
@@ -320,6 +406,10 @@ impl<B: Database> Machine<B> {
             phantom: PhantomData,
             #[cfg(not(target_os = "solana"))]
             tracer,
+            #[cfg(not(target_os = "solana"))]
+            compute_meter: ComputeMeterType::default(),
+            #[cfg(not(target_os = "solana"))]
+            parent_heap: 0,
         })
     }
 
@@ -385,6 +475,10 @@ impl<B: Database> Machine<B> {
             phantom: PhantomData,
             #[cfg(not(target_os = "solana"))]
             tracer,
+            #[cfg(not(target_os = "solana"))]
+            compute_meter: ComputeMeterType::default(),
+            #[cfg(not(target_os = "solana"))]
+            parent_heap: 0,
         })
     }
 
@@ -409,6 +503,15 @@ impl<B: Database> Machine<B> {
         let is_eof = self.container.is_some();
 
         let status = if is_precompile_address(&self.context.contract) {
+            #[cfg(not(target_os = "solana"))]
+            self.compute_meter
+                .lock()
+                .expect("compute meter lock is poisoned")
+                .record_units(precompile::precompile_compute_units(
+                    &self.context.contract,
+                    &self.call_data,
+                ));
+
             let value = Self::precompile(&self.context.contract, &self.call_data).unwrap();
             backend.commit_snapshot();
 
@@ -423,6 +526,9 @@ impl<B: Database> Machine<B> {
                 let code = self.get_code();
                 let opcode = code.get_or_default(self.pc);
 
+                #[cfg(not(target_os = "solana"))]
+                let compute_units = compute_meter::opcode_units(opcode, &self.stack);
+
                 tracing_event!(
                     self,
                     tracing::Event::BeginStep {
@@ -452,6 +558,17 @@ impl<B: Database> Machine<B> {
                     _ => None,
                 });
 
+                #[cfg(not(target_os = "solana"))]
+                {
+                    let heap_size = self.heap_size();
+                    let mut compute_meter = self
+                        .compute_meter
+                        .lock()
+                        .expect("compute meter lock is poisoned");
+                    compute_meter.record_heap(heap_size);
+                    compute_meter.record_step(compute_units);
+                }
+
                 match opcode_result {
                     Action::Continue => self.pc += 1,
                     Action::Jump(target) => self.pc = target,
@@ -530,6 +647,10 @@ impl<B: Database> Machine<B> {
             phantom: PhantomData,
             #[cfg(not(target_os = "solana"))]
             tracer: self.tracer.clone(),
+            #[cfg(not(target_os = "solana"))]
+            compute_meter: self.compute_meter.clone(),
+            #[cfg(not(target_os = "solana"))]
+            parent_heap: self.heap_size(),
         };
 
         core::mem::swap(self, &mut other);
@@ -546,3 +667,145 @@ impl<B: Database> Machine<B> {
         other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_storage::MemoryAccountStorage;
+    use crate::config::CHAIN_ID;
+    use crate::executor::{ExecutorState, OwnedAccountInfo};
+    use crate::types::{LegacyTx, TransactionPayload};
+    use solana_program::account_info::IntoAccountInfo;
+    use solana_program::pubkey::Pubkey;
+
+    fn transaction(target: Address) -> Transaction {
+        Transaction {
+            transaction: TransactionPayload::Legacy(LegacyTx {
+                nonce: 0,
+                gas_price: U256::ZERO,
+                gas_limit: U256::MAX,
+                target: Some(target),
+                value: U256::ZERO,
+                call_data: Buffer::empty(),
+                v: U256::default(),
+                r: U256::default(),
+                s: U256::default(),
+                chain_id: Some(CHAIN_ID.into()),
+                recovery_id: u8::default(),
+            }),
+            byte_len: usize::default(),
+            hash: <[u8; 32]>::default(),
+            signed_hash: <[u8; 32]>::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn account_buffer_reinit() {
+        let mut code_account = OwnedAccountInfo {
+            key: Pubkey::new_unique(),
+            is_signer: false,
+            is_writable: false,
+            lamports: 0,
+            data: (0..=255).collect(),
+            owner: Pubkey::default(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let code_range = 16..144;
+        let code = code_account.data[code_range.clone()].to_vec();
+
+        let mut storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        storage.add_solana_account(code_account.clone());
+        let mut backend = ExecutorState::new(&storage);
+
+        let mut trx = transaction(Address::from([2; 20]));
+        let mut evm = Machine::new(&mut trx, Address::from([1; 20]), &mut backend, None)
+            .await
+            .unwrap();
+
+        let mut buffer = vec![0_u8; 4096];
+        evm.execution_code = Buffer::from_slice(&code);
+        let owned_len = evm.serialize_into(&mut buffer).unwrap();
+
+        let info = (&mut code_account).into_account_info();
+        evm.execution_code = unsafe { Buffer::from_account(&info, code_range) };
+        assert_eq!(&*evm.execution_code, code.as_slice());
+        assert_eq!(evm.execution_code.heap_size(), 0);
+
+        // The account buffer is stored as the key and range instead of the length and code
+        let account_len = evm.serialize_into(&mut buffer).unwrap();
+        assert_eq!(owned_len - account_len, (8 + code.len()) - (32 + 16));
+
+        let restored = Machine::deserialize_from(&buffer[..account_len], &backend)
+            .await
+            .unwrap();
+        assert_eq!(&*restored.execution_code, code.as_slice());
+        assert!(restored.execution_code.is_initialized());
+        assert_eq!(restored.execution_code.heap_size(), 0);
+    }
+
+    fn walk_heap_size<B: Database>(evm: &Machine<B>) -> usize {
+        let mut size = 0;
+
+        let mut frame = Some(evm);
+        while let Some(machine) = frame {
+            size += machine.frame_heap();
+            frame = machine.parent.as_deref();
+        }
+
+        size
+    }
+
+    #[tokio::test]
+    async fn incremental_heap_size() {
+        let storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        let mut backend = ExecutorState::new(&storage);
+
+        let mut trx = transaction(Address::from([2; 20]));
+        let mut evm = Machine::new(&mut trx, Address::from([1; 20]), &mut backend, None)
+            .await
+            .unwrap();
+        let root_memory = evm.memory.capacity();
+        assert_eq!(evm.heap_size(), stack::STACK_SIZE + root_memory);
+
+        let context = evm.context;
+        evm.fork(
+            Reason::Call,
+            context,
+            Buffer::empty(),
+            Buffer::from_slice(&[1; 100]),
+            None,
+        )
+        .unwrap();
+        evm.memory.write_32(4096, &[1; 32]).unwrap();
+        evm.fork(
+            Reason::Create,
+            context,
+            Buffer::from_slice(&[0; 50]),
+            Buffer::empty(),
+            None,
+        )
+        .unwrap();
+
+        let memory =
+            root_memory + evm.parent.as_ref().unwrap().memory.capacity() + evm.memory.capacity();
+        assert_eq!(evm.call_depth(), 2);
+        assert_eq!(evm.heap_size(), 3 * stack::STACK_SIZE + memory + 100 + 50);
+        assert_eq!(evm.heap_size(), walk_heap_size(&evm));
+
+        let mut buffer = vec![0_u8; 64 * 1024];
+        let len = evm.serialize_into(&mut buffer).unwrap();
+        let restored = Machine::deserialize_from(&buffer[..len], &backend)
+            .await
+            .unwrap();
+        assert_eq!(restored.heap_size(), evm.heap_size());
+
+        let create_memory = evm.memory.capacity();
+        evm.join();
+        assert_eq!(
+            evm.heap_size(),
+            2 * stack::STACK_SIZE + memory - create_memory + 100
+        );
+        assert_eq!(evm.heap_size(), walk_heap_size(&evm));
+    }
+}
@@ -1369,6 +1369,15 @@ impl<B: Database> Machine<B> {
         backend: &mut B,
         address: &Address,
     ) -> Result<Action> {
+        #[cfg(not(target_os = "solana"))]
+        self.compute_meter
+            .lock()
+            .expect("compute meter lock is poisoned")
+            .record_units(super::precompile::precompile_compute_units(
+                address,
+                &self.call_data,
+            ));
+
         let result = match Self::precompile(address, &self.call_data).map(Ok) {
             Some(x) => Some(x),
             None => {
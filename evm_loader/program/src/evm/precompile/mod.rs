@@ -73,3 +73,54 @@ impl<B: Database> Machine<B> {
         }
     }
 }
+
+/// Estimated compute units of the precompile call, see `evm::compute_meter`
+#[cfg(not(target_os = "solana"))]
+#[must_use]
+pub fn precompile_compute_units(address: &Address, data: &[u8]) -> u64 {
+    use crate::evm::compute_meter::hash_units;
+
+    let len = data.len() as u64;
+    let word = |index: usize| -> u64 {
+        data.get(index * 32..(index + 1) * 32)
+            .and_then(|word| {
+                ethnum::U256::from_be_bytes(word.try_into().unwrap())
+                    .try_into()
+                    .ok()
+            })
+            .unwrap_or(u64::MAX)
+    };
+
+    match *address {
+        // secp256k1_recover syscall
+        SYSTEM_ACCOUNT_ECRECOVER => 25_000,
+        SYSTEM_ACCOUNT_SHA_256 => hash_units(data.len()),
+        // Implemented in the program, about 2000 units per 64 bytes block
+        SYSTEM_ACCOUNT_RIPEMD160 => (len / 64 + 1) * 2_000,
+        SYSTEM_ACCOUNT_DATACOPY => 100 + len / 32,
+        // big_mod_exp syscall, quadratic in the modulus length and linear in the exponent length
+        SYSTEM_ACCOUNT_BIGMODEXP => {
+            let (base_len, exp_len, mod_len) = (word(0), word(1), word(2));
+            let size = base_len.max(mod_len);
+            size.saturating_mul(size)
+                .saturating_mul(exp_len)
+                .saturating_div(64)
+                .saturating_add(190)
+        }
+        // alt_bn128 syscalls
+        SYSTEM_ACCOUNT_BN256_ADD => 334,
+        SYSTEM_ACCOUNT_BN256_SCALAR_MUL => 3_840,
+        SYSTEM_ACCOUNT_BN256_PAIRING => {
+            let pairs = len / 192;
+            36_364 + pairs.saturating_sub(1) * 12_121
+        }
+        // Implemented in the program, about 300 units per round
+        SYSTEM_ACCOUNT_BLAKE2F => {
+            let rounds = data
+                .get(..4)
+                .map_or(0, |rounds| u32::from_be_bytes(rounds.try_into().unwrap()));
+            u64::from(rounds) * 300
+        }
+        _ => 0,
+    }
+}
@@ -18,6 +18,10 @@ pub struct Stack {
     top: *mut u8,
 }
 
+// The stack exclusively owns its allocation
+unsafe impl Send for Stack {}
+unsafe impl Sync for Stack {}
+
 impl Stack {
     pub fn new() -> Self {
         let (begin, end) = unsafe {
@@ -57,6 +61,25 @@ impl Stack {
         slice.to_vec()
     }
 
+    /// Value at `depth` from the top, zero if the stack is shorter
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn peek_u256(&self, depth: usize) -> U256 {
+        if depth >= self.len() {
+            return U256::ZERO;
+        }
+
+        let value = unsafe { *(self.top.sub((depth + 1) * ELEMENT_SIZE) as *const [u8; 32]) };
+        U256::from_be_bytes(value)
+    }
+
+    /// Value at `depth` from the top saturated to `usize`, zero if the stack is shorter
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn peek_usize(&self, depth: usize) -> usize {
+        self.peek_u256(depth).try_into().unwrap_or(usize::MAX)
+    }
+
     #[inline(always)]
     unsafe fn read(&self) -> &[u8; 32] {
         &*(self.top as *const [u8; 32])
@@ -1,7 +1,6 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use crate::account::EthereumAccount;
 use crate::executor::Action;
@@ -19,7 +18,16 @@ pub struct EmulationResult {
     pub exit_status: ExitStatus,
     pub steps_executed: u64,
     pub used_gas: u64,
+    /// `used_gas * gas_price`, charged to the sender and credited to the operator
+    pub gas_payment: U256,
     pub actions: Vec<Action>,
+    /// Estimated compute units of each iteration
+    pub compute_units: Vec<u64>,
+    /// Estimated peak heap usage
+    pub heap_size: usize,
+    /// Call depth and program counter of the executing frame, set on `ExitStatus::StepLimit`
+    pub call_depth: Option<usize>,
+    pub pc: Option<usize>,
 }
 
 pub trait EventListener: Send + Sync + Debug {
@@ -27,7 +35,7 @@ pub trait EventListener: Send + Sync + Debug {
     fn into_traces(self: Box<Self>, emulation_result: EmulationResult) -> Value;
 }
 
-pub type TracerType = Rc<RefCell<Box<dyn EventListener>>>;
+pub type TracerType = Arc<Mutex<Box<dyn EventListener>>>;
 pub type TracerTypeOpt = Option<TracerType>;
 
 /// Trace event
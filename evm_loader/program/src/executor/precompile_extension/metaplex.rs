@@ -5,13 +5,12 @@ use std::convert::{Into, TryInto};
 use ethnum::U256;
 use maybe_async::maybe_async;
 use mpl_token_metadata::state::{
-    Creator, Metadata, TokenMetadataAccount, TokenStandard, CREATE_FEE, MAX_MASTER_EDITION_LEN,
-    MAX_METADATA_LEN,
+    Creator, DataV2, Metadata, TokenMetadataAccount, TokenStandard, CREATE_FEE, MAX_EDITION_LEN,
+    MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN,
 };
 use solana_program::{pubkey::Pubkey, rent::Rent, sysvar::Sysvar};
 
 use crate::{
-    account::ACCOUNT_SEED_VERSION,
     account_storage::AccountStorage,
     error::{Error, Result},
     executor::ExecutorState,
@@ -19,13 +18,22 @@ use crate::{
 };
 
 // "[0xc5, 0x73, 0x50, 0xc6]": "createMetadata(bytes32,string,string,string)"
+// "[0x5d, 0x41, 0x72, 0xf9]": "createMetadata(bytes32,string,string,string,bool)"
 // "[0x4a, 0xe8, 0xb6, 0x6b]": "createMasterEdition(bytes32,uint64)"
+// "[0x1b, 0x46, 0x51, 0xda]": "updateUri(bytes32,string)"
+// "[0x8d, 0x11, 0x18, 0xc1]": "setAndVerifyCollection(bytes32,bytes32)"
+// "[0x89, 0xf3, 0x5d, 0xd1]": "verifyCollection(bytes32,bytes32)"
+// "[0x47, 0x7f, 0x75, 0x14]": "printEdition(bytes32,bytes32,bytes32,uint64)"
+// "[0xd0, 0xf5, 0x29, 0x84]": "burn(bytes32,bytes32)"
 // "[0xf7, 0xb6, 0x37, 0xbb]": "isInitialized(bytes32)"
 // "[0x23, 0x5b, 0x2b, 0x94]": "isNFT(bytes32)"
 // "[0x9e, 0xd1, 0x9d, 0xdb]": "uri(bytes32)"
 // "[0x69, 0x1f, 0x34, 0x31]": "name(bytes32)"
 // "[0x6b, 0xaa, 0x03, 0x30]": "symbol(bytes32)"
+// "[0x1b, 0x25, 0x5b, 0x4c]": "isMutable(bytes32)"
+// "[0x36, 0xa6, 0x80, 0x2f]": "collection(bytes32)"
 
+#[allow(clippy::too_many_lines)]
 #[maybe_async]
 pub async fn metaplex<B: AccountStorage>(
     state: &mut ExecutorState<'_, B>,
@@ -59,7 +67,21 @@ pub async fn metaplex<B: AccountStorage>(
             let symbol = read_string(input, 64, 256)?;
             let uri = read_string(input, 96, 1024)?;
 
-            create_metadata(context, state, mint, name, symbol, uri)
+            create_metadata(context, state, mint, name, symbol, uri, false)
+        }
+        [0x5d, 0x41, 0x72, 0xf9] => {
+            // "createMetadata(bytes32,string,string,string,bool)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let mint = read_pubkey(input)?;
+            let name = read_string(input, 32, 256)?;
+            let symbol = read_string(input, 64, 256)?;
+            let uri = read_string(input, 96, 1024)?;
+            let is_mutable = read_bool(&input[128..])?;
+
+            create_metadata(context, state, mint, name, symbol, uri, is_mutable)
         }
         [0x4a, 0xe8, 0xb6, 0x6b] => {
             // "createMasterEdition(bytes32,uint64)"
@@ -72,6 +94,70 @@ pub async fn metaplex<B: AccountStorage>(
 
             create_master_edition(context, state, mint, Some(max_supply))
         }
+        [0x1b, 0x46, 0x51, 0xda] => {
+            // "updateUri(bytes32,string)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let mint = read_pubkey(input)?;
+            let uri = read_string(input, 32, 1024)?;
+
+            update_uri(context, state, mint, uri).await
+        }
+        [0x8d, 0x11, 0x18, 0xc1] => {
+            // "setAndVerifyCollection(bytes32,bytes32)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let mint = read_pubkey(input)?;
+            let collection_mint = read_pubkey(&input[32..])?;
+
+            set_and_verify_collection(context, state, mint, collection_mint)
+        }
+        [0x89, 0xf3, 0x5d, 0xd1] => {
+            // "verifyCollection(bytes32,bytes32)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let mint = read_pubkey(input)?;
+            let collection_mint = read_pubkey(&input[32..])?;
+
+            verify_collection(context, state, mint, collection_mint)
+        }
+        [0x47, 0x7f, 0x75, 0x14] => {
+            // "printEdition(bytes32,bytes32,bytes32,uint64)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let master_mint = read_pubkey(input)?;
+            let master_token_account = read_pubkey(&input[32..])?;
+            let new_mint = read_pubkey(&input[64..])?;
+            let edition = read_u64(&input[96..])?;
+
+            print_edition(
+                context,
+                state,
+                master_mint,
+                master_token_account,
+                new_mint,
+                edition,
+            )
+        }
+        [0xd0, 0xf5, 0x29, 0x84] => {
+            // "burn(bytes32,bytes32)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let mint = read_pubkey(input)?;
+            let token_account = read_pubkey(&input[32..])?;
+
+            burn(context, state, mint, token_account).await
+        }
         [0xf7, 0xb6, 0x37, 0xbb] => {
             // "isInitialized(bytes32)"
             let mint = read_pubkey(input)?;
@@ -97,6 +183,16 @@ pub async fn metaplex<B: AccountStorage>(
             let mint = read_pubkey(input)?;
             symbol(context, state, mint).await
         }
+        [0x1b, 0x25, 0x5b, 0x4c] => {
+            // "isMutable(bytes32)"
+            let mint = read_pubkey(input)?;
+            is_mutable(context, state, mint).await
+        }
+        [0x36, 0xa6, 0x80, 0x2f] => {
+            // "collection(bytes32)"
+            let mint = read_pubkey(input)?;
+            collection(context, state, mint).await
+        }
         _ => Err(Error::UnknownPrecompileMethodSelector(*address, selector)),
     }
 }
@@ -111,6 +207,18 @@ fn read_u64(input: &[u8]) -> Result<u64> {
         .map_err(Into::into)
 }
 
+#[inline]
+fn read_bool(input: &[u8]) -> Result<bool> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+    let value = U256::from_be_bytes(*arrayref::array_ref![input, 0, 32]);
+    if value > 1 {
+        return Err(Error::Custom("Invalid bool value".to_string()));
+    }
+    Ok(value == 1)
+}
+
 #[inline]
 fn read_pubkey(input: &[u8]) -> Result<Pubkey> {
     if input.len() < 32 {
@@ -151,15 +259,10 @@ fn create_metadata<B: AccountStorage>(
     name: String,
     symbol: String,
     uri: String,
+    is_mutable: bool,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
 
@@ -185,12 +288,12 @@ fn create_metadata<B: AccountStorage>(
                 share: 100,
             },
         ]),
-        0,     // Seller Fee
-        true,  // Update Authority == Mint Authority
-        false, // Is Mutable
-        None,  // Collection
-        None,  // Uses
-        None,  // Collection Details
+        0,          // Seller Fee
+        true,       // Update Authority == Mint Authority
+        is_mutable, // Is Mutable
+        None,       // Collection
+        None,       // Uses
+        None,       // Collection Details
     );
 
     let rent = Rent::get()?;
@@ -208,13 +311,7 @@ fn create_master_edition<B: AccountStorage>(
     max_supply: Option<u64>,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
     let (edition_pubkey, _) = mpl_token_metadata::pda::find_master_edition_account(&mint);
@@ -238,6 +335,192 @@ fn create_master_edition<B: AccountStorage>(
     Ok(edition_pubkey.to_bytes().to_vec())
 }
 
+#[maybe_async]
+async fn update_uri<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    mint: Pubkey,
+    uri: String,
+) -> Result<Vec<u8>> {
+    let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
+    let metadata = metadata(context, state, mint)
+        .await?
+        .ok_or(Error::AccountInvalidData(metadata_pubkey))?;
+
+    let signer = context.caller;
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
+
+    let data = DataV2 {
+        name: metadata.data.name.trim_end_matches('\0').to_string(),
+        symbol: metadata.data.symbol.trim_end_matches('\0').to_string(),
+        uri,
+        seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        creators: metadata.data.creators,
+        collection: metadata.collection,
+        uses: metadata.uses,
+    };
+
+    let instruction = mpl_token_metadata::instruction::update_metadata_accounts_v2(
+        mpl_token_metadata::ID,
+        metadata_pubkey,
+        signer_pubkey,
+        None, // New Update Authority
+        Some(data),
+        None, // Primary Sale Happened
+        None, // Is Mutable
+    );
+
+    state.queue_external_instruction(instruction, seeds, 0);
+
+    Ok(vec![])
+}
+
+fn set_and_verify_collection<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<B>,
+    mint: Pubkey,
+    collection_mint: Pubkey,
+) -> Result<Vec<u8>> {
+    let signer = context.caller;
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
+
+    let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
+    let (collection_metadata_pubkey, _) =
+        mpl_token_metadata::pda::find_metadata_account(&collection_mint);
+    let (collection_edition_pubkey, _) =
+        mpl_token_metadata::pda::find_master_edition_account(&collection_mint);
+
+    let instruction = mpl_token_metadata::instruction::set_and_verify_collection(
+        mpl_token_metadata::ID,
+        metadata_pubkey,
+        signer_pubkey,
+        *state.backend.operator(),
+        signer_pubkey,
+        collection_mint,
+        collection_metadata_pubkey,
+        collection_edition_pubkey,
+        None, // Collection Authority Record
+    );
+
+    state.queue_external_instruction(instruction, seeds, 0);
+
+    Ok(vec![])
+}
+
+fn verify_collection<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<B>,
+    mint: Pubkey,
+    collection_mint: Pubkey,
+) -> Result<Vec<u8>> {
+    let signer = context.caller;
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
+
+    let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
+    let (collection_metadata_pubkey, _) =
+        mpl_token_metadata::pda::find_metadata_account(&collection_mint);
+    let (collection_edition_pubkey, _) =
+        mpl_token_metadata::pda::find_master_edition_account(&collection_mint);
+
+    let instruction = mpl_token_metadata::instruction::verify_collection(
+        mpl_token_metadata::ID,
+        metadata_pubkey,
+        signer_pubkey,
+        *state.backend.operator(),
+        collection_mint,
+        collection_metadata_pubkey,
+        collection_edition_pubkey,
+        None, // Collection Authority Record
+    );
+
+    state.queue_external_instruction(instruction, seeds, 0);
+
+    Ok(vec![])
+}
+
+fn print_edition<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<B>,
+    master_mint: Pubkey,
+    master_token_account: Pubkey,
+    new_mint: Pubkey,
+    edition: u64,
+) -> Result<Vec<u8>> {
+    let signer = context.caller;
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
+
+    let (master_metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&master_mint);
+    let (master_edition_pubkey, _) =
+        mpl_token_metadata::pda::find_master_edition_account(&master_mint);
+    let (new_metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&new_mint);
+    let (new_edition_pubkey, _) = mpl_token_metadata::pda::find_master_edition_account(&new_mint);
+
+    let instruction =
+        mpl_token_metadata::instruction::mint_new_edition_from_master_edition_via_token(
+            mpl_token_metadata::ID,
+            new_metadata_pubkey,
+            new_edition_pubkey,
+            master_edition_pubkey,
+            new_mint,
+            signer_pubkey,
+            *state.backend.operator(),
+            signer_pubkey,
+            master_token_account,
+            signer_pubkey,
+            master_metadata_pubkey,
+            master_mint,
+            edition,
+        );
+
+    let rent = Rent::get()?;
+    let fee = rent.minimum_balance(MAX_METADATA_LEN)
+        + rent.minimum_balance(MAX_EDITION_LEN)
+        + rent.minimum_balance(MAX_EDITION_MARKER_SIZE)
+        + CREATE_FEE;
+
+    state.queue_external_instruction(instruction, seeds, fee);
+
+    Ok(new_edition_pubkey.to_bytes().to_vec())
+}
+
+#[maybe_async]
+async fn burn<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    mint: Pubkey,
+    token_account: Pubkey,
+) -> Result<Vec<u8>> {
+    let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
+    let metadata = metadata(context, state, mint)
+        .await?
+        .ok_or(Error::AccountInvalidData(metadata_pubkey))?;
+
+    let signer = context.caller;
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
+
+    let (edition_pubkey, _) = mpl_token_metadata::pda::find_master_edition_account(&mint);
+
+    let collection_metadata_pubkey = metadata
+        .collection
+        .filter(|c| c.verified)
+        .map(|c| mpl_token_metadata::pda::find_metadata_account(&c.key).0);
+
+    let instruction = mpl_token_metadata::instruction::burn_nft(
+        mpl_token_metadata::ID,
+        metadata_pubkey,
+        signer_pubkey,
+        mint,
+        token_account,
+        edition_pubkey,
+        spl_token::ID,
+        collection_metadata_pubkey,
+    );
+
+    state.queue_external_instruction(instruction, seeds, 0);
+
+    Ok(vec![])
+}
+
 #[maybe_async]
 async fn is_initialized<B: AccountStorage>(
     context: &crate::evm::Context,
@@ -304,6 +587,39 @@ async fn symbol<B: AccountStorage>(
     Ok(to_solidity_string(symbol.trim_end_matches('\0')))
 }
 
+#[maybe_async]
+async fn is_mutable<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    mint: Pubkey,
+) -> Result<Vec<u8>> {
+    let is_mutable = metadata(context, state, mint)
+        .await?
+        .map_or_else(|| false, |m| m.is_mutable);
+
+    Ok(to_solidity_bool(is_mutable))
+}
+
+#[maybe_async]
+async fn collection<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    mint: Pubkey,
+) -> Result<Vec<u8>> {
+    let collection = metadata(context, state, mint)
+        .await?
+        .and_then(|m| m.collection);
+
+    // Returns (bytes32 collectionMint, bool verified)
+    let mut result = vec![0_u8; 64];
+    if let Some(collection) = collection {
+        result[0..32].copy_from_slice(collection.key.as_ref());
+        result[63] = u8::from(collection.verified);
+    }
+
+    Ok(result)
+}
+
 #[maybe_async]
 async fn metadata<B: AccountStorage>(
     _context: &crate::evm::Context,
@@ -353,3 +669,46 @@ fn to_solidity_string(s: &str) -> Vec<u8> {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: u128) -> Vec<u8> {
+        U256::new(value).to_be_bytes().to_vec()
+    }
+
+    #[test]
+    fn bool_argument() {
+        assert!(!read_bool(&word(0)).unwrap());
+        assert!(read_bool(&word(1)).unwrap());
+        assert!(matches!(read_bool(&word(2)), Err(Error::Custom(_))));
+        assert!(matches!(read_bool(&[1; 31]), Err(Error::OutOfBounds)));
+    }
+
+    #[test]
+    fn string_argument() {
+        let uri = "https://example.com/nft/1.json";
+        let encoded = to_solidity_string(uri);
+        assert_eq!(encoded.len(), 32 + 32 + 32);
+        assert_eq!(read_string(&encoded, 0, 200).unwrap(), uri);
+
+        // Longer than the metadata field allows
+        assert!(matches!(
+            read_string(&encoded, 0, uri.len() - 1),
+            Err(Error::OutOfBounds)
+        ));
+        // Data cut before the end of the string
+        assert!(matches!(
+            read_string(&encoded[..64 + 10], 0, 200),
+            Err(Error::OutOfBounds)
+        ));
+        // Offset pointing past the input
+        let mut input = word(0x1000);
+        input.extend_from_slice(&encoded[32..]);
+        assert!(matches!(
+            read_string(&input, 0, 200),
+            Err(Error::OutOfBounds)
+        ));
+    }
+}
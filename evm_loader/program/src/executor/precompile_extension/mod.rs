@@ -7,6 +7,8 @@ mod metaplex;
 mod neon_token;
 mod query_account;
 mod spl_token;
+mod system_program;
+mod sysvar;
 
 impl<'a, B: AccountStorage> ExecutorState<'a, B> {
     #[deprecated]
@@ -25,6 +27,12 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
     const SYSTEM_ACCOUNT_METAPLEX: Address = Address([
         0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x05,
     ]);
+    const SYSTEM_ACCOUNT_SYSTEM_PROGRAM: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x06,
+    ]);
+    const SYSTEM_ACCOUNT_SYSVAR: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x07,
+    ]);
 
     #[must_use]
     #[allow(clippy::unused_self)]
@@ -33,6 +41,8 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
             || *address == Self::SYSTEM_ACCOUNT_NEON_TOKEN
             || *address == Self::SYSTEM_ACCOUNT_SPL_TOKEN
             || *address == Self::SYSTEM_ACCOUNT_METAPLEX
+            || *address == Self::SYSTEM_ACCOUNT_SYSTEM_PROGRAM
+            || *address == Self::SYSTEM_ACCOUNT_SYSVAR
     }
 
     #[maybe_async]
@@ -56,6 +66,12 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
             Self::SYSTEM_ACCOUNT_METAPLEX => {
                 Some(metaplex::metaplex(self, address, input, context, is_static).await)
             }
+            Self::SYSTEM_ACCOUNT_SYSTEM_PROGRAM => {
+                Some(system_program::system_program(self, address, input, context, is_static).await)
+            }
+            Self::SYSTEM_ACCOUNT_SYSVAR => {
+                Some(sysvar::sysvar(self, address, input, context, is_static).await)
+            }
             _ => None,
         }
     }
@@ -71,7 +71,11 @@ async fn withdraw<B: AccountStorage>(
         return Err(Error::Custom("Neon Withdraw: value == 0".to_string()));
     }
 
-    let additional_decimals: u32 = (18 - crate::config::token_mint::decimals()).into();
+    let decimals = crate::config::chain_info(state.backend.chain_id())
+        .ok_or_else(|| Error::InvalidChainId(state.backend.chain_id().into()))?
+        .decimals;
+
+    let additional_decimals: u32 = (18 - decimals).into();
     let min_amount: u128 = u128::pow(10, additional_decimals);
 
     let spl_amount = value / min_amount;
@@ -118,7 +122,7 @@ async fn withdraw<B: AccountStorage>(
         &authority,
         &[],
         spl_amount.as_u64(),
-        crate::config::token_mint::decimals(),
+        decimals,
     )?;
     let transfer_seeds = vec![b"Deposit".to_vec(), vec![bump_seed]];
     state.queue_external_instruction(transfer, transfer_seeds, 0);
@@ -3,7 +3,10 @@ use std::convert::TryInto;
 use arrayref::{array_ref, array_refs};
 use ethnum::U256;
 use maybe_async::maybe_async;
-use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+    system_program,
+};
 
 use crate::{
     account_storage::AccountStorage,
@@ -30,6 +33,19 @@ use crate::{
 // "b64a097e": "info(bytes32)",
 // "a9dbaf25": "length(bytes32)",
 // "7dd6c1a0": "data(bytes32,uint64,uint64)",
+// "a0f5c1d1": "batch(bytes32[],uint64[],uint64[])",
+// "6ff3a21f": "splMint(bytes32)",
+// "b119bc31": "splAccount(bytes32)",
+// "3d8432b4": "pythPrice(bytes32)",
+
+/// Upper bound of accounts in the one `batch` call
+const BATCH_MAX_ACCOUNTS: usize = 32;
+/// Upper bound of slices in the one `batch` call
+const BATCH_MAX_SLICES: usize = 16;
+/// Upper bound of the one slice length in the `batch` call
+const BATCH_MAX_SLICE_LENGTH: usize = 1024;
+/// Upper bound of the account data returned by the one `batch` call, all slices of all accounts
+const BATCH_MAX_DATA_LENGTH: usize = 8 * 1024;
 
 #[maybe_async]
 pub async fn query_account<B: AccountStorage>(
@@ -48,6 +64,15 @@ pub async fn query_account<B: AccountStorage>(
     let (method_id, rest) = input.split_at(4);
     let method_id: [u8; 4] = method_id.try_into()?;
 
+    if method_id == [0xa0, 0xf5, 0xc1, 0xd1] {
+        // batch(bytes32[],uint64[],uint64[])
+        let accounts = read_array(rest, 0, BATCH_MAX_ACCOUNTS)?;
+        let offsets = read_array(rest, 32, BATCH_MAX_SLICES)?;
+        let lengths = read_array(rest, 64, BATCH_MAX_SLICES)?;
+        debug_print!("query_account.batch({:?})", &accounts);
+        return account_batch(state, &accounts, &offsets, &lengths).await;
+    }
+
     let (account_address, rest) = rest.split_at(32);
     let account_address = Pubkey::try_from(account_address)?;
 
@@ -94,6 +119,18 @@ pub async fn query_account<B: AccountStorage>(
             debug_print!("query_account.info({})", &account_address);
             account_info(state, &account_address).await
         }
+        [0x6f, 0xf3, 0xa2, 0x1f] => {
+            debug_print!("query_account.splMint({})", &account_address);
+            spl_mint(state, &account_address).await
+        }
+        [0xb1, 0x19, 0xbc, 0x31] => {
+            debug_print!("query_account.splAccount({})", &account_address);
+            spl_account(state, &account_address).await
+        }
+        [0x3d, 0x84, 0x32, 0xb4] => {
+            debug_print!("query_account.pythPrice({})", &account_address);
+            pyth_price(state, &account_address).await
+        }
         _ => {
             debug_print!("query_account UNKNOWN {:?}", method_id);
             Err(Error::UnknownPrecompileMethodSelector(*address, method_id))
@@ -236,3 +273,324 @@ async fn account_info<B: AccountStorage>(
 
     Ok(info)
 }
+
+/// Reads ABI encoded dynamic array of 32 byte words
+fn read_array(input: &[u8], offset_position: usize, max_length: usize) -> Result<Vec<[u8; 32]>> {
+    if input.len() < offset_position + 32 {
+        return Err(Error::OutOfBounds);
+    }
+    let offset: usize = U256::from_be_bytes(*array_ref![input, offset_position, 32]).try_into()?;
+    if input.len() < offset.saturating_add(32) {
+        return Err(Error::OutOfBounds);
+    }
+    let length: usize = U256::from_be_bytes(*array_ref![input, offset, 32]).try_into()?;
+    if length > max_length {
+        return Err(Error::OutOfBounds);
+    }
+
+    let begin = offset.saturating_add(32);
+    let end = begin.saturating_add(length * 32);
+    if input.len() < end {
+        return Err(Error::OutOfBounds);
+    }
+
+    let items = input[begin..end]
+        .chunks_exact(32)
+        .map(|chunk| *array_ref![chunk, 0, 32])
+        .collect();
+
+    Ok(items)
+}
+
+/// Returns ABI encoded `(bytes32 owner, uint64 lamports, bytes[] data)[]`.
+/// Each `data` item is a slice `[offsets[i], offsets[i] + lengths[i])` of the account data,
+/// out of bounds slices are returned empty.
+#[maybe_async]
+async fn account_batch<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    accounts: &[[u8; 32]],
+    offsets: &[[u8; 32]],
+    lengths: &[[u8; 32]],
+) -> Result<Vec<u8>> {
+    fn pad_len(len: usize) -> usize {
+        (len + 31) / 32 * 32
+    }
+
+    fn to_solidity_batch_value(info: &AccountInfo, slices: &[(usize, usize)]) -> Vec<u8> {
+        let data = info.data.borrow();
+        let chunks = slices
+            .iter()
+            .map(|(offset, length)| {
+                data.get(*offset..offset.saturating_add(*length))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let chunks_size: usize = chunks.iter().map(|c| 32 + pad_len(c.len())).sum();
+        let mut buffer = vec![0_u8; 3 * 32 + 32 + chunks.len() * 32 + chunks_size];
+
+        buffer[0..32].copy_from_slice(info.owner.as_ref());
+        buffer[56..64].copy_from_slice(&info.lamports().to_be_bytes());
+        buffer[64..96].copy_from_slice(&U256::new(3 * 32).to_be_bytes());
+        buffer[96..128].copy_from_slice(&U256::new(chunks.len() as u128).to_be_bytes());
+
+        let heads = 128;
+        let mut tail = chunks.len() * 32;
+        for (i, chunk) in chunks.iter().enumerate() {
+            let head = heads + i * 32;
+            buffer[head..head + 32].copy_from_slice(&U256::new(tail as u128).to_be_bytes());
+
+            let position = heads + tail;
+            buffer[position..position + 32]
+                .copy_from_slice(&U256::new(chunk.len() as u128).to_be_bytes());
+            buffer[position + 32..position + 32 + chunk.len()].copy_from_slice(chunk);
+
+            tail += 32 + pad_len(chunk.len());
+        }
+
+        buffer
+    }
+
+    if offsets.len() != lengths.len() {
+        return Err(Error::Custom(
+            "Query Account: batch() - offsets and lengths mismatch".to_string(),
+        ));
+    }
+
+    let mut slices = Vec::with_capacity(offsets.len());
+    for (offset, length) in offsets.iter().zip(lengths) {
+        let offset: usize = U256::from_be_bytes(*offset).try_into()?;
+        let length: usize = U256::from_be_bytes(*length).try_into()?;
+        if length == 0 {
+            return Err(Error::Custom(
+                "Query Account: batch() - length == 0".to_string(),
+            ));
+        }
+        if length > BATCH_MAX_SLICE_LENGTH {
+            return Err(Error::Custom(format!(
+                "Query Account: batch() - length {length} > {BATCH_MAX_SLICE_LENGTH}"
+            )));
+        }
+        slices.push((offset, length));
+    }
+
+    let data_length = slices.iter().map(|(_, length)| length).sum::<usize>() * accounts.len();
+    if data_length > BATCH_MAX_DATA_LENGTH {
+        return Err(Error::Custom(format!(
+            "Query Account: batch() - total length {data_length} > {BATCH_MAX_DATA_LENGTH}"
+        )));
+    }
+
+    let mut values = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let address = Pubkey::new_from_array(*account);
+        let value = state
+            .backend
+            .map_solana_account(&address, |info| to_solidity_batch_value(info, &slices))
+            .await;
+        values.push(value);
+    }
+
+    let values_size: usize = values.iter().map(Vec::len).sum();
+    let mut result = Vec::with_capacity(64 + values.len() * 32 + values_size);
+    result.extend_from_slice(&U256::new(0x20).to_be_bytes());
+    result.extend_from_slice(&U256::new(values.len() as u128).to_be_bytes());
+
+    let mut tail = values.len() * 32;
+    for value in &values {
+        result.extend_from_slice(&U256::new(tail as u128).to_be_bytes());
+        tail += value.len();
+    }
+    for value in values {
+        result.extend_from_slice(&value);
+    }
+
+    Ok(result)
+}
+
+#[maybe_async]
+async fn spl_mint<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Pubkey,
+) -> Result<Vec<u8>> {
+    let mint = state
+        .backend
+        .map_solana_account(address, |info| {
+            if spl_token::check_id(info.owner) {
+                spl_token::state::Mint::unpack(&info.data.borrow())
+            } else if system_program::check_id(info.owner) {
+                Ok(spl_token::state::Mint::default())
+            } else {
+                Err(ProgramError::IllegalOwner)
+            }
+        })
+        .await?;
+
+    Ok(super::spl_token::to_solidity_mint(&mint))
+}
+
+#[maybe_async]
+async fn spl_account<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Pubkey,
+) -> Result<Vec<u8>> {
+    let token = state
+        .backend
+        .map_solana_account(address, |info| {
+            if spl_token::check_id(info.owner) {
+                spl_token::state::Account::unpack(&info.data.borrow())
+            } else if system_program::check_id(info.owner) {
+                Ok(spl_token::state::Account::default())
+            } else {
+                Err(ProgramError::IllegalOwner)
+            }
+        })
+        .await?;
+
+    Ok(super::spl_token::to_solidity_account(&token))
+}
+
+/// Returns ABI encoded `(int64 price, uint64 conf, int32 expo, uint64 publishTime, uint8 status, uint64 publishSlot)`
+/// decoded from the Pyth V2 price account. The account owner is not checked,
+/// the caller is responsible for passing an account of the expected Pyth program.
+#[maybe_async]
+async fn pyth_price<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Pubkey,
+) -> Result<Vec<u8>> {
+    const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+    const PYTH_VERSION: u32 = 2;
+    const PYTH_PRICE_ACCOUNT: u32 = 3;
+    const PYTH_PRICE_ACCOUNT_HEADER_LEN: usize = 240;
+
+    fn to_solidity_price_value(info: &AccountInfo) -> Option<Vec<u8>> {
+        let data = info.data.borrow();
+        if data.len() < PYTH_PRICE_ACCOUNT_HEADER_LEN {
+            return None;
+        }
+
+        let header = array_ref![data, 0, PYTH_PRICE_ACCOUNT_HEADER_LEN];
+        #[rustfmt::skip]
+        let (
+            magic, version, account_type, _, _, expo, _, _, _, _,
+            _, _, timestamp, _, _, _, _,
+            price, conf, status, _, publish_slot,
+        ) = array_refs![
+            header,
+            4, 4, 4, 4, 4, 4, 4, 4, 8, 8,
+            24, 24, 8, 8, 32, 32, 32,
+            8, 8, 4, 4, 8
+        ];
+
+        if (u32::from_le_bytes(*magic) != PYTH_MAGIC)
+            || (u32::from_le_bytes(*version) != PYTH_VERSION)
+            || (u32::from_le_bytes(*account_type) != PYTH_PRICE_ACCOUNT)
+        {
+            return None;
+        }
+
+        let mut buffer = [0_u8; 6 * 32];
+        let (price_value, conf_value, expo_value, timestamp_value, status_value, slot_value) =
+            arrayref::mut_array_refs![&mut buffer, 32, 32, 32, 32, 32, 32];
+
+        let price = i64::from_le_bytes(*price);
+        *price_value = ethnum::I256::from(price).to_be_bytes();
+        conf_value[24..].copy_from_slice(&u64::from_le_bytes(*conf).to_be_bytes());
+        let expo = i32::from_le_bytes(*expo);
+        *expo_value = ethnum::I256::from(expo).to_be_bytes();
+        let timestamp = i64::from_le_bytes(*timestamp);
+        timestamp_value[24..].copy_from_slice(&timestamp.max(0).to_be_bytes());
+        // PriceStatus: Unknown = 0, Trading = 1, Halted = 2, Auction = 3, Ignored = 4
+        status_value[31] = u32::from_le_bytes(*status).try_into().unwrap_or(0);
+        slot_value[24..].copy_from_slice(&u64::from_le_bytes(*publish_slot).to_be_bytes());
+
+        Some(buffer.to_vec())
+    }
+
+    state
+        .backend
+        .map_solana_account(address, to_solidity_price_value)
+        .await
+        .ok_or_else(|| {
+            Error::Custom("Query Account: pythPrice() - not a price account".to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_storage::MemoryAccountStorage, config::CHAIN_ID, executor::OwnedAccountInfo,
+    };
+
+    fn word(value: usize) -> [u8; 32] {
+        U256::new(value as u128).to_be_bytes()
+    }
+
+    fn storage_with_account(key: Pubkey, data: Vec<u8>) -> MemoryAccountStorage {
+        let mut storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        storage.add_solana_account(OwnedAccountInfo {
+            key,
+            is_signer: false,
+            is_writable: false,
+            lamports: 42,
+            data,
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        });
+        storage
+    }
+
+    #[tokio::test]
+    async fn batch_slices() {
+        let key = Pubkey::new_unique();
+        let storage = storage_with_account(key, (0..64).collect());
+        let mut state = ExecutorState::new(&storage);
+
+        let result = account_batch(
+            &mut state,
+            &[key.to_bytes()],
+            &[word(2), word(60)],
+            &[word(3), word(8)],
+        )
+        .await
+        .unwrap();
+
+        // array offset, array length, tuple offset
+        assert_eq!(result[0..96], [word(0x20), word(1), word(0x20)].concat());
+        let value = &result[96..];
+        assert_eq!(value[0..32], system_program::ID.to_bytes());
+        assert_eq!(value[32..64], word(42));
+        // bytes[]: two items, the second one is out of bounds and returned empty
+        assert_eq!(value[96..128], word(2));
+        assert_eq!(value[192..224], word(3));
+        assert_eq!(value[224..227], [2, 3, 4]);
+        assert_eq!(value[256..288], word(0));
+        assert_eq!(value.len(), 288);
+    }
+
+    #[tokio::test]
+    async fn batch_limits() {
+        let key = Pubkey::new_unique();
+        let storage = storage_with_account(key, vec![0; 16]);
+        let mut state = ExecutorState::new(&storage);
+
+        let zero = account_batch(&mut state, &[key.to_bytes()], &[word(0)], &[word(0)]).await;
+        assert!(zero.is_err());
+
+        let slice = BATCH_MAX_SLICE_LENGTH + 1;
+        let long = account_batch(&mut state, &[key.to_bytes()], &[word(0)], &[word(slice)]).await;
+        assert!(long.is_err());
+
+        let accounts = vec![key.to_bytes(); BATCH_MAX_ACCOUNTS];
+        let offsets = vec![word(0); BATCH_MAX_SLICES];
+        let lengths = vec![word(BATCH_MAX_SLICE_LENGTH); BATCH_MAX_SLICES];
+        let total = account_batch(&mut state, &accounts, &offsets, &lengths).await;
+        assert!(total.is_err());
+
+        let lengths = vec![word(16); BATCH_MAX_SLICES];
+        let total = account_batch(&mut state, &accounts, &offsets, &lengths).await;
+        assert!(total.is_ok());
+    }
+}
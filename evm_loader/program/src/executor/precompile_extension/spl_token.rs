@@ -296,29 +296,18 @@ async fn initialize_mint<B: AccountStorage>(
     let signer = context.caller;
     let (signer_pubkey, _) = state.backend.solana_address(&signer);
 
-    let (mint_key, bump_seed) = Pubkey::find_program_address(
-        &[
-            &[ACCOUNT_SEED_VERSION],
-            b"ContractData",
-            signer.as_bytes(),
-            seed,
-        ],
-        state.backend.program_id(),
-    );
+    let (mint_key, seeds) = state.backend.find_program_address(&[
+        &[ACCOUNT_SEED_VERSION],
+        b"ContractData",
+        signer.as_bytes(),
+        seed,
+    ]);
 
     let account = state.external_account(mint_key).await?;
     if !system_program::check_id(&account.owner) {
         return Err(Error::AccountInvalidOwner(mint_key, system_program::ID));
     }
 
-    let seeds: Vec<Vec<u8>> = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        b"ContractData".to_vec(),
-        signer.as_bytes().to_vec(),
-        seed.to_vec(),
-        vec![bump_seed],
-    ];
-
     create_account(state, &account, spl_token::state::Mint::LEN, seeds)?;
 
     let initialize_mint = spl_token::instruction::initialize_mint(
@@ -344,29 +333,18 @@ async fn initialize_account<B: AccountStorage>(
     let signer = context.caller;
     let (signer_pubkey, _) = state.backend.solana_address(&signer);
 
-    let (account_key, bump_seed) = Pubkey::find_program_address(
-        &[
-            &[ACCOUNT_SEED_VERSION],
-            b"ContractData",
-            signer.as_bytes(),
-            seed,
-        ],
-        state.backend.program_id(),
-    );
+    let (account_key, seeds) = state.backend.find_program_address(&[
+        &[ACCOUNT_SEED_VERSION],
+        b"ContractData",
+        signer.as_bytes(),
+        seed,
+    ]);
 
     let account = state.external_account(account_key).await?;
     if !system_program::check_id(&account.owner) {
         return Err(Error::AccountInvalidOwner(account_key, system_program::ID));
     }
 
-    let seeds: Vec<Vec<u8>> = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        b"ContractData".to_vec(),
-        signer.as_bytes().to_vec(),
-        seed.to_vec(),
-        vec![bump_seed],
-    ];
-
     create_account(state, &account, spl_token::state::Account::LEN, seeds)?;
 
     let initialize_mint = spl_token::instruction::initialize_account2(
@@ -386,13 +364,7 @@ fn close_account<B: AccountStorage>(
     account: Pubkey,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let close_account = spl_token::instruction::close_account(
         &spl_token::ID,
@@ -414,13 +386,7 @@ fn approve<B: AccountStorage>(
     amount: u64,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let approve = spl_token::instruction::approve(
         &spl_token::ID,
@@ -441,13 +407,7 @@ fn revoke<B: AccountStorage>(
     account: Pubkey,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let revoke = spl_token::instruction::revoke(&spl_token::ID, &account, &signer_pubkey, &[])?;
     state.queue_external_instruction(revoke, seeds, 0);
@@ -463,13 +423,7 @@ fn transfer<B: AccountStorage>(
     amount: u64,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let transfer = spl_token::instruction::transfer(
         &spl_token::ID,
@@ -492,22 +446,12 @@ fn transfer_with_seed<B: AccountStorage>(
     target: Pubkey,
     amount: u64,
 ) -> Result<Vec<u8>> {
-    let seeds: &[&[u8]] = &[
+    let (signer_pubkey, seeds) = state.backend.find_program_address(&[
         &[ACCOUNT_SEED_VERSION],
         b"AUTH",
         context.caller.as_bytes(),
         seed,
-    ];
-    let (signer_pubkey, signer_seed) =
-        Pubkey::find_program_address(seeds, state.backend.program_id());
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        b"AUTH".to_vec(),
-        context.caller.as_bytes().to_vec(),
-        seed.to_vec(),
-        vec![signer_seed],
-    ];
+    ]);
 
     let transfer = spl_token::instruction::transfer(
         &spl_token::ID,
@@ -530,13 +474,7 @@ fn mint_to<B: AccountStorage>(
     amount: u64,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let mint_to = spl_token::instruction::mint_to(
         &spl_token::ID,
@@ -559,13 +497,7 @@ fn burn<B: AccountStorage>(
     amount: u64,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     #[rustfmt::skip]
     let burn = spl_token::instruction::burn(
@@ -588,13 +520,7 @@ fn freeze<B: AccountStorage>(
     target: Pubkey,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     let freeze = spl_token::instruction::freeze_account(
         &spl_token::ID,
@@ -615,13 +541,7 @@ fn thaw<B: AccountStorage>(
     target: Pubkey,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
-    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
-
-    let seeds = vec![
-        vec![ACCOUNT_SEED_VERSION],
-        signer.as_bytes().to_vec(),
-        vec![bump_seed],
-    ];
+    let (signer_pubkey, seeds) = state.backend.solana_address_with_seeds(&signer);
 
     #[rustfmt::skip]
     let thaw = spl_token::instruction::thaw_account(
@@ -644,15 +564,12 @@ fn find_account<B: AccountStorage>(
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
 
-    let (account_key, _) = Pubkey::find_program_address(
-        &[
-            &[ACCOUNT_SEED_VERSION],
-            b"ContractData",
-            signer.as_bytes(),
-            seed,
-        ],
-        state.backend.program_id(),
-    );
+    let (account_key, _) = state.backend.find_program_address(&[
+        &[ACCOUNT_SEED_VERSION],
+        b"ContractData",
+        signer.as_bytes(),
+        seed,
+    ]);
 
     Ok(account_key.to_bytes().to_vec())
 }
@@ -691,6 +608,10 @@ async fn get_account<B: AccountStorage>(
 
     debug_print!("spl_token get_account: {:?}", token);
 
+    Ok(to_solidity_account(&token))
+}
+
+pub(super) fn to_solidity_account(token: &spl_token::state::Account) -> Vec<u8> {
     let mut result = [0_u8; 7 * 32];
     let (mint, owner, _, amount, delegate, _, delegated_amount, close_authority, state) =
         arrayref::mut_array_refs![&mut result, 32, 32, 24, 8, 32, 24, 8, 32, 32];
@@ -706,7 +627,7 @@ async fn get_account<B: AccountStorage>(
         .unwrap_or_default();
     state[31] = token.state as u8;
 
-    Ok(result.to_vec())
+    result.to_vec()
 }
 
 #[maybe_async]
@@ -726,6 +647,10 @@ async fn get_mint<B: AccountStorage>(
 
     debug_print!("spl_token get_mint: {:?}", mint);
 
+    Ok(to_solidity_mint(&mint))
+}
+
+pub(super) fn to_solidity_mint(mint: &spl_token::state::Mint) -> Vec<u8> {
     let mut result = [0_u8; 5 * 32];
     let (_, supply, _, decimals, _, is_initialized, freeze_authority, mint_authority) =
         arrayref::mut_array_refs![&mut result, 24, 8, 31, 1, 31, 1, 32, 32];
@@ -742,5 +667,5 @@ async fn get_mint<B: AccountStorage>(
         .map(Pubkey::to_bytes)
         .unwrap_or_default();
 
-    Ok(result.to_vec())
+    result.to_vec()
 }
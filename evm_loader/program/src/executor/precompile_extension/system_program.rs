@@ -0,0 +1,361 @@
+use std::convert::{Into, TryInto};
+
+use ethnum::U256;
+use maybe_async::maybe_async;
+use solana_program::{
+    pubkey::Pubkey, rent::Rent, system_instruction, system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    account::ACCOUNT_SEED_VERSION,
+    account_storage::AccountStorage,
+    error::{Error, Result},
+    executor::ExecutorState,
+    types::Address,
+};
+
+// [0x30, 0xaa, 0x81, 0xc6] : "getPayer()"
+// [0xeb, 0x7d, 0xa7, 0x8c] : "findAccount(bytes32)"
+// [0x94, 0x59, 0x6c, 0xe6] : "getAccountWithSeed(bytes32,string)"
+// [0xfd, 0x54, 0xd1, 0xea] : "transfer(bytes32,uint64)"
+// [0xfc, 0x96, 0x70, 0x32] : "createAccountWithSeed(bytes32,string,uint64)"
+// [0xfb, 0x2c, 0xa9, 0xb1] : "allocate(bytes32,uint64)"
+// [0x8a, 0xc0, 0x0b, 0xdc] : "assign(bytes32,bytes32)"
+
+#[maybe_async]
+pub async fn system_program<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Address,
+    input: &[u8],
+    context: &crate::evm::Context,
+    is_static: bool,
+) -> Result<Vec<u8>> {
+    if context.value != 0 {
+        return Err(Error::Custom("SystemProgram: value != 0".to_string()));
+    }
+
+    if &context.contract != address {
+        return Err(Error::Custom(
+            "SystemProgram: callcode or delegatecall is not allowed".to_string(),
+        ));
+    }
+
+    if input.len() < 4 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let (selector, input) = input.split_at(4);
+    let selector: [u8; 4] = selector.try_into()?;
+
+    match selector {
+        [0x30, 0xaa, 0x81, 0xc6] => {
+            // getPayer()
+            get_payer(context, state)
+        }
+        [0xeb, 0x7d, 0xa7, 0x8c] => {
+            // findAccount(bytes32 seed)
+            let seed = read_salt(input)?;
+            find_account(context, state, seed)
+        }
+        [0x94, 0x59, 0x6c, 0xe6] => {
+            // getAccountWithSeed(bytes32 owner, string seed)
+            let owner = read_pubkey(input)?;
+            let seed = read_string(input, 32, 32)?;
+            get_account_with_seed(context, state, &owner, &seed)
+        }
+        [0xfd, 0x54, 0xd1, 0xea] => {
+            // transfer(bytes32 target, uint64 lamports)
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let target = read_pubkey(input)?;
+            let lamports = read_u64(&input[32..])?;
+            transfer(context, state, target, lamports).await
+        }
+        [0xfc, 0x96, 0x70, 0x32] => {
+            // createAccountWithSeed(bytes32 owner, string seed, uint64 space)
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let owner = read_pubkey(input)?;
+            let seed = read_string(input, 32, 32)?;
+            let space = read_u64(&input[64..])?;
+            create_account_with_seed(context, state, owner, &seed, space).await
+        }
+        [0xfb, 0x2c, 0xa9, 0xb1] => {
+            // allocate(bytes32 seed, uint64 space)
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let seed = read_salt(input)?;
+            let space = read_u64(&input[32..])?;
+            allocate(context, state, seed, space).await
+        }
+        [0x8a, 0xc0, 0x0b, 0xdc] => {
+            // assign(bytes32 seed, bytes32 owner)
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            let seed = read_salt(input)?;
+            let owner = read_pubkey(&input[32..])?;
+            assign(context, state, seed, owner).await
+        }
+        _ => Err(Error::UnknownPrecompileMethodSelector(*address, selector)),
+    }
+}
+
+#[inline]
+fn read_u64(input: &[u8]) -> Result<u64> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+    U256::from_be_bytes(*arrayref::array_ref![input, 0, 32])
+        .try_into()
+        .map_err(Into::into)
+}
+
+#[inline]
+fn read_pubkey(input: &[u8]) -> Result<Pubkey> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+    Ok(Pubkey::new_from_array(*arrayref::array_ref![input, 0, 32]))
+}
+
+#[inline]
+fn read_salt(input: &[u8]) -> Result<&[u8; 32]> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+    Ok(arrayref::array_ref![input, 0, 32])
+}
+
+#[inline]
+fn read_string(input: &[u8], offset_position: usize, max_length: usize) -> Result<String> {
+    if input.len() < offset_position + 32 {
+        return Err(Error::OutOfBounds);
+    }
+    let offset: usize =
+        U256::from_be_bytes(*arrayref::array_ref![input, offset_position, 32]).try_into()?;
+    if input.len() < offset.saturating_add(32) {
+        return Err(Error::OutOfBounds);
+    }
+    let length = U256::from_be_bytes(*arrayref::array_ref![input, offset, 32]).try_into()?;
+    if length > max_length {
+        return Err(Error::OutOfBounds);
+    }
+
+    let begin = offset.saturating_add(32);
+    let end = begin.saturating_add(length);
+
+    if input.len() < end {
+        return Err(Error::OutOfBounds);
+    }
+    let data = input[begin..end].to_vec();
+    String::from_utf8(data).map_err(|_| Error::Custom("Invalid utf8 string".to_string()))
+}
+
+/// Lamports holder of the contract. System owned, so it can be a source of `transfer`
+/// and a base of `createAccountWithSeed`. Anyone can fund it with a regular SOL transfer.
+fn payer_address<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &ExecutorState<B>,
+) -> (Pubkey, Vec<Vec<u8>>) {
+    let signer = context.caller;
+
+    let (payer_pubkey, seeds) =
+        state
+            .backend
+            .find_program_address(&[&[ACCOUNT_SEED_VERSION], b"PAYER", signer.as_bytes()]);
+
+    (payer_pubkey, seeds)
+}
+
+fn contract_data_address<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &ExecutorState<B>,
+    seed: &[u8],
+) -> (Pubkey, Vec<Vec<u8>>) {
+    let signer = context.caller;
+
+    let (account_key, seeds) = state.backend.find_program_address(&[
+        &[ACCOUNT_SEED_VERSION],
+        b"ContractData",
+        signer.as_bytes(),
+        seed,
+    ]);
+
+    (account_key, seeds)
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn get_payer<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<B>,
+) -> Result<Vec<u8>> {
+    let (payer_pubkey, _) = payer_address(context, state);
+
+    Ok(payer_pubkey.to_bytes().to_vec())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn find_account<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<B>,
+    seed: &[u8],
+) -> Result<Vec<u8>> {
+    let (account_key, _) = contract_data_address(context, state, seed);
+
+    Ok(account_key.to_bytes().to_vec())
+}
+
+fn get_account_with_seed<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<B>,
+    owner: &Pubkey,
+    seed: &str,
+) -> Result<Vec<u8>> {
+    let (payer_pubkey, _) = payer_address(context, state);
+    let account_key = Pubkey::create_with_seed(&payer_pubkey, seed, owner)
+        .map_err(|e| Error::Custom(format!("SystemProgram: {e}")))?;
+
+    Ok(account_key.to_bytes().to_vec())
+}
+
+#[maybe_async]
+async fn transfer<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    target: Pubkey,
+    lamports: u64,
+) -> Result<Vec<u8>> {
+    let (payer_pubkey, seeds) = payer_address(context, state);
+
+    let payer = state.external_account(payer_pubkey).await?;
+    if payer.lamports < lamports {
+        return Err(Error::Custom(format!(
+            "SystemProgram: insufficient lamports {} < {lamports}",
+            payer.lamports
+        )));
+    }
+
+    let transfer = system_instruction::transfer(&payer_pubkey, &target, lamports);
+    state.queue_external_instruction(transfer, seeds, 0);
+
+    Ok(vec![])
+}
+
+#[maybe_async]
+async fn create_account_with_seed<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    owner: Pubkey,
+    seed: &str,
+    space: u64,
+) -> Result<Vec<u8>> {
+    let (payer_pubkey, seeds) = payer_address(context, state);
+    let account_key = Pubkey::create_with_seed(&payer_pubkey, seed, &owner)
+        .map_err(|e| Error::Custom(format!("SystemProgram: {e}")))?;
+
+    let account = state.external_account(account_key).await?;
+    if !system_program::check_id(&account.owner) {
+        return Err(Error::AccountInvalidOwner(account_key, system_program::ID));
+    }
+
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(space.try_into()?);
+
+    let create_account = system_instruction::create_account_with_seed(
+        state.backend.operator(),
+        &account_key,
+        &payer_pubkey,
+        seed,
+        lamports,
+        space,
+        &owner,
+    );
+    state.queue_external_instruction(create_account, seeds, lamports);
+
+    Ok(account_key.to_bytes().to_vec())
+}
+
+#[maybe_async]
+async fn allocate<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    seed: &[u8],
+    space: u64,
+) -> Result<Vec<u8>> {
+    let (account_key, seeds) = contract_data_address(context, state, seed);
+
+    let account = state.external_account(account_key).await?;
+    if !system_program::check_id(&account.owner) {
+        return Err(Error::AccountInvalidOwner(account_key, system_program::ID));
+    }
+
+    let rent = Rent::get()?;
+    let minimum_balance = rent.minimum_balance(space.try_into()?);
+
+    let required_lamports = minimum_balance.saturating_sub(account.lamports);
+    if required_lamports > 0 {
+        let transfer =
+            system_instruction::transfer(state.backend.operator(), &account_key, required_lamports);
+        state.queue_external_instruction(transfer, vec![], required_lamports);
+    }
+
+    let allocate = system_instruction::allocate(&account_key, space);
+    state.queue_external_instruction(allocate, seeds, 0);
+
+    Ok(account_key.to_bytes().to_vec())
+}
+
+#[maybe_async]
+async fn assign<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    seed: &[u8],
+    owner: Pubkey,
+) -> Result<Vec<u8>> {
+    let (account_key, seeds) = contract_data_address(context, state, seed);
+
+    let account = state.external_account(account_key).await?;
+    if !system_program::check_id(&account.owner) {
+        return Err(Error::AccountInvalidOwner(account_key, system_program::ID));
+    }
+
+    let assign = system_instruction::assign(&account_key, &owner);
+    state.queue_external_instruction(assign, seeds, 0);
+
+    Ok(account_key.to_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account_storage::MemoryAccountStorage, config::CHAIN_ID, evm::Context};
+
+    const SYSTEM_PROGRAM: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x06,
+    ]);
+
+    #[tokio::test]
+    async fn short_input() {
+        let storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        let mut state = ExecutorState::new(&storage);
+        let context = Context {
+            caller: Address::default(),
+            contract: SYSTEM_PROGRAM,
+            value: U256::ZERO,
+            code_address: None,
+        };
+
+        for input in [&[][..], &[0x30, 0xaa, 0x81][..]] {
+            let result = system_program(&mut state, &SYSTEM_PROGRAM, input, &context, false).await;
+            assert!(matches!(result, Err(Error::OutOfBounds)));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::convert::{Into, TryInto};
+
+use ethnum::U256;
+use maybe_async::maybe_async;
+use solana_program::{rent::Rent, sysvar::Sysvar};
+
+use crate::{
+    account_storage::AccountStorage,
+    error::{Error, Result},
+    executor::ExecutorState,
+    types::Address,
+};
+
+// [0x91, 0xdd, 0xad, 0xf4] : "clock()"
+// [0x1a, 0x88, 0xbc, 0x66] : "slot()"
+// [0x90, 0x0c, 0xf0, 0xcf] : "epoch()"
+// [0x15, 0x2c, 0xe7, 0x83] : "epochStartTimestamp()"
+// [0x3f, 0x02, 0xaa, 0xab] : "unixTimestamp()"
+// [0xd0, 0x10, 0xcf, 0x62] : "minimumBalance(uint64)"
+// [0x05, 0x7f, 0xd0, 0x7f] : "slotHash(uint64)"
+
+#[maybe_async]
+pub async fn sysvar<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Address,
+    input: &[u8],
+    context: &crate::evm::Context,
+    _is_static: bool,
+) -> Result<Vec<u8>> {
+    if context.value != 0 {
+        return Err(Error::Custom("Sysvar: value != 0".to_string()));
+    }
+
+    if input.len() < 4 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let (selector, input) = input.split_at(4);
+    let selector: [u8; 4] = selector.try_into()?;
+
+    match selector {
+        [0x91, 0xdd, 0xad, 0xf4] => {
+            // clock()
+            let clock = state.backend.clock().await?;
+
+            let mut result = vec![0_u8; 5 * 32];
+            result[24..32].copy_from_slice(&clock.slot.to_be_bytes());
+            result[32..64].copy_from_slice(&i64_to_abi(clock.epoch_start_timestamp));
+            result[88..96].copy_from_slice(&clock.epoch.to_be_bytes());
+            result[120..128].copy_from_slice(&clock.leader_schedule_epoch.to_be_bytes());
+            result[128..160].copy_from_slice(&i64_to_abi(clock.unix_timestamp));
+
+            Ok(result)
+        }
+        [0x1a, 0x88, 0xbc, 0x66] => {
+            // slot()
+            let clock = state.backend.clock().await?;
+            Ok(U256::from(clock.slot).to_be_bytes().to_vec())
+        }
+        [0x90, 0x0c, 0xf0, 0xcf] => {
+            // epoch()
+            let clock = state.backend.clock().await?;
+            Ok(U256::from(clock.epoch).to_be_bytes().to_vec())
+        }
+        [0x15, 0x2c, 0xe7, 0x83] => {
+            // epochStartTimestamp()
+            let clock = state.backend.clock().await?;
+            Ok(i64_to_abi(clock.epoch_start_timestamp).to_vec())
+        }
+        [0x3f, 0x02, 0xaa, 0xab] => {
+            // unixTimestamp()
+            let clock = state.backend.clock().await?;
+            Ok(i64_to_abi(clock.unix_timestamp).to_vec())
+        }
+        [0xd0, 0x10, 0xcf, 0x62] => {
+            // minimumBalance(uint64 len)
+            let len = read_u64(input)?;
+
+            let rent = Rent::get()?;
+            let lamports = rent.minimum_balance(len.try_into()?);
+
+            Ok(U256::from(lamports).to_be_bytes().to_vec())
+        }
+        [0x05, 0x7f, 0xd0, 0x7f] => {
+            // slotHash(uint64 slot)
+            let slot = read_u64(input)?;
+            let hash = state.backend.block_hash(slot).await;
+
+            Ok(hash.to_vec())
+        }
+        _ => Err(Error::UnknownPrecompileMethodSelector(*address, selector)),
+    }
+}
+
+#[inline]
+fn read_u64(input: &[u8]) -> Result<u64> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+    U256::from_be_bytes(*arrayref::array_ref![input, 0, 32])
+        .try_into()
+        .map_err(Into::into)
+}
+
+/// Sign extended `int64` ABI encoding
+#[inline]
+fn i64_to_abi(value: i64) -> [u8; 32] {
+    let fill = if value < 0 { 0xFF } else { 0x00 };
+
+    let mut result = [fill; 32];
+    result[24..].copy_from_slice(&value.to_be_bytes());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account_storage::MemoryAccountStorage, config::CHAIN_ID, evm::Context};
+    use solana_program::pubkey::Pubkey;
+
+    const SYSVAR: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x07,
+    ]);
+
+    #[tokio::test]
+    async fn clock() {
+        let mut storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        storage.set_block(1000, -5);
+        let mut state = ExecutorState::new(&storage);
+        let context = Context {
+            caller: Address::default(),
+            contract: SYSVAR,
+            value: U256::ZERO,
+            code_address: None,
+        };
+
+        let slot = sysvar(
+            &mut state,
+            &SYSVAR,
+            &[0x1a, 0x88, 0xbc, 0x66],
+            &context,
+            false,
+        )
+        .await;
+        assert_eq!(slot.unwrap(), U256::new(1000).to_be_bytes());
+
+        let time = sysvar(
+            &mut state,
+            &SYSVAR,
+            &[0x3f, 0x02, 0xaa, 0xab],
+            &context,
+            false,
+        )
+        .await;
+        assert_eq!(time.unwrap(), i64_to_abi(-5));
+
+        let short = sysvar(&mut state, &SYSVAR, &[0x1a, 0x88], &context, false).await;
+        assert!(matches!(short, Err(Error::OutOfBounds)));
+    }
+}
@@ -1,5 +1,8 @@
-use std::cell::RefCell;
+#[cfg(target_os = "solana")]
+use std::cell::{Ref, RefCell, RefMut};
 use std::collections::BTreeMap;
+#[cfg(not(target_os = "solana"))]
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use ethnum::{AsU256, U256};
 use maybe_async::maybe_async;
@@ -16,11 +19,17 @@ use super::action::Action;
 use super::cache::Cache;
 use super::OwnedAccountInfo;
 
+/// The program is single-threaded, off-chain state is shared by the emulation threads
+#[cfg(target_os = "solana")]
+type CacheCell = RefCell<Cache>;
+#[cfg(not(target_os = "solana"))]
+type CacheCell = RwLock<Cache>;
+
 /// Represents the state of executor abstracted away from a self.backend.
 /// UPDATE `serialize/deserialize` WHEN THIS STRUCTURE CHANGES
 pub struct ExecutorState<'a, B: AccountStorage> {
     pub backend: &'a B,
-    cache: RefCell<Cache>,
+    cache: CacheCell,
     actions: Vec<Action>,
     stack: Vec<usize>,
     exit_status: Option<ExitStatus>,
@@ -57,16 +66,34 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
 
         Self {
             backend,
-            cache: RefCell::new(cache),
+            cache: CacheCell::new(cache),
             actions: Vec::with_capacity(64),
             stack: Vec::with_capacity(16),
             exit_status: None,
         }
     }
 
-    pub fn into_actions(self) -> Vec<Action> {
-        assert!(self.stack.is_empty());
+    #[cfg(target_os = "solana")]
+    fn cache(&self) -> Ref<'_, Cache> {
+        self.cache.borrow()
+    }
 
+    #[cfg(target_os = "solana")]
+    fn cache_mut(&self) -> RefMut<'_, Cache> {
+        self.cache.borrow_mut()
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    fn cache(&self) -> RwLockReadGuard<'_, Cache> {
+        self.cache.read().expect("executor cache lock is poisoned")
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    fn cache_mut(&self) -> RwLockWriteGuard<'_, Cache> {
+        self.cache.write().expect("executor cache lock is poisoned")
+    }
+
+    pub fn into_actions(self) -> Vec<Action> {
         self.actions
     }
 
@@ -120,28 +147,17 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
             .collect::<Vec<_>>();
 
         if !metas.iter().any(|m| (m.pubkey == address) && m.is_writable) {
-            insert_account_if_not_present(&self.cache, address, self.backend).await;
-            return Ok(self
-                .cache
-                .borrow()
-                .solana_accounts
-                .get(&address)
-                .unwrap()
-                .clone());
+            self.insert_account_if_not_present(address).await;
+            return Ok(self.cache().solana_accounts.get(&address).unwrap().clone());
         }
 
         let mut accounts = BTreeMap::<Pubkey, OwnedAccountInfo>::new();
 
         for m in metas {
-            insert_account_if_not_present(&self.cache, m.pubkey, self.backend).await;
+            self.insert_account_if_not_present(m.pubkey).await;
             accounts.insert(
                 m.pubkey,
-                self.cache
-                    .borrow()
-                    .solana_accounts
-                    .get(&m.pubkey)
-                    .unwrap()
-                    .clone(),
+                self.cache().solana_accounts.get(&m.pubkey).unwrap().clone(),
             );
         }
 
@@ -181,24 +197,19 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
 
         Ok(accounts[&address].clone())
     }
-}
 
-#[maybe_async]
-async fn insert_account_if_not_present<B: AccountStorage>(
-    cache: &RefCell<Cache>,
-    key: Pubkey,
-    backend: &B,
-) {
-    if !cache.borrow().solana_accounts.contains_key(&key) {
-        let owned_account_info = backend.clone_solana_account(&key).await;
-        cache
-            .borrow_mut()
-            .solana_accounts
-            .insert(key, owned_account_info);
+    #[maybe_async]
+    async fn insert_account_if_not_present(&self, key: Pubkey) {
+        if !self.cache().solana_accounts.contains_key(&key) {
+            let owned_account_info = self.backend.clone_solana_account(&key).await;
+            self.cache_mut()
+                .solana_accounts
+                .insert(key, owned_account_info);
+        }
     }
 }
 
-#[maybe_async(?Send)]
+#[maybe_async]
 impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
     fn chain_id(&self) -> U256 {
         let chain_id = self.backend.chain_id();
@@ -384,7 +395,7 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
         }
 
         let number = number.as_u64();
-        let block_slot = self.cache.borrow().block_number.as_u64();
+        let block_slot = self.cache().block_number.as_u64();
         let lower_block_slot = if block_slot < 257 {
             0
         } else {
@@ -399,18 +410,18 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
     }
 
     fn block_number(&self) -> Result<U256> {
-        let cache = self.cache.borrow();
+        let cache = self.cache();
         Ok(cache.block_number)
     }
 
     fn block_timestamp(&self) -> Result<U256> {
-        let cache = self.cache.borrow();
+        let cache = self.cache();
         Ok(cache.block_timestamp)
     }
 
     async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
     where
-        F: FnOnce(&solana_program::account_info::AccountInfo) -> R,
+        F: FnOnce(&solana_program::account_info::AccountInfo) -> R + Send,
     {
         self.backend.map_solana_account(address, action).await
     }
@@ -451,3 +462,33 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_storage::MemoryAccountStorage;
+    use crate::config::CHAIN_ID;
+
+    #[test]
+    fn partial_actions_of_open_frames() {
+        let storage = MemoryAccountStorage::new(Pubkey::new_unique(), CHAIN_ID);
+        let mut state = ExecutorState::new(&storage);
+        let withdraw = |value: u32| Action::NeonWithdraw {
+            source: Address::from([1; 20]),
+            value: U256::from(value),
+        };
+
+        state.withdraw_neons(Address::from([1; 20]), U256::ONE);
+        state.snapshot();
+        state.withdraw_neons(Address::from([1; 20]), U256::new(2));
+        state.snapshot();
+        state.withdraw_neons(Address::from([1; 20]), U256::new(3));
+        assert_eq!(state.call_depth(), 2);
+
+        let actions = state.into_actions();
+        assert_eq!(
+            format!("{actions:?}"),
+            format!("{:?}", vec![withdraw(1), withdraw(2), withdraw(3)])
+        );
+    }
+}
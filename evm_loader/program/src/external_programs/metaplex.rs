@@ -1,22 +1,27 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use mpl_token_metadata::assertions::collection::assert_collection_update_is_valid;
+use mpl_token_metadata::assertions::collection::{
+    assert_collection_update_is_valid, assert_collection_verify_is_valid,
+    assert_has_collection_authority,
+};
 use mpl_token_metadata::assertions::uses::assert_valid_use;
 use mpl_token_metadata::utils::{assert_data_valid, assert_initialized, puff_out_data_fields};
-use solana_program::account_info::IntoAccountInfo;
+use solana_program::account_info::{AccountInfo, IntoAccountInfo};
 use solana_program::instruction::AccountMeta;
 use solana_program::program_option::COption;
 use solana_program::rent::Rent;
 use solana_program::sysvar::Sysvar;
-use spl_token::state::Mint;
+use spl_token::state::{Account, Mint};
 use std::collections::BTreeMap;
 
 use crate::executor::OwnedAccountInfo;
 use mpl_token_metadata::instruction::{
     CreateMasterEditionArgs, CreateMetadataAccountArgsV3, MetadataInstruction,
+    MintNewEditionFromMasterEditionViaTokenArgs, UpdateMetadataAccountArgsV2,
 };
 use mpl_token_metadata::state::{
-    Key, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard, MAX_MASTER_EDITION_LEN,
-    MAX_METADATA_LEN,
+    Collection, CollectionDetails, Edition, EditionMarker, Key, MasterEditionV2, Metadata,
+    TokenMetadataAccount, TokenStandard, Uses, MAX_EDITION_LEN, MAX_EDITION_MARKER_SIZE,
+    MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN,
 };
 use solana_program::{
     entrypoint::ProgramResult, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
@@ -34,6 +39,15 @@ pub fn emulate(
         MetadataInstruction::CreateMasterEditionV3(args) => {
             create_master_edition_v3(meta, accounts, &args)
         }
+        MetadataInstruction::UpdateMetadataAccountV2(args) => {
+            update_metadata_accounts_v2(meta, accounts, &args)
+        }
+        MetadataInstruction::SetAndVerifyCollection => set_and_verify_collection(meta, accounts),
+        MetadataInstruction::VerifyCollection => verify_collection(meta, accounts),
+        MetadataInstruction::MintNewEditionFromMasterEditionViaToken(args) => {
+            mint_new_edition_from_master_edition_via_token(meta, accounts, &args)
+        }
+        MetadataInstruction::BurnNft => burn_nft(meta, accounts),
         _ => Err!(ProgramError::InvalidInstructionData; "Unknown Metaplex instruction"),
     }
 }
@@ -196,3 +210,533 @@ fn create_master_edition_v3(
 
     Ok(())
 }
+
+fn update_metadata_accounts_v2(
+    meta: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+    args: &UpdateMetadataAccountArgsV2,
+) -> ProgramResult {
+    let metadata_account_key = &meta[0].pubkey;
+    let update_authority_key = &meta[1].pubkey;
+
+    let mut metadata: Metadata = {
+        let metadata_info = accounts
+            .get_mut(metadata_account_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&metadata_info)?
+    };
+
+    if (&metadata.update_authority != update_authority_key) || !meta[1].is_signer {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Update Authority is incorrect");
+    }
+
+    if let Some(data) = &args.data {
+        if !metadata.is_mutable {
+            return Err!(ProgramError::InvalidArgument; "Metaplex: Data is immutable");
+        }
+
+        let compatible_data = data.to_v1();
+        assert_data_valid(
+            &compatible_data,
+            update_authority_key,
+            &metadata,
+            false,
+            meta[1].is_signer,
+        )?;
+        metadata.data = compatible_data;
+
+        assert_collection_update_is_valid(false, &metadata.collection, &data.collection)?;
+        metadata.collection = data.collection.clone();
+
+        assert_valid_use(&data.uses, &metadata.uses)?;
+        metadata.uses = data.uses.clone();
+    }
+
+    if let Some(update_authority) = args.update_authority {
+        metadata.update_authority = update_authority;
+    }
+
+    if let Some(primary_sale_happened) = args.primary_sale_happened {
+        if !primary_sale_happened {
+            return Err!(ProgramError::InvalidArgument; "Metaplex: Primary sale can only be flipped to true");
+        }
+        metadata.primary_sale_happened = true;
+    }
+
+    if let Some(is_mutable) = args.is_mutable {
+        if is_mutable && !metadata.is_mutable {
+            return Err!(ProgramError::InvalidArgument; "Metaplex: Is Mutable can only be flipped to false");
+        }
+        metadata.is_mutable = is_mutable;
+    }
+
+    puff_out_data_fields(&mut metadata);
+
+    {
+        let metadata_account = accounts.get_mut(metadata_account_key).unwrap();
+        metadata.serialize(&mut metadata_account.data.as_mut_slice())?;
+    }
+
+    Ok(())
+}
+
+fn set_and_verify_collection(
+    meta: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+) -> ProgramResult {
+    let metadata_account_key = &meta[0].pubkey;
+    // let _collection_authority_key = &meta[1].pubkey;
+    // let _payer_account_key = &meta[2].pubkey;
+    let update_authority_key = &meta[3].pubkey;
+    let collection_mint_key = &meta[4].pubkey;
+    let collection_metadata_key = &meta[5].pubkey;
+    let collection_edition_key = &meta[6].pubkey;
+
+    let mut metadata: Metadata = {
+        let metadata_info = accounts
+            .get_mut(metadata_account_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&metadata_info)?
+    };
+
+    let collection_metadata: Metadata = {
+        let collection_info = accounts
+            .get_mut(collection_metadata_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&collection_info)?
+    };
+
+    if (&metadata.update_authority != update_authority_key)
+        || (metadata.update_authority != collection_metadata.update_authority)
+    {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Update Authority is incorrect");
+    }
+
+    if let Some(collection) = &metadata.collection {
+        if (&collection.key != collection_mint_key) && collection.verified {
+            return Err!(ProgramError::InvalidArgument; "Metaplex: Must unverify before migrating collections");
+        }
+    }
+
+    metadata.collection = Some(Collection {
+        key: *collection_mint_key,
+        verified: true,
+    });
+
+    assert_collection_verify(
+        accounts,
+        &meta[1],
+        collection_mint_key,
+        collection_edition_key,
+        &metadata.collection,
+        &collection_metadata,
+    )?;
+
+    {
+        let metadata_account = accounts.get_mut(metadata_account_key).unwrap();
+        metadata.serialize(&mut metadata_account.data.as_mut_slice())?;
+    }
+
+    Ok(())
+}
+
+fn verify_collection(
+    meta: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+) -> ProgramResult {
+    let metadata_account_key = &meta[0].pubkey;
+    // let _collection_authority_key = &meta[1].pubkey;
+    // let _payer_account_key = &meta[2].pubkey;
+    let collection_mint_key = &meta[3].pubkey;
+    let collection_metadata_key = &meta[4].pubkey;
+    let collection_edition_key = &meta[5].pubkey;
+
+    let mut metadata: Metadata = {
+        let metadata_info = accounts
+            .get_mut(metadata_account_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&metadata_info)?
+    };
+
+    let collection_metadata: Metadata = {
+        let collection_info = accounts
+            .get_mut(collection_metadata_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&collection_info)?
+    };
+
+    assert_collection_verify(
+        accounts,
+        &meta[1],
+        collection_mint_key,
+        collection_edition_key,
+        &metadata.collection,
+        &collection_metadata,
+    )?;
+
+    if let Some(collection) = metadata.collection.as_mut() {
+        collection.verified = true;
+    }
+
+    {
+        let metadata_account = accounts.get_mut(metadata_account_key).unwrap();
+        metadata.serialize(&mut metadata_account.data.as_mut_slice())?;
+    }
+
+    Ok(())
+}
+
+fn assert_collection_verify(
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+    collection_authority: &AccountMeta,
+    collection_mint_key: &Pubkey,
+    collection_edition_key: &Pubkey,
+    member_collection: &Option<Collection>,
+    collection_metadata: &Metadata,
+) -> ProgramResult {
+    if !collection_authority.is_signer {
+        return Err!(ProgramError::MissingRequiredSignature; "Metaplex: Collection Authority is not a signer");
+    }
+
+    // This emulation can only verify non-sized collections
+    if collection_metadata.collection_details.is_some() {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Sized collections are not supported");
+    }
+
+    let accounts_info = accounts
+        .iter_mut()
+        .map(|(key, a)| (*key, a.into_account_info()))
+        .collect::<BTreeMap<Pubkey, AccountInfo>>();
+
+    assert_has_collection_authority(
+        &accounts_info[&collection_authority.pubkey],
+        collection_metadata,
+        collection_mint_key,
+        None,
+    )?;
+
+    assert_collection_verify_is_valid(
+        member_collection,
+        collection_metadata,
+        &accounts_info[collection_mint_key],
+        &accounts_info[collection_edition_key],
+    )?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
+fn mint_new_edition_from_master_edition_via_token(
+    meta: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+    args: &MintNewEditionFromMasterEditionViaTokenArgs,
+) -> ProgramResult {
+    let new_metadata_account_key = &meta[0].pubkey;
+    let new_edition_account_key = &meta[1].pubkey;
+    let master_edition_account_key = &meta[2].pubkey;
+    let mint_key = &meta[3].pubkey;
+    let edition_marker_account_key = &meta[4].pubkey;
+    let mint_authority_key = &meta[5].pubkey;
+    // let _payer_account_key = &meta[6].pubkey;
+    let token_account_owner_key = &meta[7].pubkey;
+    let token_account_key = &meta[8].pubkey;
+    let update_authority_key = &meta[9].pubkey;
+    let master_metadata_account_key = &meta[10].pubkey;
+    // let _token_program_key = &meta[11].pubkey;
+    // let _system_account_key = &meta[12].pubkey;
+
+    let edition = args.edition;
+    if edition == 0 {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Edition override cannot be zero");
+    }
+
+    let master_metadata: Metadata = {
+        let metadata_info = accounts
+            .get_mut(master_metadata_account_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&metadata_info)?
+    };
+
+    let token_account: Account = {
+        let token_info = accounts
+            .get_mut(token_account_key)
+            .unwrap()
+            .into_account_info();
+        assert_initialized(&token_info)?
+    };
+
+    if !meta[7].is_signer || (&token_account.owner != token_account_owner_key) {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Invalid token account owner");
+    }
+
+    if token_account.mint != master_metadata.mint {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Token account mint mismatch");
+    }
+
+    if token_account.amount < 1 {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Not enough tokens to mint a limited edition");
+    }
+
+    if master_metadata.token_standard != Some(TokenStandard::NonFungible) {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Invalid token standard");
+    }
+
+    if !accounts[new_metadata_account_key].data.is_empty()
+        || !accounts[new_edition_account_key].data.is_empty()
+    {
+        return Err!(ProgramError::AccountAlreadyInitialized; "Metaplex: Already initialized");
+    }
+
+    let mut master_edition: MasterEditionV2 = {
+        let edition_info = accounts
+            .get_mut(master_edition_account_key)
+            .unwrap()
+            .into_account_info();
+        MasterEditionV2::from_account_info(&edition_info)?
+    };
+
+    match master_edition.max_supply {
+        Some(max_supply) if edition > max_supply => {
+            return Err!(ProgramError::InvalidArgument; "Metaplex: Edition number greater than max supply");
+        }
+        Some(max_supply) if master_edition.supply >= max_supply => {}
+        _ => master_edition.supply += 1,
+    }
+
+    {
+        let rent = Rent::get()?;
+
+        let marker_account = accounts.get_mut(edition_marker_account_key).unwrap();
+        let mut marker = if marker_account.data.is_empty() {
+            marker_account.data.resize(MAX_EDITION_MARKER_SIZE, 0);
+            marker_account.owner = mpl_token_metadata::ID;
+            marker_account.lamports = marker_account
+                .lamports
+                .max(rent.minimum_balance(MAX_EDITION_MARKER_SIZE));
+
+            EditionMarker::default()
+        } else {
+            EditionMarker::from_account_info(&(&mut *marker_account).into_account_info())?
+        };
+
+        if marker.edition_taken(edition)? {
+            return Err!(ProgramError::AccountAlreadyInitialized; "Metaplex: Edition {edition} is already taken");
+        }
+        marker.insert_edition(edition)?;
+        marker.serialize(&mut marker_account.data.as_mut_slice())?;
+    }
+
+    let mut mint: Mint = {
+        let mint_info = accounts.get_mut(mint_key).unwrap().into_account_info();
+        assert_initialized(&mint_info)?
+    };
+
+    if (mint.mint_authority != COption::Some(*mint_authority_key)) || !meta[5].is_signer {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Invalid mint authority");
+    }
+
+    if mint.supply != 1 {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Editions must have exactly one token");
+    }
+
+    if mint.decimals != 0 {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Edition mint decimals should be zero");
+    }
+
+    {
+        let rent = Rent::get()?;
+
+        let metadata_account = accounts.get_mut(new_metadata_account_key).unwrap();
+        metadata_account.data.resize(MAX_METADATA_LEN, 0);
+        metadata_account.owner = mpl_token_metadata::ID;
+        metadata_account.lamports = metadata_account
+            .lamports
+            .max(rent.minimum_balance(MAX_METADATA_LEN));
+
+        let (_, edition_bump_seed) = mpl_token_metadata::pda::find_master_edition_account(mint_key);
+
+        let mut metadata = Metadata {
+            key: Key::MetadataV1,
+            update_authority: *update_authority_key,
+            mint: *mint_key,
+            data: master_metadata.data,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: Some(edition_bump_seed),
+            token_standard: Some(TokenStandard::NonFungibleEdition),
+            collection: master_metadata.collection,
+            uses: master_metadata.uses.map(|u| Uses {
+                use_method: u.use_method,
+                remaining: u.total,
+                total: u.total,
+            }),
+            collection_details: None,
+            programmable_config: None,
+        };
+        puff_out_data_fields(&mut metadata);
+        metadata.serialize(&mut metadata_account.data.as_mut_slice())?;
+    }
+
+    {
+        let rent = Rent::get()?;
+
+        let edition_account = accounts.get_mut(new_edition_account_key).unwrap();
+        edition_account.data.resize(MAX_EDITION_LEN, 0);
+        edition_account.owner = mpl_token_metadata::ID;
+        edition_account.lamports = edition_account
+            .lamports
+            .max(rent.minimum_balance(MAX_EDITION_LEN));
+
+        let new_edition = Edition {
+            key: Key::EditionV1,
+            parent: *master_edition_account_key,
+            edition,
+        };
+        new_edition.serialize(&mut edition_account.data.as_mut_slice())?;
+    }
+
+    {
+        let master_edition_account = accounts.get_mut(master_edition_account_key).unwrap();
+        master_edition.serialize(&mut master_edition_account.data.as_mut_slice())?;
+    }
+
+    {
+        mint.mint_authority = COption::Some(*new_edition_account_key);
+        if mint.freeze_authority.is_some() {
+            mint.freeze_authority = COption::Some(*new_edition_account_key);
+        }
+
+        let mint_account = accounts.get_mut(mint_key).unwrap();
+        mint.pack_into_slice(&mut mint_account.data);
+    }
+
+    Ok(())
+}
+
+fn burn_nft(
+    meta: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+) -> ProgramResult {
+    let metadata_account_key = &meta[0].pubkey;
+    let owner_key = &meta[1].pubkey;
+    let mint_key = &meta[2].pubkey;
+    let token_account_key = &meta[3].pubkey;
+    let edition_account_key = &meta[4].pubkey;
+    // let _token_program_key = &meta[5].pubkey;
+    let collection_metadata_key = meta.get(6).map(|m| m.pubkey);
+
+    if !meta[1].is_signer {
+        return Err!(ProgramError::MissingRequiredSignature; "Metaplex: Owner is not a signer");
+    }
+
+    let metadata: Metadata = {
+        let metadata_info = accounts
+            .get_mut(metadata_account_key)
+            .unwrap()
+            .into_account_info();
+        Metadata::from_account_info(&metadata_info)?
+    };
+
+    let token_account: Account = {
+        let token_info = accounts
+            .get_mut(token_account_key)
+            .unwrap()
+            .into_account_info();
+        assert_initialized(&token_info)?
+    };
+
+    if &token_account.owner != owner_key {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Invalid token account owner");
+    }
+
+    if (&token_account.mint != mint_key) || (&metadata.mint != mint_key) {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Mint mismatch");
+    }
+
+    if token_account.amount != 1 {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Insufficient token balance");
+    }
+
+    let verified_collection = metadata.collection.as_ref().filter(|c| c.verified);
+    if verified_collection.is_some() && collection_metadata_key.is_none() {
+        return Err!(ProgramError::NotEnoughAccountKeys; "Metaplex: Missing collection metadata account");
+    }
+
+    let master_edition: MasterEditionV2 = {
+        let edition_info = accounts
+            .get_mut(edition_account_key)
+            .unwrap()
+            .into_account_info();
+        MasterEditionV2::from_account_info(&edition_info)?
+    };
+
+    if master_edition.supply > 0 {
+        return Err!(ProgramError::InvalidArgument; "Metaplex: Master Edition has prints");
+    }
+
+    {
+        let mut mint: Mint = {
+            let mint_info = accounts.get_mut(mint_key).unwrap().into_account_info();
+            assert_initialized(&mint_info)?
+        };
+        mint.supply -= 1;
+
+        let mint_account = accounts.get_mut(mint_key).unwrap();
+        mint.pack_into_slice(&mut mint_account.data);
+    }
+
+    close_account(accounts, token_account_key, owner_key);
+    close_account(accounts, metadata_account_key, owner_key);
+    close_account(accounts, edition_account_key, owner_key);
+
+    if let (Some(collection), Some(collection_metadata_key)) =
+        (verified_collection, collection_metadata_key)
+    {
+        let collection_metadata_account = accounts.get_mut(&collection_metadata_key).unwrap();
+        if collection_metadata_account.data.is_empty() {
+            // Collection parent is already burned
+            return Ok(());
+        }
+
+        let mut collection_metadata =
+            Metadata::from_account_info(&collection_metadata_account.into_account_info())?;
+
+        if collection_metadata.mint != collection.key {
+            return Err!(ProgramError::InvalidArgument; "Metaplex: Not a member of collection");
+        }
+
+        if let Some(CollectionDetails::V1 { size }) = collection_metadata.collection_details {
+            collection_metadata.collection_details = Some(CollectionDetails::V1 {
+                size: size.saturating_sub(1),
+            });
+
+            let collection_metadata_account = accounts.get_mut(&collection_metadata_key).unwrap();
+            collection_metadata.serialize(&mut collection_metadata_account.data.as_mut_slice())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn close_account(
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+    account_key: &Pubkey,
+    destination_key: &Pubkey,
+) {
+    let lamports = {
+        let account = accounts.get_mut(account_key).unwrap();
+        account.data.clear();
+        account.owner = solana_program::system_program::ID;
+
+        std::mem::take(&mut account.lamports)
+    };
+
+    let destination = accounts.get_mut(destination_key).unwrap();
+    destination.lamports += lamports;
+}
@@ -46,6 +46,54 @@ pub fn emulate(
                 account.data.resize(space as usize, 0_u8);
             }
         }
+        SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed,
+            lamports,
+            space,
+            owner,
+        } => {
+            let funder_key = &meta[0].pubkey;
+            let account_key = &meta[1].pubkey;
+
+            if !meta[0].is_signer {
+                return Err!(ProgramError::MissingRequiredSignature; "Create Account: funder {} must sign", funder_key);
+            }
+
+            // Base is listed separately only if it differs from the funder
+            let base_is_signer = meta.iter().any(|m| (m.pubkey == base) && m.is_signer);
+            if !base_is_signer {
+                return Err!(ProgramError::MissingRequiredSignature; "Create Account: base {} must sign", base);
+            }
+
+            let address = Pubkey::create_with_seed(&base, &seed, &owner)?;
+            if &address != account_key {
+                return Err!(ProgramError::InvalidArgument; "Create Account: address {} does not match derived address {}", account_key, address);
+            }
+
+            {
+                let funder = accounts.get_mut(funder_key).unwrap();
+                if funder.lamports < lamports {
+                    return Err!(ProgramError::InsufficientFunds; "Insufficient operator lamports");
+                }
+
+                funder.lamports -= lamports;
+            }
+
+            {
+                let account = accounts.get_mut(account_key).unwrap();
+                if (account.lamports > 0)
+                    || !account.data.is_empty()
+                    || !system_program::check_id(&account.owner)
+                {
+                    return Err!(ProgramError::InvalidInstructionData; "Create Account: account already in use");
+                }
+
+                account.lamports = lamports;
+                account.owner = owner;
+                account.data.resize(space as usize, 0_u8);
+            }
+        }
         SystemInstruction::Assign { owner } => {
             let account_key = &meta[0].pubkey;
             let account = accounts.get_mut(account_key).unwrap();
@@ -99,3 +147,72 @@ pub fn emulate(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::system_instruction;
+
+    fn system_account(key: Pubkey, lamports: u64) -> OwnedAccountInfo {
+        OwnedAccountInfo {
+            key,
+            is_signer: false,
+            is_writable: true,
+            lamports,
+            data: vec![],
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn create_with_seed(meta: &[AccountMeta], data: &[u8]) -> (ProgramResult, u64) {
+        let mut accounts = BTreeMap::new();
+        for m in meta {
+            accounts.insert(m.pubkey, system_account(m.pubkey, 0));
+        }
+        accounts.get_mut(&meta[0].pubkey).unwrap().lamports = 1_000;
+
+        let result = emulate(data, meta, &mut accounts);
+        (result, accounts[&meta[1].pubkey].lamports)
+    }
+
+    #[test]
+    fn create_account_with_seed() {
+        let funder = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = Pubkey::create_with_seed(&base, "seed", &owner).unwrap();
+
+        let instruction = system_instruction::create_account_with_seed(
+            &funder, &account, &base, "seed", 100, 10, &owner,
+        );
+
+        let (result, lamports) = create_with_seed(&instruction.accounts, &instruction.data);
+        assert_eq!(result, Ok(()));
+        assert_eq!(lamports, 100);
+    }
+
+    #[test]
+    fn create_account_with_seed_requires_signers() {
+        let funder = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = Pubkey::create_with_seed(&base, "seed", &owner).unwrap();
+
+        let instruction = system_instruction::create_account_with_seed(
+            &funder, &account, &base, "seed", 100, 10, &owner,
+        );
+
+        let mut meta = instruction.accounts.clone();
+        meta[0].is_signer = false;
+        let (result, _) = create_with_seed(&meta, &instruction.data);
+        assert_eq!(result, Err(ProgramError::MissingRequiredSignature));
+
+        let mut meta = instruction.accounts;
+        meta[2].is_signer = false;
+        let (result, lamports) = create_with_seed(&meta, &instruction.data);
+        assert_eq!(result, Err(ProgramError::MissingRequiredSignature));
+        assert_eq!(lamports, 0);
+    }
+}
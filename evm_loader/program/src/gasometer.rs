@@ -1,7 +1,7 @@
 use std::convert::TryInto;
 
 use crate::account::Operator;
-use crate::{config::HOLDER_MSG_SIZE, types::Transaction};
+use crate::types::Transaction;
 use ethnum::U256;
 use solana_program::account_info::AccountInfo;
 use solana_program::program_error::ProgramError;
@@ -56,9 +56,9 @@ impl Gasometer {
             .saturating_add(CANCEL_TRX_COST);
     }
 
-    pub fn record_write_to_holder(&mut self, trx: &Transaction) {
+    pub fn record_write_to_holder(&mut self, trx: &Transaction, holder_msg_size: u64) {
         let size: u64 = trx.rlp_len().try_into().expect("usize is 8 bytes");
-        let cost: u64 = ((size + (HOLDER_MSG_SIZE - 1)) / HOLDER_MSG_SIZE)
+        let cost: u64 = ((size + (holder_msg_size - 1)) / holder_msg_size)
             .saturating_mul(WRITE_TO_HOLDER_TRX_COST);
 
         self.gas = self.gas.saturating_add(cost);
@@ -1,6 +1,6 @@
 use std::collections::BTreeSet;
 
-use crate::account::{EthereumAccount, Operator, State};
+use crate::account::{EthereumAccount, Operator, OperatorRegistry, State};
 use crate::error::{Error, Result};
 use crate::state_account::BlockedAccountMeta;
 use solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT;
@@ -17,7 +17,8 @@ pub fn process<'a>(
     solana_program::msg!("Instruction: Block Accounts");
 
     let mut state = State::from_account(program_id, &accounts[0])?;
-    let operator = Operator::from_account(&accounts[1])?;
+    let (accounts, registry) = OperatorRegistry::split(program_id, accounts)?;
+    let operator = Operator::from_account(&accounts[1], registry.as_ref())?;
 
     if &state.owner != operator.key {
         return Err(Error::HolderInvalidOwner(state.owner, *operator.key));
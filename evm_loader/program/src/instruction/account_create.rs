@@ -5,6 +5,7 @@ use solana_program::{
 };
 
 use crate::account::{program, EthereumAccount, Operator};
+use crate::config::{chain_info, CHAIN_ID};
 use crate::types::Address;
 
 struct Accounts<'a> {
@@ -29,21 +30,32 @@ pub fn process<'a>(
     let address = array_ref![instruction, 0, 20];
     let address = Address::from(*address);
     solana_program::msg!("Address: {}", address);
+    // Optional chain id, the default chain is used if omitted
+    let chain_id = if instruction.len() >= 28 {
+        u64::from_le_bytes(*array_ref![instruction, 20, 8])
+    } else {
+        CHAIN_ID
+    };
+    if chain_info(chain_id).is_none() {
+        return Err!(ProgramError::InvalidInstructionData; "Chain {} is not hosted by the program", chain_id);
+    }
 
-    let bump_seed = validate(program_id, &parsed_accounts, &address)?;
-    execute(program_id, &parsed_accounts, address, bump_seed)
+    let bump_seed = validate(program_id, &parsed_accounts, &address, chain_id)?;
+    execute(program_id, &parsed_accounts, address, chain_id, bump_seed)
 }
 
 fn validate(
     program_id: &Pubkey,
     accounts: &Accounts,
     address: &Address,
+    chain_id: u64,
 ) -> Result<u8, ProgramError> {
     if !solana_program::system_program::check_id(accounts.ether_account.owner) {
         return Err!(ProgramError::InvalidArgument; "Account {} - expected system owned", accounts.ether_account.key);
     }
 
-    let (expected_address, bump_seed) = address.find_solana_address(program_id);
+    let (expected_address, bump_seed) =
+        address.find_solana_address_in_chain(program_id, chain_id, CHAIN_ID);
     if expected_address != *accounts.ether_account.key {
         return Err!(ProgramError::InvalidArgument; "Account {} - expected PDA address {}", accounts.ether_account.key, expected_address);
     }
@@ -55,6 +67,7 @@ fn execute(
     program_id: &Pubkey,
     accounts: &Accounts,
     address: Address,
+    chain_id: u64,
     bump_seed: u8,
 ) -> ProgramResult {
     EthereumAccount::create_and_init_account(
@@ -62,6 +75,7 @@ fn execute(
         program_id,
         &accounts.operator,
         address,
+        chain_id,
         accounts.ether_account,
         bump_seed,
         EthereumAccount::SIZE,
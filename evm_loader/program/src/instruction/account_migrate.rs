@@ -0,0 +1,23 @@
+use crate::account::{program, Operator, OperatorRegistry};
+use crate::error::Result;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    _instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Migrate Accounts");
+
+    let (accounts, registry) = OperatorRegistry::split(program_id, accounts)?;
+    let operator = Operator::from_account(&accounts[0], registry.as_ref())?;
+    let system_program = program::System::from_account(&accounts[1])?;
+
+    for info in &accounts[2..] {
+        if crate::account::migrate(program_id, info, &operator, &system_program)? {
+            solana_program::msg!("Account {} - migrated", info.key);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::account::{EthereumAccount, EthereumStorage, Operator, OperatorRegistry};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Close contract storage accounts which are all-zero or belong to the previous contract generation
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    _instruction: &[u8],
+) -> ProgramResult {
+    solana_program::msg!("Instruction: Collect storage");
+
+    let (accounts, registry) = OperatorRegistry::split(program_id, accounts)?;
+    let operator = Operator::from_account(&accounts[0], registry.as_ref())?;
+
+    let pairs = accounts[1..].chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err!(ProgramError::NotEnoughAccountKeys; "Expected pairs of contract and storage accounts");
+    }
+
+    for pair in pairs {
+        let contract = EthereumAccount::from_account(program_id, &pair[0])?;
+        let storage = EthereumStorage::from_account(program_id, &pair[1])?;
+
+        if storage.address != contract.address {
+            return Err!(ProgramError::InvalidArgument; "Account {} - storage doesn't belong to contract {}", storage.info.key, contract.address);
+        }
+
+        if contract.rw_blocked {
+            solana_program::msg!("Skip storage {} - contract is blocked", storage.info.key);
+            continue;
+        }
+
+        if (storage.generation == contract.generation) && !storage.is_zeroed() {
+            solana_program::msg!("Skip storage {} - not empty", storage.info.key);
+            continue;
+        }
+
+        storage.close(&operator)?;
+    }
+
+    Ok(())
+}
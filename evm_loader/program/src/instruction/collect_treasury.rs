@@ -1,11 +1,11 @@
 use crate::{
     account::{program::System, MainTreasury, Treasury},
-    config::TREASURY_POOL_SEED,
+    config::CHAIN_ID,
 };
 use arrayref::array_ref;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey,
-    rent::Rent, system_instruction, sysvar::Sysvar,
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_instruction, sysvar::Sysvar,
 };
 
 pub fn process<'a>(
@@ -16,28 +16,61 @@ pub fn process<'a>(
     solana_program::msg!("Instruction: Collect treasury");
 
     let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
+    // Optional chain id, pools of the default chain are collected if omitted
+    let chain_id = if instruction.len() >= 12 {
+        u64::from_le_bytes(*array_ref![instruction, 4, 8])
+    } else {
+        CHAIN_ID
+    };
 
     let main_treasury = MainTreasury::from_account(program_id, &accounts[0])?;
-    let treasury = Treasury::from_account(program_id, treasury_index, &accounts[1])?;
+    let treasury =
+        Treasury::from_account_in_chain(program_id, chain_id, treasury_index, &accounts[1])?;
     let system = System::from_account(&accounts[2])?;
 
     let rent = Rent::get()?;
+    collect(
+        &main_treasury,
+        &treasury,
+        &system,
+        &rent,
+        chain_id,
+        treasury_index,
+    )?;
+
+    Ok(())
+}
+
+/// Move lamports above the rent exempt minimum from the treasury pool to the main treasury.
+/// Returns collected amount.
+pub fn collect<'a>(
+    main_treasury: &MainTreasury<'a>,
+    treasury: &Treasury<'a>,
+    system: &System<'a>,
+    rent: &Rent,
+    chain_id: u64,
+    treasury_index: u32,
+) -> Result<u64, ProgramError> {
     let minimal_balance_for_rent_exempt = rent.minimum_balance(treasury.data_len());
     let available_lamports = treasury
         .lamports()
         .saturating_sub(minimal_balance_for_rent_exempt);
 
     if available_lamports > 0 {
+        let mut seeds = Treasury::seeds(chain_id, CHAIN_ID, treasury_index);
+        seeds.push(vec![treasury.get_bump_seed()]);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+
         invoke_signed(
             &system_instruction::transfer(treasury.key, main_treasury.key, available_lamports),
-            &[treasury.clone(), main_treasury.clone(), system.clone()],
-            &[&[
-                TREASURY_POOL_SEED.as_bytes(),
-                &treasury_index.to_le_bytes(),
-                &[treasury.get_bump_seed()],
-            ]],
+            &[
+                (*treasury).clone(),
+                (*main_treasury).clone(),
+                (*system).clone(),
+            ],
+            &[&seeds],
         )?;
     };
 
-    Ok(())
+    Ok(available_lamports)
 }
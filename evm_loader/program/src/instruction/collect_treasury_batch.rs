@@ -0,0 +1,56 @@
+use crate::account::{program::System, MainTreasury, Treasury};
+use crate::error::{Error, Result};
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar};
+
+/// Collect several treasury pools of the chain in a single instruction.
+/// Instruction data: `chain_id: u64 LE`, then `index: u32 LE` for every pool account.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Collect treasury batch");
+
+    let chain_id = u64::from_le_bytes(*array_ref![instruction, 0, 8]);
+    let indices = &instruction[8..];
+    if indices.is_empty() || (indices.len() % 4 != 0) {
+        return Err(Error::Custom(
+            "Collect treasury batch: invalid pool indices".to_string(),
+        ));
+    }
+
+    let main_treasury = MainTreasury::from_account(program_id, &accounts[0])?;
+    let system = System::from_account(&accounts[1])?;
+    let pools = &accounts[2..];
+
+    if pools.len() != indices.len() / 4 {
+        return Err(Error::Custom(format!(
+            "Collect treasury batch: expected {} pool accounts, found {}",
+            indices.len() / 4,
+            pools.len()
+        )));
+    }
+
+    let rent = Rent::get()?;
+
+    let mut total = 0_u64;
+    for (index, info) in indices.chunks_exact(4).zip(pools) {
+        let index = u32::from_le_bytes(*array_ref![index, 0, 4]);
+        let treasury = Treasury::from_account_in_chain(program_id, chain_id, index, info)?;
+
+        let collected = super::collect_treasury::collect(
+            &main_treasury,
+            &treasury,
+            &system,
+            &rent,
+            chain_id,
+            index,
+        )?;
+        total = total.saturating_add(collected);
+    }
+
+    solana_program::msg!("Collected {} lamports", total);
+
+    Ok(())
+}
@@ -37,7 +37,7 @@ impl<'a> Accounts<'a> {
     }
 }
 
-fn get_program_upgrade_authority<'a>(
+pub fn get_program_upgrade_authority<'a>(
     program_id: &'a Pubkey,
     program_data: &'a AccountInfo<'a>,
 ) -> Result<Pubkey, ProgramError> {
@@ -0,0 +1,48 @@
+use crate::account::deposit_rates::Rate;
+use crate::account::{program, DepositRates, Operator};
+use crate::error::Result;
+use arrayref::{array_ref, array_refs};
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Set conversion rate of the SPL token deposit.
+/// Instruction data: `mint: Pubkey`, `chain_id: u64 LE`, `numerator: u64 LE`, `denominator: u64 LE`.
+/// Zero numerator removes the mint from the accepted list and refunds the released rent to the payer.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Set Deposit Rate");
+
+    let (mint, chain_id, numerator, denominator) =
+        array_refs![array_ref![instruction, 0, 56], 32, 8, 8, 8];
+    let rate = Rate {
+        mint: Pubkey::new_from_array(*mint),
+        chain_id: u64::from_le_bytes(*chain_id),
+        numerator: u64::from_le_bytes(*numerator),
+        denominator: u64::from_le_bytes(*denominator),
+    };
+
+    let mut rates = DepositRates::from_pda(program_id, &accounts[0])?;
+    let authority = &accounts[1];
+    let payer = unsafe { Operator::from_account_not_whitelisted(&accounts[2])? };
+    let system_program = program::System::from_account(&accounts[3])?;
+
+    rates.validate_authority(authority)?;
+
+    if rate.numerator == 0 {
+        rates.remove(&rate.mint, rate.chain_id, &payer)?;
+        solana_program::msg!("Mint {} chain {} - removed", rate.mint, rate.chain_id);
+    } else {
+        rates.set(&rate, &payer, &system_program)?;
+        solana_program::msg!(
+            "Mint {} chain {} - rate {}/{}",
+            rate.mint,
+            rate.chain_id,
+            rate.numerator,
+            rate.denominator
+        );
+    }
+
+    Ok(())
+}
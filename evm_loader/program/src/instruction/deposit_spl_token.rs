@@ -0,0 +1,70 @@
+use arrayref::array_ref;
+use ethnum::U256;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::account::DepositRates;
+use crate::config::chain_info;
+use crate::error::{Error, Result};
+use crate::types::Address;
+
+use super::neon_tokens_deposit::{execute, validate, Accounts};
+
+/// Deposit SPL tokens or wrapped SOL as the chain gas token balance.
+/// Instruction data: `address: [u8; 20]`, `chain_id: u64 LE`.
+/// The credited amount is converted at the rate of the deposit rates account.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Deposit SPL Token");
+
+    let parsed_accounts = Accounts::from_slice(accounts)?;
+    let rates = DepositRates::from_pda(program_id, &accounts[6])?;
+
+    let ethereum_address = Address::from(*array_ref![instruction, 0, 20]);
+    let chain_id = u64::from_le_bytes(*array_ref![instruction, 20, 8]);
+    let chain = chain_info(chain_id)
+        .ok_or_else(|| Error::Custom(format!("Chain {chain_id} is not hosted by the program")))?;
+
+    let mint = parsed_accounts.source_mint();
+    let rate = rates
+        .rate(&mint, chain_id)?
+        .ok_or(Error::DepositMintNotSupported(mint, chain_id))?;
+
+    let ethereum_bump_seed = validate(
+        program_id,
+        &parsed_accounts,
+        &ethereum_address,
+        chain,
+        &mint,
+    )?;
+
+    let deposit = rate.deposit_amount(parsed_accounts.delegated_amount(), chain.decimals);
+    if deposit == U256::ZERO {
+        return Err(Error::Custom(format!(
+            "Deposit of {} tokens {} is too small",
+            parsed_accounts.delegated_amount(),
+            mint
+        )));
+    }
+
+    execute(
+        program_id,
+        &parsed_accounts,
+        ethereum_address,
+        ethereum_bump_seed,
+        chain,
+        deposit,
+    )?;
+
+    solana_program::msg!(
+        "Deposit {} tokens {} - credited {} to {}",
+        parsed_accounts.delegated_amount(),
+        mint,
+        deposit,
+        ethereum_address
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::account::managed::{Data, Kind};
+use crate::account::{program, AccountData, Operator};
+use crate::error::{Error, Result};
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Create the authority-managed PDA: operator registry, parameters or deposit rates,
+/// managed by the `authority` key from the instruction data.
+/// Requires signature of the program upgrade authority.
+pub fn process<'a, K: Kind>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Create {}", K::NAME);
+
+    let authority = Pubkey::new_from_array(*array_ref![instruction, 0, 32]);
+
+    let pda_info = &accounts[0];
+    let program_data = &accounts[1];
+    let program_upgrade_auth = &accounts[2];
+    let payer = unsafe { Operator::from_account_not_whitelisted(&accounts[3])? };
+    let system_program = program::System::from_account(&accounts[4])?;
+
+    let upgrade_authority =
+        super::create_main_treasury::get_program_upgrade_authority(program_id, program_data)?;
+    if *program_upgrade_auth.key != upgrade_authority {
+        return Err(K::invalid_authority(
+            *program_upgrade_auth.key,
+            upgrade_authority,
+        ));
+    }
+    if !program_upgrade_auth.is_signer {
+        return Err(Error::AccountNotSigner(*program_upgrade_auth.key));
+    }
+
+    let (expected_key, bump_seed) = AccountData::<Data<K>>::address(program_id);
+    if *pda_info.key != expected_key {
+        return Err(Error::AccountInvalidKey(*pda_info.key, expected_key));
+    }
+
+    system_program.create_pda_account(
+        program_id,
+        &payer,
+        pda_info,
+        &[K::SEED.as_bytes(), &[bump_seed]],
+        AccountData::<Data<K>>::required_size(K::INITIAL_COUNT),
+    )?;
+
+    let data = Data::<K>::new(authority, bump_seed, K::INITIAL_COUNT);
+    AccountData::init(program_id, pda_info, data)?;
+
+    Ok(())
+}
@@ -21,15 +21,18 @@ pub enum EvmInstruction {
     DepositV03,
 
     /// Create Ethereum account V3
+    /// Instruction data: ether address and optional chain id, the default chain if omitted
     /// # Account references
     ///   0. [WRITE, SIGNER] Funding account
     ///   1. [] System Program
     ///   2. [WRITE] New account (program_address(version, ether, bump_seed))
+    ///      or program_address(version, ether, chain_id, bump_seed) for additional chains
     CreateAccountV03,
 
     /// Collect lamports from treasury pool accounts to main pool balance
     ///   0. `[WRITE]` Main treasury balance: PDA["treasury_pool"]
     ///   1. `[WRITE]` Auxiliary treasury balance: PDA["treasury_pool", index.to_le_bytes()]
+    ///      or PDA["treasury_pool", index.to_le_bytes(), chain_id.to_le_bytes()] for additional chains
     ///   2. `[]` System program
     CollectTreasure,
 
@@ -43,21 +46,27 @@ pub enum EvmInstruction {
     HolderWrite,
 
     /// Execute Transaction from Instruction in single iteration
+    /// Created Parameters PDA may be passed as the last account to override compiled defaults
     TransactionExecuteFromInstruction,
 
     /// Execute Transaction from Account in single iteration
+    /// Created Parameters PDA may be passed as the last account to override compiled defaults
     TransactionExecuteFromAccount,
 
     /// Execute Iterative Transaction from Instruction
+    /// Created Operator registry and Parameters PDAs may be passed as the last accounts, in any order
     TransactionStepFromInstruction,
 
     /// Execute Iterative Transaction from Account
+    /// Created Operator registry and Parameters PDAs may be passed as the last accounts, in any order
     TransactionStepFromAccount,
 
     /// Execute Iterative Transaction without ChainId from Account
+    /// Created Operator registry and Parameters PDAs may be passed as the last accounts, in any order
     TransactionStepFromAccountNoChainId,
 
     /// Cancel Transaction
+    /// Created Operator registry and Parameters PDAs may be passed as the last accounts, in any order
     Cancel,
 
     /// CreateMainTreasury
@@ -71,10 +80,133 @@ pub enum EvmInstruction {
     CreateMainTreasury,
 
     /// Block additional accounts
+    /// Created Operator registry PDA may be passed as the last account
     AccountBlockAdd,
 
     /// Modify account's nonce. Only for test environment.
     TestAccountUpdateNonce,
+
+    /// Execute EVM call from another Solana program (CPI) in a single iteration
+    ///   0. `[SIGNER]` Caller authority, PDA of the calling program,
+    ///      mapped to the Neon address keccak(pubkey)[12..]. Pays for the used gas
+    ///   1. `[WRITE,SIGNER]` Payer (must be a system account)
+    ///   2. `[WRITE]` Treasury of the chain passed in the instruction data
+    ///   3. `[WRITE]` Payer's Neon account, receives the gas payment
+    ///   4. `[]` System program
+    ///   5. `[]` Neon program
+    ///   6.. Accounts used by the EVM call
+    ///   Last. `[]` Optional, created Parameters PDA
+    TransactionExecuteFromCpi,
+
+    /// Cancel Transaction abandoned by operators for `CANCEL_TIMEOUT_SLOTS`
+    ///   0. `[WRITE]` State account
+    ///   1. `[WRITE,SIGNER]` Canceller, receives the state deposit (must be a system account)
+    ///   2.. Accounts blocked by the transaction
+    ///   Last. `[]` Optional, created Parameters PDA
+    CancelAbandoned,
+
+    /// Close all-zero and outdated generation contract storage accounts
+    ///   0. `[WRITE,SIGNER]` Operator, receives the rent
+    ///   1.. Pairs of `[]` Contract account and `[WRITE]` Storage account
+    ///   Last. `[]` Optional, created Operator registry PDA
+    CollectStorage,
+
+    /// Upgrade accounts of the previous layouts to the current one
+    ///   0. `[WRITE,SIGNER]` Operator, pays for the additional space
+    ///   1. `[]` System program
+    ///   2.. `[WRITE]` Accounts to migrate
+    ///   Last. `[]` Optional, created Operator registry PDA
+    AccountMigrate,
+
+    /// Execute several independent Transactions from Instruction in single iteration
+    ///   0. `[WRITE,SIGNER]` Operator
+    ///   1. `[WRITE]` Treasury, the index is shared by all chains of the batch
+    ///   2. `[WRITE]` Operator's Neon account, receives the gas payments
+    ///   3. `[]` System program
+    ///   4. `[]` Neon program
+    ///   5.. Accounts used by the transactions
+    ///   Last. `[]` Optional, created Parameters PDA
+    TransactionExecuteBatch,
+
+    /// Create Operator Registry
+    ///   0. `[WRITE]` Operator registry PDA
+    ///   1. `[]` Program data account
+    ///   2. `[SIGNER]` Program upgrade authority
+    ///   3. `[WRITE,SIGNER]` Payer
+    ///   4. `[]` System program
+    OperatorRegistryInit,
+
+    /// Add operator to the registry
+    ///   0. `[WRITE]` Operator registry
+    ///   1. `[SIGNER]` Registry authority
+    ///   2. `[WRITE,SIGNER]` Payer
+    ///   3. `[]` System program
+    OperatorRegistryAdd,
+
+    /// Remove operator from the registry
+    ///   0. `[WRITE]` Operator registry
+    ///   1. `[WRITE,SIGNER]` Registry authority, receives the released rent
+    OperatorRegistryRemove,
+
+    /// Suspend or resume registered operator
+    ///   0. `[WRITE]` Operator registry
+    ///   1. `[SIGNER]` Registry authority
+    OperatorRegistrySuspend,
+
+    /// Create runtime Parameters account
+    ///   0. `[WRITE]` Parameters PDA
+    ///   1. `[]` Program data account
+    ///   2. `[SIGNER]` Program upgrade authority
+    ///   3. `[WRITE,SIGNER]` Payer
+    ///   4. `[]` System program
+    ParametersInit,
+
+    /// Override runtime parameter
+    ///   0. `[WRITE]` Parameters account
+    ///   1. `[SIGNER]` Parameters authority
+    ParametersUpdate,
+
+    /// Collect lamports from several treasury pools of the chain to main pool balance
+    ///   0. `[WRITE]` Main treasury balance: PDA["treasury_pool"]
+    ///   1. `[]` System program
+    ///   2.. `[WRITE]` Auxiliary treasury balances in the order of instruction data indices
+    CollectTreasureBatch,
+
+    /// Create Deposit Rates account
+    ///   0. `[WRITE]` Deposit rates PDA
+    ///   1. `[]` Program data account
+    ///   2. `[SIGNER]` Program upgrade authority
+    ///   3. `[WRITE,SIGNER]` Payer
+    ///   4. `[]` System program
+    DepositRatesInit,
+
+    /// Set or remove conversion rate of the SPL token deposit
+    ///   0. `[WRITE]` Deposit rates PDA
+    ///   1. `[SIGNER]` Deposit rates authority
+    ///   2. `[WRITE,SIGNER]` Payer, receives the released rent on remove
+    ///   3. `[]` System program
+    DepositRatesSet,
+
+    /// Deposit SPL tokens or wrapped SOL as the chain gas token balance
+    ///   0. `[WRITE]` Source token account, tokens delegated to the ethereum account
+    ///   1. `[WRITE]` Pool: ATA of PDA["Deposit"] for the source mint
+    ///   2. `[WRITE]` Ethereum account
+    ///   3. `[]` Token program
+    ///   4. `[WRITE,SIGNER]` Operator, pays for the ethereum account creation
+    ///   5. `[]` System program
+    ///   6. `[]` Deposit rates PDA
+    DepositSplToken,
+
+    /// Execute several independent Transactions from Holder Account in single iteration
+    ///   0. `[]` Holder, stores the batch in the format of `TransactionExecuteBatch` data
+    ///   1. `[WRITE,SIGNER]` Operator
+    ///   2. `[WRITE]` Treasury, the index is shared by all chains of the batch
+    ///   3. `[WRITE]` Operator's Neon account, receives the gas payments
+    ///   4. `[]` System program
+    ///   5. `[]` Neon program
+    ///   6.. Accounts used by the transactions
+    ///   Last. `[]` Optional, created Parameters PDA
+    TransactionExecuteBatchFromAccount,
 }
 
 impl EvmInstruction {
@@ -99,6 +231,22 @@ impl EvmInstruction {
             0x2A => Self::TransactionExecuteFromAccount,       // 42
             0x2B => Self::AccountBlockAdd,                     // 43
             0x2C => Self::TestAccountUpdateNonce,              // 44
+            0x2D => Self::TransactionExecuteFromCpi,           // 45
+            0x2E => Self::CancelAbandoned,                     // 46
+            0x2F => Self::CollectStorage,                      // 47
+            0x30 => Self::AccountMigrate,                      // 48
+            0x31 => Self::TransactionExecuteBatch,             // 49
+            0x32 => Self::OperatorRegistryInit,                // 50
+            0x33 => Self::OperatorRegistryAdd,                 // 51
+            0x34 => Self::OperatorRegistryRemove,              // 52
+            0x35 => Self::OperatorRegistrySuspend,             // 53
+            0x36 => Self::ParametersInit,                      // 54
+            0x37 => Self::ParametersUpdate,                    // 55
+            0x38 => Self::CollectTreasureBatch,                // 56
+            0x39 => Self::DepositRatesInit,                    // 57
+            0x3A => Self::DepositRatesSet,                     // 58
+            0x3B => Self::DepositSplToken,                     // 59
+            0x3C => Self::TransactionExecuteBatchFromAccount,  // 60
 
             _ => return Err(ProgramError::InvalidInstructionData),
         })
@@ -110,12 +258,26 @@ pub mod account_create;
 pub mod account_holder_create;
 pub mod account_holder_delete;
 pub mod account_holder_write;
+pub mod account_migrate;
+pub mod collect_storage;
 pub mod collect_treasury;
+pub mod collect_treasury_batch;
 pub mod create_main_treasury;
+pub mod deposit_rates_set;
+pub mod deposit_spl_token;
+pub mod managed_init;
 pub mod neon_tokens_deposit;
+pub mod operator_registry_add;
+pub mod operator_registry_remove;
+pub mod operator_registry_suspend;
+pub mod parameters_update;
 pub mod transaction_cancel;
+pub mod transaction_cancel_abandoned;
 pub mod transaction_execute;
+pub mod transaction_execute_batch;
+pub mod transaction_execute_batch_from_account;
 pub mod transaction_execute_from_account;
+pub mod transaction_execute_from_cpi;
 pub mod transaction_execute_from_instruction;
 pub mod transaction_step;
 pub mod transaction_step_from_account;
@@ -7,10 +7,11 @@ use solana_program::{
 };
 use spl_associated_token_account::get_associated_token_address;
 
-use crate::account::{program, token, EthereumAccount, Operator, ACCOUNT_SEED_VERSION};
+use crate::account::{program, token, EthereumAccount, Operator};
+use crate::config::{chain_info, ChainInfo, CHAIN_ID};
 use crate::types::Address;
 
-struct Accounts<'a> {
+pub(crate) struct Accounts<'a> {
     source: token::State<'a>,
     pool: token::State<'a>,
     ethereum_account: &'a AccountInfo<'a>,
@@ -32,6 +33,14 @@ impl<'a> Accounts<'a> {
             system_program: program::System::from_account(&accounts[5])?,
         })
     }
+
+    pub fn source_mint(&self) -> Pubkey {
+        self.source.mint
+    }
+
+    pub fn delegated_amount(&self) -> u64 {
+        self.source.delegated_amount
+    }
 }
 
 pub fn process<'a>(
@@ -43,23 +52,48 @@ pub fn process<'a>(
 
     let parsed_accounts = Accounts::from_slice(accounts)?;
     let ethereum_address = Address::from(*array_ref![instruction, 0, 20]);
+    // Optional chain id, the default chain is used if omitted
+    let chain_id = if instruction.len() >= 28 {
+        u64::from_le_bytes(*array_ref![instruction, 20, 8])
+    } else {
+        CHAIN_ID
+    };
+    let chain = chain_info(chain_id).ok_or_else(|| {
+        E!(ProgramError::InvalidInstructionData; "Chain {} is not hosted by the program", chain_id)
+    })?;
+
+    let ethereum_bump_seed = validate(
+        program_id,
+        &parsed_accounts,
+        &ethereum_address,
+        chain,
+        &chain.token_mint,
+    )?;
+
+    let additional_decimals: u32 = (18 - chain.decimals).into();
+    let deposit =
+        U256::from(parsed_accounts.source.delegated_amount) * 10_u128.pow(additional_decimals);
 
-    let ethereum_bump_seed = validate(program_id, &parsed_accounts, &ethereum_address)?;
     execute(
         program_id,
         &parsed_accounts,
         ethereum_address,
         ethereum_bump_seed,
+        chain,
+        deposit,
     )
 }
 
-fn validate(
+/// Validate the deposit of `mint` tokens to the chain balance of `ethereum_address`
+pub(crate) fn validate(
     program_id: &Pubkey,
     accounts: &Accounts,
     ethereum_address: &Address,
+    chain: &ChainInfo,
+    mint: &Pubkey,
 ) -> Result<u8, ProgramError> {
     let (expected_solana_address, ethereum_bump_seed) =
-        ethereum_address.find_solana_address(program_id);
+        ethereum_address.find_solana_address_in_chain(program_id, chain.id, CHAIN_ID);
     if expected_solana_address != *accounts.ethereum_account.key {
         return Err!(
             ProgramError::InvalidArgument;
@@ -69,32 +103,33 @@ fn validate(
         );
     }
 
-    if accounts.source.mint != crate::config::token_mint::id() {
+    if accounts.source.mint != *mint {
         return Err!(
             ProgramError::InvalidArgument;
-            "Account {} - expected Neon Token account",
-            accounts.source.info.key
+            "Account {} - expected token account of mint {}",
+            accounts.source.info.key,
+            mint
         );
     }
 
     let (authority_address, _) = Pubkey::find_program_address(&[AUTHORITY_SEED], program_id);
-    let expected_pool_address =
-        get_associated_token_address(&authority_address, &crate::config::token_mint::id());
+    let expected_pool_address = get_associated_token_address(&authority_address, mint);
 
     if accounts.pool.info.key != &expected_pool_address {
         return Err!(
             ProgramError::InvalidArgument;
-            "Account {} - expected Neon Token Pool {}",
+            "Account {} - expected Token Pool {}",
             accounts.pool.info.key,
             expected_pool_address
         );
     }
 
-    if accounts.pool.mint != crate::config::token_mint::id() {
+    if accounts.pool.mint != *mint {
         return Err!(
             ProgramError::InvalidArgument;
-            "Account {} - expected Neon Token account",
-            accounts.pool.info.key
+            "Account {} - expected token account of mint {}",
+            accounts.pool.info.key,
+            mint
         );
     }
 
@@ -121,17 +156,19 @@ fn validate(
     Ok(ethereum_bump_seed)
 }
 
-fn execute(
+/// Move delegated tokens to the pool and credit `deposit` to the chain balance
+pub(crate) fn execute(
     program_id: &Pubkey,
     accounts: &Accounts,
     ethereum_address: Address,
     ethereum_bump_seed: u8,
+    chain: &ChainInfo,
+    deposit: U256,
 ) -> ProgramResult {
-    let signers_seeds: &[&[&[u8]]] = &[&[
-        &[ACCOUNT_SEED_VERSION],
-        ethereum_address.as_bytes(),
-        &[ethereum_bump_seed],
-    ]];
+    let mut seeds = ethereum_address.solana_address_seeds(chain.id, CHAIN_ID);
+    seeds.push(vec![ethereum_bump_seed]);
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let signers_seeds: &[&[&[u8]]] = &[&seeds];
 
     let instruction = spl_token::instruction::transfer(
         accounts.token_program.key,
@@ -157,14 +194,13 @@ fn execute(
             program_id,
             &accounts.operator,
             ethereum_address,
+            chain.id,
             accounts.ethereum_account,
             ethereum_bump_seed,
             EthereumAccount::SIZE,
         )?;
     }
 
-    let additional_decimals: u32 = (18 - crate::config::token_mint::decimals()).into();
-    let deposit = U256::from(accounts.source.delegated_amount) * 10_u128.pow(additional_decimals);
     let mut ethereum_account =
         EthereumAccount::from_account(program_id, accounts.ethereum_account)?;
     ethereum_account.balance = ethereum_account
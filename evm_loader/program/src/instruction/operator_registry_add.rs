@@ -0,0 +1,26 @@
+use crate::account::{program, Operator, OperatorRegistry};
+use crate::error::Result;
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Add Operator");
+
+    let operator = Pubkey::new_from_array(*array_ref![instruction, 0, 32]);
+
+    let mut registry = OperatorRegistry::from_account(program_id, &accounts[0])?;
+    let authority = &accounts[1];
+    let payer = unsafe { Operator::from_account_not_whitelisted(&accounts[2])? };
+    let system_program = program::System::from_account(&accounts[3])?;
+
+    registry.validate_authority(authority)?;
+    registry.add(&operator, &payer, &system_program)?;
+
+    solana_program::msg!("Operator {} - added", operator);
+
+    Ok(())
+}
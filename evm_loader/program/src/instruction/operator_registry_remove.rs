@@ -0,0 +1,24 @@
+use crate::account::OperatorRegistry;
+use crate::error::Result;
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Remove Operator");
+
+    let operator = Pubkey::new_from_array(*array_ref![instruction, 0, 32]);
+
+    let mut registry = OperatorRegistry::from_account(program_id, &accounts[0])?;
+    let authority = &accounts[1];
+
+    registry.validate_authority(authority)?;
+    registry.remove(&operator, authority)?;
+
+    solana_program::msg!("Operator {} - removed", operator);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::account::operator_registry::OperatorStatus;
+use crate::account::OperatorRegistry;
+use crate::error::Result;
+use arrayref::{array_ref, array_refs};
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Suspend or resume registered operator.
+/// Instruction data: `operator: Pubkey`, `suspend: u8` (0 - resume, otherwise - suspend).
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Suspend Operator");
+
+    let (operator, suspend) = array_refs![array_ref![instruction, 0, 33], 32, 1];
+    let operator = Pubkey::new_from_array(*operator);
+    let status = if suspend[0] == 0 {
+        OperatorStatus::Active
+    } else {
+        OperatorStatus::Suspended
+    };
+
+    let mut registry = OperatorRegistry::from_account(program_id, &accounts[0])?;
+    let authority = &accounts[1];
+
+    registry.validate_authority(authority)?;
+    registry.set_status(&operator, status)?;
+
+    solana_program::msg!("Operator {} - {:?}", operator, status);
+
+    Ok(())
+}
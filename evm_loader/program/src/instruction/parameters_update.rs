@@ -0,0 +1,30 @@
+use crate::account::parameters::NAMES;
+use crate::account::Parameters;
+use crate::error::Result;
+use arrayref::{array_ref, array_refs};
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Override runtime parameter.
+/// Instruction data: `index: u8` in `parameters::NAMES`,
+/// `is_set: u8` (0 - restore compiled default), `value: u64 LE`.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Update Parameters");
+
+    let (index, is_set, value) = array_refs![array_ref![instruction, 0, 10], 1, 1, 8];
+    let index = usize::from(index[0]);
+    let value = (is_set[0] != 0).then_some(u64::from_le_bytes(*value));
+
+    let mut parameters = Parameters::from_account(program_id, &accounts[0])?;
+    let authority = &accounts[1];
+
+    parameters.validate_authority(authority)?;
+    parameters.set(index, value)?;
+
+    solana_program::msg!("Parameter {} - {:?}", NAMES[index], value);
+
+    Ok(())
+}
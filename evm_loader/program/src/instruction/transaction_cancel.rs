@@ -1,4 +1,4 @@
-use crate::account::{EthereumAccount, Incinerator, Operator, State};
+use crate::account::{EthereumAccount, Incinerator, Operator, OperatorRegistry, State};
 use crate::state_account::{BlockedAccounts, Deposit};
 use arrayref::array_ref;
 use ethnum::U256;
@@ -7,10 +7,9 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-struct Accounts<'a> {
-    storage: State<'a>,
-    incinerator: Incinerator<'a>,
-    remaining_accounts: &'a [AccountInfo<'a>],
+pub struct Accounts<'a> {
+    pub storage: State<'a>,
+    pub remaining_accounts: &'a [AccountInfo<'a>],
 }
 
 pub fn process<'a>(
@@ -21,21 +20,26 @@ pub fn process<'a>(
     solana_program::msg!("Instruction: Cancel Transaction");
 
     let storage_info = &accounts[0];
-    let operator = Operator::from_account(&accounts[1])?;
+    let (accounts, registry, parameters) =
+        OperatorRegistry::split_with_parameters(program_id, accounts)?;
+    let operator = Operator::from_account(&accounts[1], registry.as_ref())?;
     let incinerator = Incinerator::from_account(&accounts[2])?;
     let remaining_accounts = &accounts[3..];
 
+    // Transaction started before the state layout upgrade
+    State::migrate_in_place(program_id, storage_info)?;
+
     let (storage, blocked_accounts) = State::restore(
         program_id,
         storage_info,
         &operator,
         remaining_accounts,
         true,
+        parameters.operator_priority_slots,
     )?;
 
     let accounts = Accounts {
         storage,
-        incinerator,
         remaining_accounts,
     };
     let transaction_hash = array_ref![instruction, 0, 32];
@@ -43,10 +47,15 @@ pub fn process<'a>(
     solana_program::log::sol_log_data(&[b"HASH", transaction_hash]);
 
     validate(&accounts, transaction_hash)?;
-    execute(program_id, accounts, &blocked_accounts)
+    execute(
+        program_id,
+        accounts,
+        &blocked_accounts,
+        Deposit::Burn(incinerator),
+    )
 }
 
-fn validate(accounts: &Accounts, transaction_hash: &[u8; 32]) -> ProgramResult {
+pub fn validate(accounts: &Accounts, transaction_hash: &[u8; 32]) -> ProgramResult {
     let storage = &accounts.storage;
 
     if &storage.transaction_hash != transaction_hash {
@@ -56,10 +65,11 @@ fn validate(accounts: &Accounts, transaction_hash: &[u8; 32]) -> ProgramResult {
     Ok(())
 }
 
-fn execute<'a>(
+pub fn execute<'a>(
     program_id: &'a Pubkey,
     accounts: Accounts<'a>,
     blocked_accounts: &BlockedAccounts,
+    deposit: Deposit<'a>,
 ) -> ProgramResult {
     let used_gas = U256::ZERO;
     let total_used_gas = accounts.storage.gas_used;
@@ -76,15 +86,16 @@ fn execute<'a>(
 
         if let Ok(mut ether_account) = EthereumAccount::from_account(program_id, info) {
             ether_account.rw_blocked = false;
-            if ether_account.address == accounts.storage.caller {
+            // Nonce of the caller is counted in the transaction chain
+            if (ether_account.address == accounts.storage.caller)
+                && ether_account.is_in_chain(accounts.storage.chain_id)
+            {
                 ether_account.trx_count += 1;
             }
         }
     }
 
-    accounts
-        .storage
-        .finalize(Deposit::Burn(accounts.incinerator))?;
+    accounts.storage.finalize(deposit)?;
 
     Ok(())
 }
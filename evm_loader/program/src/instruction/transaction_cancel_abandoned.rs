@@ -0,0 +1,49 @@
+use crate::account::{Operator, Parameters, State};
+use crate::instruction::transaction_cancel::{execute, validate, Accounts};
+use crate::state_account::Deposit;
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+/// Cancel transaction which was not progressed by any operator for `CANCEL_TIMEOUT_SLOTS`,
+/// the runtime parameter of the optional parameters account passed last.
+/// Any signer can do it, the state deposit is paid to the canceller as a bounty.
+/// Gas is accounted as in the operator cancel: the completed iterations are charged to the caller
+/// by each iteration, the cancel cost is charged in advance by the first one.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> ProgramResult {
+    solana_program::msg!("Instruction: Cancel Abandoned Transaction");
+
+    let (accounts, parameters) = Parameters::split(program_id, accounts)?;
+    let storage_info = &accounts[0];
+    let canceller = unsafe { Operator::from_account_not_whitelisted(&accounts[1])? };
+    let remaining_accounts = &accounts[2..];
+
+    // Transaction started before the state layout upgrade
+    State::migrate_in_place(program_id, storage_info)?;
+
+    let (storage, blocked_accounts) = State::restore_abandoned(
+        program_id,
+        storage_info,
+        remaining_accounts,
+        parameters.cancel_timeout_slots,
+    )?;
+
+    let accounts = Accounts {
+        storage,
+        remaining_accounts,
+    };
+    let transaction_hash = array_ref![instruction, 0, 32];
+
+    solana_program::log::sol_log_data(&[b"HASH", transaction_hash]);
+
+    validate(&accounts, transaction_hash)?;
+    execute(
+        program_id,
+        accounts,
+        &blocked_accounts,
+        Deposit::ReturnToOperator(canceller),
+    )
+}
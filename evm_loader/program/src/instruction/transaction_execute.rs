@@ -1,9 +1,9 @@
-use crate::account::{program, EthereumAccount, Operator, Treasury};
-use crate::account_storage::{AccountsReadiness, ProgramAccountStorage};
-use crate::config::CHAIN_ID;
+use crate::account::{parameters, program, EthereumAccount, Operator, Treasury};
+use crate::account_storage::{AccountStorage, AccountsReadiness, ProgramAccountStorage};
+use crate::config::CHAINS;
 use crate::error::{Error, Result};
-use crate::evm::Machine;
-use crate::executor::ExecutorState;
+use crate::evm::{ExitStatus, Machine};
+use crate::executor::{Action, ExecutorState};
 use crate::gasometer::Gasometer;
 use crate::instruction::transaction_step::log_return_value;
 use crate::types::{Address, Transaction};
@@ -18,6 +18,18 @@ pub struct Accounts<'a> {
     pub neon_program: program::Neon<'a>,
     pub remaining_accounts: &'a [AccountInfo<'a>],
     pub all_accounts: &'a [AccountInfo<'a>],
+    pub parameters: parameters::Values,
+}
+
+/// Hosted chain the transaction is signed for
+pub fn chain_id(trx: &Transaction) -> Result<u64> {
+    let chain_id = trx.chain_id().ok_or(Error::InvalidChainId(U256::ZERO))?;
+
+    CHAINS
+        .iter()
+        .map(|chain| chain.id)
+        .find(|&id| U256::from(id) == chain_id)
+        .ok_or(Error::InvalidChainId(chain_id))
 }
 
 pub fn validate(
@@ -26,7 +38,7 @@ pub fn validate(
     trx: &Transaction,
     _caller_address: &Address,
 ) -> Result<()> {
-    if trx.chain_id() != Some(CHAIN_ID.into()) {
+    if trx.chain_id() != Some(account_storage.chain_id().into()) {
         return Err(Error::InvalidChainId(trx.chain_id().unwrap_or(U256::ZERO)));
     }
 
@@ -38,30 +50,57 @@ pub fn validate(
 pub fn execute<'a>(
     accounts: Accounts<'a>,
     account_storage: &mut ProgramAccountStorage<'a>,
-    mut gasometer: Gasometer,
+    gasometer: Gasometer,
     trx: &mut Transaction,
     caller_address: Address,
+) -> Result<()> {
+    let (exit_reason, apply_state) = run(account_storage, trx, caller_address)?;
+    complete(
+        accounts,
+        account_storage,
+        gasometer,
+        trx,
+        caller_address,
+        exit_reason,
+        apply_state,
+    )
+}
+
+/// Execute transaction in EVM without touching Solana accounts
+pub fn run(
+    account_storage: &mut ProgramAccountStorage,
+    trx: &mut Transaction,
+    caller_address: Address,
+) -> Result<(ExitStatus, Vec<Action>)> {
+    let mut backend = ExecutorState::new(account_storage);
+
+    let mut evm = Machine::new(trx, caller_address, &mut backend)?;
+    let (result, _) = evm.execute(u64::MAX, &mut backend)?;
+
+    let actions = backend.into_actions();
+
+    Ok((result, actions))
+}
+
+/// Apply the results of `run` and charge the caller for the used gas
+pub fn complete<'a>(
+    accounts: Accounts<'a>,
+    account_storage: &mut ProgramAccountStorage<'a>,
+    mut gasometer: Gasometer,
+    trx: &Transaction,
+    caller_address: Address,
+    exit_reason: ExitStatus,
+    apply_state: Vec<Action>,
 ) -> Result<()> {
     accounts.system_program.transfer(
         &accounts.operator,
         &accounts.treasury,
-        crate::config::PAYMENT_TO_TREASURE,
+        accounts.parameters.payment_to_treasure,
     )?;
 
     let gas_limit = trx.gas_limit();
     let gas_price = trx.gas_price();
 
-    let (exit_reason, apply_state) = {
-        let mut backend = ExecutorState::new(account_storage);
-
-        let mut evm = Machine::new(trx, caller_address, &mut backend)?;
-        let (result, _) = evm.execute(u64::MAX, &mut backend)?;
-
-        let actions = backend.into_actions();
-
-        (result, actions)
-    };
-
     let accounts_readiness = account_storage.apply_state_change(
         &accounts.neon_program,
         &accounts.system_program,
@@ -69,13 +108,14 @@ pub fn execute<'a>(
         apply_state,
     )?;
 
-    assert_eq!(
-        accounts_readiness,
-        AccountsReadiness::Ready,
-        "Deployment of contract which needs more than 10kb of account space needs several \
+    if accounts_readiness != AccountsReadiness::Ready {
+        return Err(Error::Custom(
+            "Deployment of contract which needs more than 10kb of account space needs several \
             transactions for reallocation and cannot be performed in a single instruction. \
-            That's why you have to use iterative transaction for the deployment.",
-    );
+            That's why you have to use iterative transaction for the deployment."
+                .to_string(),
+        ));
+    }
 
     gasometer.record_operator_expenses(&accounts.operator);
     let used_gas = gasometer.used_gas();
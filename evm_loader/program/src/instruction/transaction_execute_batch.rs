@@ -0,0 +1,138 @@
+use crate::account::{parameters, program, EthereumAccount, Operator, Parameters, Treasury};
+use crate::account_storage::{AccountStorage, ProgramAccountStorage};
+use crate::error::{Error, Result};
+use crate::gasometer::Gasometer;
+use crate::instruction::transaction_execute::Accounts;
+use crate::types::Transaction;
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Execute several independent Ethereum transactions in a single Solana transaction.
+/// Instruction data: `treasury_index: u32 LE`, then `len: u32 LE` followed by `len` bytes of RLP
+/// for every transaction.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Execute Transaction Batch from Instruction");
+
+    let (accounts, parameters) = Parameters::split(program_id, accounts)?;
+    let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
+    let messages = Transaction::split_batch(&instruction[4..])?;
+
+    execute(
+        program_id,
+        accounts,
+        parameters,
+        treasury_index,
+        &messages,
+        false,
+    )
+}
+
+/// Execute the batch messages one by one, `accounts` start with the operator.
+///
+/// Transactions may be signed for different hosted chains, the treasury pool index is shared.
+/// A failed transaction is logged with `SKIP` and doesn't affect the others. The caller balance
+/// must cover `gas_limit * gas_price + value` before the execution, so the gas payment can't fail
+/// once the state of the transaction is applied.
+pub fn execute<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    parameters: parameters::Values,
+    treasury_index: u32,
+    messages: &[&[u8]],
+    from_holder: bool,
+) -> Result<()> {
+    let mut executed = 0_usize;
+    for (index, message) in messages.iter().enumerate() {
+        let operator = unsafe { Operator::from_account_not_whitelisted(&accounts[0])? };
+
+        // Signature and ALT costs are paid once for the whole batch
+        let mut gasometer = Gasometer::new(None, &operator)?;
+        if executed == 0 {
+            gasometer.record_solana_transaction_cost();
+            gasometer.record_address_lookup_table(accounts);
+        }
+
+        let result = Transaction::from_rlp(message).and_then(|mut trx| {
+            let chain_id = super::transaction_execute::chain_id(&trx)?;
+
+            let batch_accounts = Accounts {
+                operator,
+                treasury: Treasury::from_account_in_chain(
+                    program_id,
+                    chain_id,
+                    treasury_index,
+                    &accounts[1],
+                )?,
+                operator_ether_account: EthereumAccount::from_account(program_id, &accounts[2])?,
+                system_program: program::System::from_account(&accounts[3])?,
+                neon_program: program::Neon::from_account(program_id, &accounts[4])?,
+                remaining_accounts: &accounts[5..],
+                all_accounts: accounts,
+                parameters,
+            };
+
+            let mut account_storage = ProgramAccountStorage::new_in_chain(
+                program_id,
+                &batch_accounts.operator,
+                Some(&batch_accounts.system_program),
+                batch_accounts.remaining_accounts,
+                chain_id,
+            )?;
+
+            let caller_address = trx.recover_caller_address()?;
+            solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
+
+            if from_holder {
+                gasometer.record_write_to_holder(&trx, parameters.holder_msg_size);
+            }
+
+            super::transaction_execute::validate(
+                &batch_accounts,
+                &account_storage,
+                &trx,
+                &caller_address,
+            )?;
+
+            let upfront_cost = trx
+                .gas_limit()
+                .saturating_mul(trx.gas_price())
+                .saturating_add(trx.value());
+            if account_storage.balance(&caller_address) < upfront_cost {
+                return Err(Error::InsufficientBalance(caller_address, upfront_cost));
+            }
+
+            let (exit_reason, apply_state) =
+                super::transaction_execute::run(&mut account_storage, &mut trx, caller_address)?;
+
+            super::transaction_execute::complete(
+                batch_accounts,
+                &mut account_storage,
+                gasometer,
+                &trx,
+                caller_address,
+                exit_reason,
+                apply_state,
+            )
+        });
+
+        match result {
+            Ok(()) => executed += 1,
+            Err(e) => {
+                solana_program::msg!("Skip transaction {}: {}", index, e);
+                solana_program::log::sol_log_data(&[b"SKIP", &(index as u64).to_le_bytes()]);
+            }
+        }
+    }
+
+    if executed == 0 {
+        return Err(Error::Custom(
+            "No transactions executed in batch".to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::account::{Holder, Operator, Parameters};
+use crate::error::{Error, Result};
+use crate::types::Transaction;
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, keccak, pubkey::Pubkey};
+
+/// Execute several independent Ethereum transactions written to the holder account.
+/// Holder stores the batch in the format of `TransactionExecuteBatch` instruction data:
+/// `len: u32 LE` followed by `len` bytes of RLP for every transaction.
+/// The holder transaction hash is the hash of the whole batch.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Execute Transaction Batch from Account");
+
+    let (accounts, parameters) = Parameters::split(program_id, accounts)?;
+    let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
+
+    let batch = {
+        let holder = Holder::from_account(program_id, &accounts[0])?;
+        let operator = unsafe { Operator::from_account_not_whitelisted(&accounts[1])? };
+        holder.validate_owner(&operator)?;
+
+        let batch = holder.transaction().to_vec();
+        let hash = keccak::hash(&batch).to_bytes();
+        if hash != holder.transaction_hash {
+            return Err(Error::HolderInvalidHash(holder.transaction_hash, hash));
+        }
+
+        batch
+    };
+
+    let messages = Transaction::split_batch(&batch)?;
+
+    super::transaction_execute_batch::execute(
+        program_id,
+        &accounts[1..],
+        parameters,
+        treasury_index,
+        &messages,
+        true,
+    )
+}
@@ -1,4 +1,4 @@
-use crate::account::{program, EthereumAccount, Holder, Operator, Treasury};
+use crate::account::{program, EthereumAccount, Holder, Operator, Parameters, Treasury};
 use crate::account_storage::ProgramAccountStorage;
 use crate::error::Result;
 use crate::gasometer::Gasometer;
@@ -15,39 +15,51 @@ pub fn process<'a>(
 ) -> Result<()> {
     solana_program::msg!("Instruction: Execute Transaction from Account");
 
+    let (accounts, parameters) = Parameters::split(program_id, accounts)?;
     let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
 
     let holder = Holder::from_account(program_id, &accounts[0])?;
+    let operator = unsafe { Operator::from_account_not_whitelisted(&accounts[1])? };
+
+    holder.validate_owner(&operator)?;
+    holder.validate_complete()?;
+    let mut trx = Transaction::from_rlp(&holder.transaction())?;
+    holder.validate_transaction(&trx)?;
+
+    let chain_id = super::transaction_execute::chain_id(&trx)?;
 
     let accounts = Accounts {
-        operator: unsafe { Operator::from_account_not_whitelisted(&accounts[1])? },
-        treasury: Treasury::from_account(program_id, treasury_index, &accounts[2])?,
+        operator,
+        treasury: Treasury::from_account_in_chain(
+            program_id,
+            chain_id,
+            treasury_index,
+            &accounts[2],
+        )?,
         operator_ether_account: EthereumAccount::from_account(program_id, &accounts[3])?,
         system_program: program::System::from_account(&accounts[4])?,
         neon_program: program::Neon::from_account(program_id, &accounts[5])?,
         remaining_accounts: &accounts[6..],
         all_accounts: accounts,
+        parameters,
     };
 
-    holder.validate_owner(&accounts.operator)?;
-    let mut trx = Transaction::from_rlp(&holder.transaction())?;
-    holder.validate_transaction(&trx)?;
-
     let caller_address = trx.recover_caller_address()?;
 
     solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
 
-    let mut account_storage = ProgramAccountStorage::new(
+    let mut account_storage = ProgramAccountStorage::new_in_chain(
         program_id,
         &accounts.operator,
         Some(&accounts.system_program),
         accounts.remaining_accounts,
+        chain_id,
     )?;
 
     let mut gasometer = Gasometer::new(None, &accounts.operator)?;
     gasometer.record_solana_transaction_cost();
     gasometer.record_address_lookup_table(accounts.all_accounts);
-    gasometer.record_write_to_holder(&trx);
+    gasometer.record_write_to_holder(&trx, accounts.parameters.holder_msg_size);
 
     super::transaction_execute::validate(&accounts, &account_storage, &trx, &caller_address)?;
     super::transaction_execute::execute(
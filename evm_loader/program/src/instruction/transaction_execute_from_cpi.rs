@@ -0,0 +1,193 @@
+use crate::account::{program, EthereumAccount, Operator, Parameters, Treasury};
+use crate::account_storage::{AccountStorage, ProgramAccountStorage};
+use crate::config::{chain_info, EVM_STEPS_CPI_MAX};
+use crate::error::{Error, Result};
+use crate::evm::{ExitStatus, Machine};
+use crate::executor::ExecutorState;
+use crate::gasometer::Gasometer;
+use crate::instruction::transaction_execute::Accounts;
+use crate::types::{Address, LegacyTx, Transaction, TransactionPayload};
+use arrayref::{array_ref, array_refs};
+use ethnum::U256;
+use solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use solana_program::program::{set_return_data, MAX_RETURN_DATA};
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Size of the fixed part of the instruction data
+const HEADER_LEN: usize = 4 + 8 + 20 + 32 + 8 + 8 + 32;
+
+/// Execute EVM call requested by another Solana program.
+/// Instruction data: `treasury_index: u32 LE`, `chain_id: u64 LE`, `target: [u8; 20]`, `value: u256 LE`,
+/// `step_limit: u64 LE`, `gas_limit: u64 LE`, `gas_price: u256 LE`, `call_data`.
+/// The caller pays `used_gas * gas_price` to the payer, as the sender of a regular transaction does.
+/// Return data: exit status code followed by the returned bytes.
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    solana_program::msg!("Instruction: Execute Transaction from CPI");
+
+    if get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Err(Error::Custom(
+            "Execute from CPI: must be invoked by another program".to_string(),
+        ));
+    }
+
+    if instruction.len() < HEADER_LEN {
+        return Err(Error::OutOfBounds);
+    }
+
+    let (treasury_index, chain_id, target, value, step_limit, gas_limit, gas_price) = array_refs![
+        array_ref![instruction, 0, HEADER_LEN],
+        4,
+        8,
+        20,
+        32,
+        8,
+        8,
+        32
+    ];
+    let treasury_index = u32::from_le_bytes(*treasury_index);
+    let chain_id = u64::from_le_bytes(*chain_id);
+    let target = Address::from(*target);
+    let value = U256::from_le_bytes(*value);
+    let step_limit = u64::from_le_bytes(*step_limit);
+    let gas_limit = u64::from_le_bytes(*gas_limit);
+    let gas_price = U256::from_le_bytes(*gas_price);
+    let call_data = &instruction[HEADER_LEN..];
+
+    if chain_info(chain_id).is_none() {
+        return Err(Error::InvalidChainId(chain_id.into()));
+    }
+
+    if step_limit > EVM_STEPS_CPI_MAX {
+        return Err(Error::Custom(format!(
+            "Step limit {step_limit} exceeds maximum {EVM_STEPS_CPI_MAX}"
+        )));
+    }
+
+    let (accounts, parameters) = Parameters::split(program_id, accounts)?;
+
+    // Only a program can sign for the address off the curve
+    let caller_authority = &accounts[0];
+    if !caller_authority.is_signer {
+        return Err(Error::AccountNotSigner(*caller_authority.key));
+    }
+    if caller_authority.key.is_on_curve() {
+        return Err(Error::Custom(format!(
+            "Execute from CPI: caller authority {} is not a program derived address",
+            caller_authority.key
+        )));
+    }
+    let caller_address = Address::from_solana_address(caller_authority.key);
+
+    let accounts = Accounts {
+        operator: unsafe { Operator::from_account_not_whitelisted(&accounts[1])? },
+        treasury: Treasury::from_account_in_chain(
+            program_id,
+            chain_id,
+            treasury_index,
+            &accounts[2],
+        )?,
+        operator_ether_account: EthereumAccount::from_account(program_id, &accounts[3])?,
+        system_program: program::System::from_account(&accounts[4])?,
+        neon_program: program::Neon::from_account(program_id, &accounts[5])?,
+        remaining_accounts: &accounts[6..],
+        all_accounts: accounts,
+        parameters,
+    };
+
+    let mut account_storage = ProgramAccountStorage::new_in_chain(
+        program_id,
+        &accounts.operator,
+        Some(&accounts.system_program),
+        accounts.remaining_accounts,
+        chain_id,
+    )?;
+    account_storage.check_for_blocked_accounts()?;
+
+    let mut trx = Transaction {
+        transaction: TransactionPayload::Legacy(LegacyTx {
+            nonce: account_storage.nonce(&caller_address),
+            gas_price,
+            gas_limit: gas_limit.into(),
+            target: Some(target),
+            value,
+            call_data: crate::evm::Buffer::from_slice(call_data),
+            v: U256::default(),
+            r: U256::default(),
+            s: U256::default(),
+            chain_id: Some(chain_id.into()),
+            recovery_id: u8::default(),
+        }),
+        byte_len: usize::default(),
+        hash: <[u8; 32]>::default(),
+        signed_hash: <[u8; 32]>::default(),
+    };
+
+    let mut gasometer = Gasometer::new(None, &accounts.operator)?;
+    gasometer.record_solana_transaction_cost();
+    gasometer.record_address_lookup_table(accounts.all_accounts);
+
+    let (exit_reason, apply_state) = {
+        let mut backend = ExecutorState::new(&mut account_storage);
+
+        let mut evm = Machine::new(&mut trx, caller_address, &mut backend)?;
+        let (result, _) = evm.execute(step_limit, &mut backend)?;
+
+        let actions = backend.into_actions();
+
+        (result, actions)
+    };
+
+    if exit_reason == ExitStatus::StepLimit {
+        return Err(Error::Custom(format!(
+            "CPI call of {target} is not completed in {step_limit} steps"
+        )));
+    }
+
+    let return_data = return_data(&exit_reason)?;
+
+    super::transaction_execute::complete(
+        accounts,
+        &mut account_storage,
+        gasometer,
+        &trx,
+        caller_address,
+        exit_reason,
+        apply_state,
+    )?;
+
+    set_return_data(&return_data);
+
+    Ok(())
+}
+
+fn return_data(status: &ExitStatus) -> Result<Vec<u8>> {
+    let (code, data): (u8, &[u8]) = match status {
+        ExitStatus::Stop => (0x11, &[]),
+        ExitStatus::Return(data) => (0x12, data),
+        ExitStatus::Suicide => (0x13, &[]),
+        ExitStatus::Revert(data) => (0xd0, data),
+        ExitStatus::StepLimit => {
+            return Err(Error::Custom(
+                "Execute from CPI: transaction is not completed".to_string(),
+            ))
+        }
+    };
+
+    // Status code takes the first byte of the return data
+    if data.len() >= MAX_RETURN_DATA {
+        return Err(Error::Custom(format!(
+            "Execute from CPI: return data exceeds {} bytes",
+            MAX_RETURN_DATA - 1
+        )));
+    }
+
+    let mut result = Vec::with_capacity(1 + data.len());
+    result.push(code);
+    result.extend_from_slice(data);
+
+    Ok(result)
+}
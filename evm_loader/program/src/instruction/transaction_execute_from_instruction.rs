@@ -1,4 +1,4 @@
-use crate::account::{program, EthereumAccount, Operator, Treasury};
+use crate::account::{program, EthereumAccount, Operator, Parameters, Treasury};
 use crate::account_storage::ProgramAccountStorage;
 use crate::error::Result;
 use crate::gasometer::Gasometer;
@@ -15,29 +15,39 @@ pub fn process<'a>(
 ) -> Result<()> {
     solana_program::msg!("Instruction: Execute Transaction from Instruction");
 
+    let (accounts, parameters) = Parameters::split(program_id, accounts)?;
     let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
     let messsage = &instruction[4..];
 
+    let mut trx = Transaction::from_rlp(messsage)?;
+    let chain_id = super::transaction_execute::chain_id(&trx)?;
+
     let accounts = Accounts {
         operator: unsafe { Operator::from_account_not_whitelisted(&accounts[0])? },
-        treasury: Treasury::from_account(program_id, treasury_index, &accounts[1])?,
+        treasury: Treasury::from_account_in_chain(
+            program_id,
+            chain_id,
+            treasury_index,
+            &accounts[1],
+        )?,
         operator_ether_account: EthereumAccount::from_account(program_id, &accounts[2])?,
         system_program: program::System::from_account(&accounts[3])?,
         neon_program: program::Neon::from_account(program_id, &accounts[4])?,
         remaining_accounts: &accounts[5..],
         all_accounts: accounts,
+        parameters,
     };
 
-    let mut trx = Transaction::from_rlp(messsage)?;
     let caller_address = trx.recover_caller_address()?;
 
     solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
 
-    let mut account_storage = ProgramAccountStorage::new(
+    let mut account_storage = ProgramAccountStorage::new_in_chain(
         program_id,
         &accounts.operator,
         Some(&accounts.system_program),
         accounts.remaining_accounts,
+        chain_id,
     )?;
 
     let mut gasometer = Gasometer::new(None, &accounts.operator)?;
@@ -1,9 +1,9 @@
 use ethnum::U256;
 use solana_program::account_info::AccountInfo;
 
-use crate::account::{program, EthereumAccount, Operator, State, Treasury};
+use crate::account::{parameters, program, EthereumAccount, Operator, State, Treasury};
 use crate::account_storage::{AccountsReadiness, ProgramAccountStorage};
-use crate::config::{EVM_STEPS_LAST_ITERATION_MAX, EVM_STEPS_MIN, PAYMENT_TO_TREASURE};
+use crate::config::EVM_STEPS_LAST_ITERATION_MAX;
 use crate::error::{Error, Result};
 use crate::evm::{ExitStatus, Machine};
 use crate::executor::{Action, ExecutorState};
@@ -22,6 +22,7 @@ pub struct Accounts<'a> {
     pub neon_program: program::Neon<'a>,
     pub remaining_accounts: &'a [AccountInfo<'a>],
     pub all_accounts: &'a [AccountInfo<'a>],
+    pub parameters: parameters::Values,
 }
 
 pub fn do_begin<'a>(
@@ -54,9 +55,10 @@ pub fn do_continue<'a>(
 ) -> Result<()> {
     debug_print!("do_continue");
 
-    if (step_count < EVM_STEPS_MIN) && (storage.gas_price > 0) {
+    let evm_steps_min = accounts.parameters.evm_steps_min;
+    if (step_count < evm_steps_min) && (storage.gas_price > 0) {
         return Err(Error::Custom(format!(
-            "Step limit {step_count} below minimum {EVM_STEPS_MIN}"
+            "Step limit {step_count} below minimum {evm_steps_min}"
         )));
     }
 
@@ -124,7 +126,7 @@ fn finalize<'a>(
         accounts.system_program.transfer(
             &accounts.operator,
             &accounts.treasury,
-            PAYMENT_TO_TREASURE,
+            accounts.parameters.payment_to_treasure,
         )?;
     }
 
@@ -1,6 +1,8 @@
-use crate::account::{program, EthereumAccount, FinalizedState, Holder, Operator, State, Treasury};
+use crate::account::{
+    program, EthereumAccount, FinalizedState, Holder, Operator, OperatorRegistry, State, Treasury,
+};
 use crate::account_storage::ProgramAccountStorage;
-use crate::config::{CHAIN_ID, GAS_LIMIT_MULTIPLIER_NO_CHAINID};
+use crate::config::CHAIN_ID;
 use crate::error::{Error, Result};
 use crate::gasometer::Gasometer;
 use crate::instruction::transaction_step::{do_begin, do_continue, Accounts};
@@ -19,107 +21,142 @@ pub fn process<'a>(
     let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
     let step_count = u64::from(u32::from_le_bytes(*array_ref![instruction, 4, 4]));
 
-    let holder_or_storage_info = &accounts[0];
-
-    let accounts = Accounts {
-        operator: Operator::from_account(&accounts[1])?,
-        treasury: Treasury::from_account(program_id, treasury_index, &accounts[2])?,
-        operator_ether_account: EthereumAccount::from_account(program_id, &accounts[3])?,
-        system_program: program::System::from_account(&accounts[4])?,
-        neon_program: program::Neon::from_account(program_id, &accounts[5])?,
-        remaining_accounts: &accounts[6..],
-        all_accounts: accounts,
-    };
-
-    let mut account_storage = ProgramAccountStorage::new(
-        program_id,
-        &accounts.operator,
-        Some(&accounts.system_program),
-        accounts.remaining_accounts,
-    )?;
-
-    execute(
-        program_id,
-        holder_or_storage_info,
-        accounts,
-        &mut account_storage,
-        step_count,
-        Some(CHAIN_ID.into()),
-    )
+    execute(program_id, accounts, treasury_index, step_count, true)
 }
 
+/// Transactions without chain id are executed in the default chain
 pub fn execute<'a>(
     program_id: &'a Pubkey,
-    holder_or_storage_info: &'a AccountInfo<'a>,
-    accounts: Accounts<'a>,
-    account_storage: &mut ProgramAccountStorage<'a>,
+    accounts: &'a [AccountInfo<'a>],
+    treasury_index: u32,
     step_count: u64,
-    expected_chain_id: Option<U256>,
+    with_chain_id: bool,
 ) -> Result<()> {
-    match crate::account::tag(program_id, holder_or_storage_info)? {
+    let (accounts, registry, parameters) =
+        OperatorRegistry::split_with_parameters(program_id, accounts)?;
+    let holder_or_storage_info = &accounts[0];
+    let operator = Operator::from_account(&accounts[1], registry.as_ref())?;
+    let system_program = program::System::from_account(&accounts[4])?;
+
+    // Transaction started before the state layout upgrade
+    State::migrate(
+        program_id,
+        holder_or_storage_info,
+        &operator,
+        &system_program,
+    )?;
+
+    let (trx, chain_id) = match crate::account::tag(program_id, holder_or_storage_info)? {
         Holder::TAG => {
-            let mut trx = {
-                let holder = Holder::from_account(program_id, holder_or_storage_info)?;
-                holder.validate_owner(&accounts.operator)?;
+            let holder = Holder::from_account(program_id, holder_or_storage_info)?;
+            holder.validate_owner(&operator)?;
+            holder.validate_complete()?;
 
-                let message = holder.transaction();
-                let trx = Transaction::from_rlp(&message)?;
+            let message = holder.transaction();
+            let trx = Transaction::from_rlp(&message)?;
 
-                holder.validate_transaction(&trx)?;
+            holder.validate_transaction(&trx)?;
 
-                trx
+            let chain_id = if with_chain_id {
+                super::transaction_execute::chain_id(&trx)?
+            } else if let Some(chain_id) = trx.chain_id() {
+                return Err(Error::InvalidChainId(chain_id));
+            } else {
+                CHAIN_ID
             };
 
-            if trx.chain_id() != expected_chain_id {
-                return Err(Error::InvalidChainId(trx.chain_id().unwrap_or(U256::ZERO)));
-            }
-
-            solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
-
-            let caller = trx.recover_caller_address()?;
-            let mut storage =
-                State::new(program_id, holder_or_storage_info, &accounts, caller, &trx)?;
-
-            if expected_chain_id.is_none() {
-                let gas_multiplier = U256::from(GAS_LIMIT_MULTIPLIER_NO_CHAINID);
-                storage.gas_limit = storage.gas_limit.saturating_mul(gas_multiplier);
-            }
-
-            let mut gasometer = Gasometer::new(None, &accounts.operator)?;
-            gasometer.record_solana_transaction_cost();
-            gasometer.record_address_lookup_table(accounts.all_accounts);
-            gasometer.record_iterative_overhead();
-            gasometer.record_write_to_holder(&trx);
-
-            do_begin(
-                accounts,
-                storage,
-                account_storage,
-                gasometer,
-                &mut trx,
-                caller,
-            )
+            (Some(trx), chain_id)
         }
         State::TAG => {
-            let (storage, _blocked_accounts) = State::restore(
-                program_id,
-                holder_or_storage_info,
-                &accounts.operator,
-                accounts.remaining_accounts,
-                false,
-            )?;
+            let chain_id = State::from_account(program_id, holder_or_storage_info)?.chain_id;
+            (None, chain_id)
+        }
+        FinalizedState::TAG => return Err(Error::StorageAccountFinalized),
+        _ => {
+            return Err(Error::AccountInvalidTag(
+                *holder_or_storage_info.key,
+                Holder::TAG,
+            ))
+        }
+    };
 
-            solana_program::log::sol_log_data(&[b"HASH", &storage.transaction_hash]);
+    let accounts = Accounts {
+        operator,
+        treasury: Treasury::from_account_in_chain(
+            program_id,
+            chain_id,
+            treasury_index,
+            &accounts[2],
+        )?,
+        operator_ether_account: EthereumAccount::from_account(program_id, &accounts[3])?,
+        system_program,
+        neon_program: program::Neon::from_account(program_id, &accounts[5])?,
+        remaining_accounts: &accounts[6..],
+        all_accounts: accounts,
+        parameters,
+    };
 
-            let mut gasometer = Gasometer::new(Some(storage.gas_used), &accounts.operator)?;
-            gasometer.record_solana_transaction_cost();
+    let mut account_storage = ProgramAccountStorage::new_in_chain(
+        program_id,
+        &accounts.operator,
+        Some(&accounts.system_program),
+        accounts.remaining_accounts,
+        chain_id,
+    )?;
 
-            do_continue(step_count, accounts, storage, account_storage, gasometer)
+    if let Some(mut trx) = trx {
+        solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
+
+        let caller = trx.recover_caller_address()?;
+        let mut storage = State::new(
+            program_id,
+            holder_or_storage_info,
+            &accounts,
+            caller,
+            &trx,
+            chain_id,
+        )?;
+
+        if !with_chain_id {
+            let gas_multiplier = U256::from(accounts.parameters.gas_limit_multiplier_no_chainid);
+            storage.gas_limit = storage.gas_limit.saturating_mul(gas_multiplier);
         }
-        FinalizedState::TAG => Err(Error::StorageAccountFinalized),
-        _ => Err(Error::AccountInvalidTag(
-            *holder_or_storage_info.key,
-            Holder::TAG,
-        )),
+
+        let mut gasometer = Gasometer::new(None, &accounts.operator)?;
+        gasometer.record_solana_transaction_cost();
+        gasometer.record_address_lookup_table(accounts.all_accounts);
+        gasometer.record_iterative_overhead();
+        gasometer.record_write_to_holder(&trx, accounts.parameters.holder_msg_size);
+
+        do_begin(
+            accounts,
+            storage,
+            &mut account_storage,
+            gasometer,
+            &mut trx,
+            caller,
+        )
+    } else {
+        let (storage, _blocked_accounts) = State::restore(
+            program_id,
+            holder_or_storage_info,
+            &accounts.operator,
+            accounts.remaining_accounts,
+            false,
+            accounts.parameters.operator_priority_slots,
+        )?;
+
+        solana_program::log::sol_log_data(&[b"HASH", &storage.transaction_hash]);
+
+        let mut gasometer = Gasometer::new(Some(storage.gas_used), &accounts.operator)?;
+        gasometer.record_solana_transaction_cost();
+
+        do_continue(
+            step_count,
+            accounts,
+            storage,
+            &mut account_storage,
+            gasometer,
+        )
     }
 }
@@ -1,7 +1,4 @@
-use crate::account::{program, EthereumAccount, Operator, Treasury};
-use crate::account_storage::ProgramAccountStorage;
 use crate::error::Result;
-use crate::instruction::transaction_step::Accounts;
 use arrayref::array_ref;
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 
@@ -15,31 +12,11 @@ pub fn process<'a>(
     let treasury_index = u32::from_le_bytes(*array_ref![instruction, 0, 4]);
     let step_count = u64::from(u32::from_le_bytes(*array_ref![instruction, 4, 4]));
 
-    let holder_or_storage_info = &accounts[0];
-
-    let accounts = Accounts {
-        operator: Operator::from_account(&accounts[1])?,
-        treasury: Treasury::from_account(program_id, treasury_index, &accounts[2])?,
-        operator_ether_account: EthereumAccount::from_account(program_id, &accounts[3])?,
-        system_program: program::System::from_account(&accounts[4])?,
-        neon_program: program::Neon::from_account(program_id, &accounts[5])?,
-        remaining_accounts: &accounts[6..],
-        all_accounts: accounts,
-    };
-
-    let mut account_storage = ProgramAccountStorage::new(
-        program_id,
-        &accounts.operator,
-        Some(&accounts.system_program),
-        accounts.remaining_accounts,
-    )?;
-
     super::transaction_step_from_account::execute(
         program_id,
-        holder_or_storage_info,
         accounts,
-        &mut account_storage,
+        treasury_index,
         step_count,
-        None,
+        false,
     )
 }
@@ -1,5 +1,8 @@
-use crate::account::{program, EthereumAccount, FinalizedState, Holder, Operator, State, Treasury};
+use crate::account::{
+    program, EthereumAccount, FinalizedState, Holder, Operator, OperatorRegistry, State, Treasury,
+};
 use crate::account_storage::ProgramAccountStorage;
+use crate::config::CHAIN_ID;
 use crate::error::{Error, Result};
 use crate::gasometer::Gasometer;
 use crate::instruction::transaction_step::{do_begin, do_continue, Accounts};
@@ -19,69 +22,97 @@ pub fn process<'a>(
     // skip let unique_index = u32::from_le_bytes(*array_ref![instruction, 8, 4]);
     let message = &instruction[4 + 4 + 4..];
 
+    let (accounts, registry, parameters) =
+        OperatorRegistry::split_with_parameters(program_id, accounts)?;
     let storage_info = &accounts[0];
+    let operator = Operator::from_account(&accounts[1], registry.as_ref())?;
+    let system_program = program::System::from_account(&accounts[4])?;
+
+    // Transaction started before the state layout upgrade
+    State::migrate(program_id, storage_info, &operator, &system_program)?;
+
+    let (trx, chain_id) = match crate::account::tag(program_id, storage_info)? {
+        Holder::TAG | FinalizedState::TAG => {
+            let trx = Transaction::from_rlp(message)?;
+            // Transaction without chain id is executed in the default chain
+            let chain_id = if trx.chain_id().is_some() {
+                super::transaction_execute::chain_id(&trx)?
+            } else {
+                CHAIN_ID
+            };
+            (Some(trx), chain_id)
+        }
+        State::TAG => (
+            None,
+            State::from_account(program_id, storage_info)?.chain_id,
+        ),
+        _ => return Err(Error::AccountInvalidTag(*storage_info.key, Holder::TAG)),
+    };
 
     let accounts = Accounts {
-        operator: Operator::from_account(&accounts[1])?,
-        treasury: Treasury::from_account(program_id, treasury_index, &accounts[2])?,
+        operator,
+        treasury: Treasury::from_account_in_chain(
+            program_id,
+            chain_id,
+            treasury_index,
+            &accounts[2],
+        )?,
         operator_ether_account: EthereumAccount::from_account(program_id, &accounts[3])?,
-        system_program: program::System::from_account(&accounts[4])?,
+        system_program,
         neon_program: program::Neon::from_account(program_id, &accounts[5])?,
         remaining_accounts: &accounts[6..],
         all_accounts: accounts,
+        parameters,
     };
 
-    let mut account_storage = ProgramAccountStorage::new(
+    let mut account_storage = ProgramAccountStorage::new_in_chain(
         program_id,
         &accounts.operator,
         Some(&accounts.system_program),
         accounts.remaining_accounts,
+        chain_id,
     )?;
 
-    match crate::account::tag(program_id, storage_info)? {
-        Holder::TAG | FinalizedState::TAG => {
-            let mut trx = Transaction::from_rlp(message)?;
-            let caller = trx.recover_caller_address()?;
+    if let Some(mut trx) = trx {
+        let caller = trx.recover_caller_address()?;
 
-            solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
+        solana_program::log::sol_log_data(&[b"HASH", &trx.hash()]);
 
-            let storage = State::new(program_id, storage_info, &accounts, caller, &trx)?;
+        let storage = State::new(program_id, storage_info, &accounts, caller, &trx, chain_id)?;
 
-            let mut gasometer = Gasometer::new(None, &accounts.operator)?;
-            gasometer.record_solana_transaction_cost();
-            gasometer.record_address_lookup_table(accounts.all_accounts);
-            gasometer.record_iterative_overhead();
+        let mut gasometer = Gasometer::new(None, &accounts.operator)?;
+        gasometer.record_solana_transaction_cost();
+        gasometer.record_address_lookup_table(accounts.all_accounts);
+        gasometer.record_iterative_overhead();
 
-            do_begin(
-                accounts,
-                storage,
-                &mut account_storage,
-                gasometer,
-                &mut trx,
-                caller,
-            )
-        }
-        State::TAG => {
-            let (storage, _blocked_accounts) = State::restore(
-                program_id,
-                storage_info,
-                &accounts.operator,
-                accounts.remaining_accounts,
-                false,
-            )?;
-            solana_program::log::sol_log_data(&[b"HASH", &storage.transaction_hash]);
+        do_begin(
+            accounts,
+            storage,
+            &mut account_storage,
+            gasometer,
+            &mut trx,
+            caller,
+        )
+    } else {
+        let (storage, _blocked_accounts) = State::restore(
+            program_id,
+            storage_info,
+            &accounts.operator,
+            accounts.remaining_accounts,
+            false,
+            accounts.parameters.operator_priority_slots,
+        )?;
+        solana_program::log::sol_log_data(&[b"HASH", &storage.transaction_hash]);
 
-            let mut gasometer = Gasometer::new(Some(storage.gas_used), &accounts.operator)?;
-            gasometer.record_solana_transaction_cost();
+        let mut gasometer = Gasometer::new(Some(storage.gas_used), &accounts.operator)?;
+        gasometer.record_solana_transaction_cost();
 
-            do_continue(
-                step_count,
-                accounts,
-                storage,
-                &mut account_storage,
-                gasometer,
-            )
-        }
-        _ => Err(Error::AccountInvalidTag(*storage_info.key, Holder::TAG)),
+        do_continue(
+            step_count,
+            accounts,
+            storage,
+            &mut account_storage,
+            gasometer,
+        )
     }
 }
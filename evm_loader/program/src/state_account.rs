@@ -8,7 +8,6 @@ use {
 use {
     crate::account::EthereumAccount,
     crate::account::Holder,
-    crate::config::OPERATOR_PRIORITY_SLOTS,
     crate::error::Error,
     solana_program::account_info::AccountInfo,
     solana_program::clock::Clock,
@@ -49,6 +48,7 @@ impl<'a> State<'a> {
         accounts: &crate::instruction::transaction_step::Accounts<'a>,
         caller: Address,
         trx: &Transaction,
+        chain_id: u64,
     ) -> Result<Self, ProgramError> {
         let owner = match crate::account::tag(program_id, info)? {
             Holder::TAG => {
@@ -72,6 +72,7 @@ impl<'a> State<'a> {
             return Err!(ProgramError::InvalidAccountData; "Account {} - invalid state account owner", info.key);
         }
 
+        let clock = Clock::get()?;
         let data = crate::account::state::Data {
             owner,
             transaction_hash: trx.hash(),
@@ -80,7 +81,9 @@ impl<'a> State<'a> {
             gas_price: trx.gas_price(),
             gas_used: U256::ZERO,
             operator: *accounts.operator.key,
-            slot: Clock::get()?.slot,
+            slot: clock.slot,
+            last_progress_slot: clock.slot,
+            chain_id,
             accounts_len: accounts.remaining_accounts.len(),
             evm_state_len: 0,
             evm_machine_len: 0,
@@ -100,22 +103,14 @@ impl<'a> State<'a> {
         operator: &Operator,
         remaining_accounts: &[AccountInfo],
         is_cancelling: bool,
+        operator_priority_slots: u64,
     ) -> Result<(Self, BlockedAccounts), ProgramError> {
-        let account_tag = crate::account::tag(program_id, info)?;
-        if account_tag == FinalizedState::TAG {
-            return Err!(Error::StorageAccountFinalized.into(); "Account {} - Storage Finalized", info.key);
-        }
-        if account_tag == Holder::TAG {
-            return Err!(Error::StorageAccountUninitialized.into(); "Account {} - Storage Uninitialized", info.key);
-        }
-
-        let mut storage = State::from_account(program_id, info)?;
-        let blocked_accounts =
-            storage.check_blocked_accounts(program_id, remaining_accounts, is_cancelling)?;
+        let (mut storage, blocked_accounts) =
+            Self::load(program_id, info, remaining_accounts, is_cancelling)?;
 
         let clock = Clock::get()?;
         if (*operator.key != storage.operator)
-            && ((clock.slot - storage.slot) <= OPERATOR_PRIORITY_SLOTS)
+            && ((clock.slot - storage.slot) <= operator_priority_slots)
         {
             return Err!(ProgramError::InvalidAccountData; "operator.key != storage.operator");
         }
@@ -124,6 +119,62 @@ impl<'a> State<'a> {
             storage.operator = *operator.key;
             storage.slot = clock.slot;
         }
+        storage.last_progress_slot = clock.slot;
+
+        Ok((storage, blocked_accounts))
+    }
+
+    /// Restore state which was not progressed by any operator for `cancel_timeout_slots`
+    pub fn restore_abandoned(
+        program_id: &Pubkey,
+        info: &'a AccountInfo<'a>,
+        remaining_accounts: &[AccountInfo],
+        cancel_timeout_slots: u64,
+    ) -> Result<(Self, BlockedAccounts), ProgramError> {
+        let (storage, blocked_accounts) = Self::load(program_id, info, remaining_accounts, true)?;
+
+        let clock = Clock::get()?;
+        if (clock.slot - storage.last_progress_slot) <= cancel_timeout_slots {
+            return Err!(
+                ProgramError::InvalidAccountData;
+                "Account {} - transaction is not abandoned, last progress at slot {}, current slot {}",
+                info.key, storage.last_progress_slot, clock.slot
+            );
+        }
+
+        Ok((storage, blocked_accounts))
+    }
+
+    /// Upgrade the state of the previous layout for the cancel instructions, which have no system program
+    /// to top up the rent. Blocked accounts and the EVM data are preserved within the account size.
+    pub fn migrate_in_place(
+        program_id: &Pubkey,
+        info: &'a AccountInfo<'a>,
+    ) -> Result<bool, ProgramError> {
+        let migrated = Self::migrate_within(program_id, info, |data| {
+            data.accounts_len * ACCOUNT_CHUNK_LEN + data.evm_state_len + data.evm_machine_len
+        })?;
+
+        Ok(migrated)
+    }
+
+    fn load(
+        program_id: &Pubkey,
+        info: &'a AccountInfo<'a>,
+        remaining_accounts: &[AccountInfo],
+        is_cancelling: bool,
+    ) -> Result<(Self, BlockedAccounts), ProgramError> {
+        let account_tag = crate::account::tag(program_id, info)?;
+        if account_tag == FinalizedState::TAG {
+            return Err!(Error::StorageAccountFinalized.into(); "Account {} - Storage Finalized", info.key);
+        }
+        if account_tag == Holder::TAG {
+            return Err!(Error::StorageAccountUninitialized.into(); "Account {} - Storage Uninitialized", info.key);
+        }
+
+        let storage = State::from_account(program_id, info)?;
+        let blocked_accounts =
+            storage.check_blocked_accounts(program_id, remaining_accounts, is_cancelling)?;
 
         Ok((storage, blocked_accounts))
     }
@@ -282,6 +333,13 @@ impl<'a> State<'a> {
         Ok(blocked_accounts)
     }
 
+    /// Space available for the serialized EVM state in the holder account of `holder_size` bytes
+    /// with `accounts_len` blocked accounts
+    #[must_use]
+    pub fn evm_data_capacity(holder_size: usize, accounts_len: usize) -> usize {
+        holder_size.saturating_sub(Self::SIZE + accounts_len * ACCOUNT_CHUNK_LEN)
+    }
+
     #[must_use]
     pub fn evm_data(&self) -> Ref<[u8]> {
         let (begin, end) = self.evm_data_region();
@@ -323,3 +381,129 @@ impl<'a> State<'a> {
             && (info.data.borrow()[0] == EthereumAccount::TAG)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{state, Packable};
+    use crate::types::Address;
+    use ethnum::U256;
+
+    const TAG_STATE_V4: u8 = state::Data::DEPRECATED[0].0;
+
+    /// Account of the previous state layout with two blocked accounts,
+    /// the EVM data and `free` unused bytes
+    fn previous_state(blocked: &[Pubkey; 2], free: usize) -> Vec<u8> {
+        let data = state::Data {
+            owner: Pubkey::new_unique(),
+            transaction_hash: [1; 32],
+            caller: Address::from([2; 20]),
+            gas_limit: U256::new(3),
+            gas_price: U256::new(4),
+            gas_used: U256::new(5),
+            operator: Pubkey::new_unique(),
+            slot: 6,
+            last_progress_slot: 0,
+            chain_id: 0,
+            accounts_len: 2,
+            evm_state_len: 3,
+            evm_machine_len: 4,
+        };
+
+        let mut header = [0_u8; state::Data::SIZE];
+        data.pack(&mut header);
+
+        let mut account = vec![TAG_STATE_V4];
+        account.extend_from_slice(&header[..212 + 8]);
+        account.extend_from_slice(&header[212 + 24..]);
+        for key in blocked {
+            account.extend_from_slice(&[1, 1]);
+            account.extend_from_slice(key.as_ref());
+        }
+        account.extend_from_slice(&[7; 3 + 4]);
+        account.resize(account.len() + free, 0);
+
+        account
+    }
+
+    #[test]
+    fn migrate_in_place() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let blocked = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut lamports = 0;
+        let mut data = previous_state(&blocked, 16);
+        let data_len = data.len();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        assert!(State::migrate_in_place(&program_id, &info).unwrap());
+        assert_eq!(info.data_len(), data_len);
+
+        let storage = State::from_account(&program_id, &info).unwrap();
+        assert_eq!(storage.slot, 6);
+        assert_eq!(storage.last_progress_slot, 6);
+        assert_eq!(storage.chain_id, crate::config::CHAIN_ID);
+
+        let keys: Vec<Pubkey> = storage
+            .read_blocked_accounts()
+            .unwrap()
+            .iter()
+            .map(|meta| meta.key)
+            .collect();
+        assert_eq!(keys, blocked);
+        assert_eq!(storage.evm_data()[..3 + 4], [7; 3 + 4]);
+        drop(storage);
+
+        // Current layout is left as is
+        assert!(!State::migrate_in_place(&program_id, &info).unwrap());
+    }
+
+    #[test]
+    fn migrate_in_place_without_space() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = previous_state(&[Pubkey::new_unique(), Pubkey::new_unique()], 15);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        assert!(matches!(
+            State::migrate_in_place(&program_id, &info),
+            Err(ProgramError::Custom(_))
+        ));
+        assert_eq!(info.data.borrow()[0], TAG_STATE_V4);
+    }
+
+    #[test]
+    fn evm_data_capacity() {
+        let accounts_region = 2 * ACCOUNT_CHUNK_LEN;
+
+        assert_eq!(
+            State::evm_data_capacity(State::SIZE + accounts_region + 100, 2),
+            100
+        );
+        assert_eq!(State::evm_data_capacity(State::SIZE + 100, 0), 100);
+        assert_eq!(
+            State::evm_data_capacity(State::SIZE + accounts_region, 2),
+            0
+        );
+        assert_eq!(State::evm_data_capacity(State::SIZE, 2), 0);
+    }
+}
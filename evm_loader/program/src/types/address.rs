@@ -43,6 +43,17 @@ impl Address {
         Self(*bytes)
     }
 
+    /// Neon address of a Solana account calling the program through CPI
+    #[must_use]
+    pub fn from_solana_address(pubkey: &Pubkey) -> Self {
+        use solana_program::keccak::{hash, Hash};
+
+        let Hash(hash) = hash(pubkey.as_ref());
+
+        let bytes = arrayref::array_ref![hash, 12, 20];
+        Self(*bytes)
+    }
+
     pub fn from_hex(mut s: &str) -> Result<Self, Error> {
         if s.starts_with("0x") {
             s = &s[2..];
@@ -57,6 +68,35 @@ impl Address {
         let seeds: &[&[u8]] = &[&[ACCOUNT_SEED_VERSION], &self.0];
         Pubkey::find_program_address(seeds, program_id)
     }
+
+    /// Account PDA in the chain balance namespace.
+    /// The default chain keeps the legacy seeds, other chains add chain id to them.
+    #[must_use]
+    pub fn find_solana_address_in_chain(
+        &self,
+        program_id: &Pubkey,
+        chain_id: u64,
+        default_chain_id: u64,
+    ) -> (Pubkey, u8) {
+        if chain_id == default_chain_id {
+            return self.find_solana_address(program_id);
+        }
+
+        let seeds = self.solana_address_seeds(chain_id, default_chain_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        Pubkey::find_program_address(&seeds, program_id)
+    }
+
+    /// Account PDA seeds in the chain balance namespace, without bump seed
+    #[must_use]
+    pub fn solana_address_seeds(&self, chain_id: u64, default_chain_id: u64) -> Vec<Vec<u8>> {
+        let mut seeds = vec![vec![ACCOUNT_SEED_VERSION], self.0.to_vec()];
+        if chain_id != default_chain_id {
+            seeds.push(chain_id.to_le_bytes().to_vec());
+        }
+
+        seeds
+    }
 }
 
 impl FromStr for Address {
@@ -168,3 +208,46 @@ impl<'de> Deserialize<'de> for Address {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solana_address_in_chain() {
+        let program_id = Pubkey::new_unique();
+        let address = Address::from([7; 20]);
+
+        assert_eq!(
+            address.find_solana_address_in_chain(&program_id, 111, 111),
+            address.find_solana_address(&program_id)
+        );
+        assert_eq!(
+            address.solana_address_seeds(111, 111),
+            vec![vec![ACCOUNT_SEED_VERSION], vec![7; 20]]
+        );
+
+        let (key, bump_seed) = address.find_solana_address_in_chain(&program_id, 112, 111);
+        assert_ne!(key, address.find_solana_address(&program_id).0);
+
+        let mut seeds = address.solana_address_seeds(112, 111);
+        assert_eq!(seeds[2], 112_u64.to_le_bytes().to_vec());
+        seeds.push(vec![bump_seed]);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            Pubkey::create_program_address(&seeds, &program_id).unwrap(),
+            key
+        );
+    }
+
+    #[test]
+    fn solana_caller_address() {
+        let pubkey = Pubkey::new_unique();
+        let hash = solana_program::keccak::hash(pubkey.as_ref()).to_bytes();
+
+        let address = Address::from_solana_address(&pubkey);
+        assert_eq!(address.as_bytes(), &hash[12..]);
+        assert_eq!(address, Address::from_solana_address(&pubkey));
+        assert_ne!(address, Address::from_solana_address(&Pubkey::new_unique()));
+    }
+}
@@ -577,6 +577,33 @@ impl Transaction {
             TransactionPayload::Legacy(_) => None,
         }
     }
+
+    /// Split the batch of transactions: `len: u32 LE` followed by `len` bytes of RLP for every transaction
+    pub fn split_batch(mut data: &[u8]) -> Result<Vec<&[u8]>, Error> {
+        let mut messages = Vec::new();
+
+        while !data.is_empty() {
+            if data.len() < 4 {
+                return Err(Error::OutOfBounds);
+            }
+
+            let (len, rest) = data.split_at(4);
+            let len: usize = u32::from_le_bytes(len.try_into()?).try_into()?;
+            if rest.len() < len {
+                return Err(Error::OutOfBounds);
+            }
+
+            let (message, rest) = rest.split_at(len);
+            messages.push(message);
+            data = rest;
+        }
+
+        if messages.is_empty() {
+            return Err(Error::Custom("Empty transaction batch".to_string()));
+        }
+
+        Ok(messages)
+    }
 }
 
 #[inline]
@@ -593,3 +620,30 @@ fn u256(rlp: &rlp::Rlp) -> Result<U256, rlp::DecoderError> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_batch() {
+        let mut batch = Vec::new();
+        for message in [&b"first"[..], b"", b"third"] {
+            batch.extend_from_slice(&u32::try_from(message.len()).unwrap().to_le_bytes());
+            batch.extend_from_slice(message);
+        }
+
+        let messages = Transaction::split_batch(&batch).unwrap();
+        assert_eq!(messages, vec![&b"first"[..], b"", b"third"]);
+
+        assert!(matches!(
+            Transaction::split_batch(&batch[..batch.len() - 1]),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(matches!(
+            Transaction::split_batch(&[1, 0]),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(Transaction::split_batch(&[]).is_err());
+    }
+}
@@ -5,11 +5,19 @@ pragma abicoder v2;
 
 interface Metaplex {
     function createMetadata(bytes32 _mint, string memory _name, string memory _symbol, string memory _uri) external returns(bytes32);
+    function createMetadata(bytes32 _mint, string memory _name, string memory _symbol, string memory _uri, bool _isMutable) external returns(bytes32);
     function createMasterEdition(bytes32 mint, uint64 maxSupply) external returns(bytes32);
+    function updateUri(bytes32 mint, string memory _uri) external;
+    function setAndVerifyCollection(bytes32 mint, bytes32 collectionMint) external;
+    function verifyCollection(bytes32 mint, bytes32 collectionMint) external;
+    function printEdition(bytes32 masterMint, bytes32 masterTokenAccount, bytes32 newMint, uint64 edition) external returns(bytes32);
+    function burn(bytes32 mint, bytes32 tokenAccount) external;
 
     function isInitialized(bytes32 mint) external view returns(bool);
     function isNFT(bytes32 mint) external view returns(bool);
     function uri(bytes32 mint) external view returns(string memory);
     function name(bytes32 mint) external view returns(string memory);
     function symbol(bytes32 mint) external view returns(string memory);
+    function isMutable(bytes32 mint) external view returns(bool);
+    function collection(bytes32 mint) external view returns(bytes32 collectionMint, bool verified);
 }
\ No newline at end of file
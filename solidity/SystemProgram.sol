@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity >= 0.7.0;
+pragma abicoder v2;
+
+interface SystemProgram {
+    // System owned PDA[ACCOUNT_SEED_VERSION, b"PAYER", msg.sender]. Holds SOL of the contract.
+    // Source of `transfer` and base of `createAccountWithSeed`.
+    function getPayer() external view returns(bytes32);
+
+    // PDA[ACCOUNT_SEED_VERSION, b"ContractData", msg.sender, seed]. Target of `allocate` and `assign`.
+    function findAccount(bytes32 seed) external view returns(bytes32);
+    function getAccountWithSeed(bytes32 owner, string memory seed) external view returns(bytes32);
+
+    function transfer(bytes32 target, uint64 lamports) external;
+    function createAccountWithSeed(bytes32 owner, string memory seed, uint64 space) external returns(bytes32);
+    function allocate(bytes32 seed, uint64 space) external returns(bytes32);
+    function assign(bytes32 seed, bytes32 owner) external returns(bytes32);
+}
\ No newline at end of file
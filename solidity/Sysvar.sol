@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity >= 0.7.0;
+pragma abicoder v2;
+
+interface Sysvar {
+    struct Clock {
+        uint64 slot;
+        int64 epochStartTimestamp;
+        uint64 epoch;
+        uint64 leaderScheduleEpoch;
+        int64 unixTimestamp;
+    }
+
+    function clock() external view returns(Clock memory);
+    function slot() external view returns(uint64);
+    function epoch() external view returns(uint64);
+    function epochStartTimestamp() external view returns(int64);
+    function unixTimestamp() external view returns(int64);
+
+    // Rent exempt minimum for an account with `len` bytes of data
+    function minimumBalance(uint64 len) external view returns(uint64);
+
+    // SlotHashes sysvar lookup. Returns a deterministic fake hash for slots missing in the sysvar
+    function slotHash(uint64 slot) external view returns(bytes32);
+}
@@ -16,6 +16,39 @@ interface QueryAccount {
         uint64 rent_epoch;
     }
 
+    struct AccountData {
+        bytes32 owner;
+        uint64 lamports;
+        bytes[] data;
+    }
+
+    struct SplMint {
+        uint64 supply;
+        uint8 decimals;
+        bool is_initialized;
+        bytes32 freeze_authority;
+        bytes32 mint_authority;
+    }
+
+    struct SplAccount {
+        bytes32 mint;
+        bytes32 owner;
+        uint64 amount;
+        bytes32 delegate;
+        uint64 delegated_amount;
+        bytes32 close_authority;
+        uint8 state;
+    }
+
+    struct PythPrice {
+        int64 price;
+        uint64 conf;
+        int32 expo;
+        uint64 publish_time;
+        uint8 status;
+        uint64 publish_slot;
+    }
+
     /**
      * @dev Returns the account's owner Solana address.
      * @param solana_address Address of an account.
@@ -59,4 +92,34 @@ interface QueryAccount {
      * @param len Length in bytes of the returning chunk.
      */
     function data(bytes32 solana_address, uint64 offset, uint64 len) external view returns (bytes memory);
+
+    /**
+     * @dev Returns owner, lamports and data slices of several accounts in one call.
+     * Slice `i` of every account is `data[offsets[i] .. offsets[i] + lengths[i]]`,
+     * slices out of the account data bounds are returned empty.
+     * At most 32 accounts and 16 slices per call.
+     * @param solana_addresses Addresses of accounts.
+     * @param offsets Offsets in bytes of the slices.
+     * @param lengths Lengths in bytes of the slices.
+     */
+    function batch(bytes32[] memory solana_addresses, uint64[] memory offsets, uint64[] memory lengths) external view returns (AccountData[] memory);
+
+    /**
+     * @dev Returns the SPL Token mint decoded from the account.
+     * @param solana_address Address of an account.
+     */
+    function splMint(bytes32 solana_address) external view returns (SplMint memory);
+
+    /**
+     * @dev Returns the SPL Token account decoded from the account.
+     * @param solana_address Address of an account.
+     */
+    function splAccount(bytes32 solana_address) external view returns (SplAccount memory);
+
+    /**
+     * @dev Returns the aggregate price decoded from the Pyth price account.
+     * The account owner is not checked.
+     * @param solana_address Address of an account.
+     */
+    function pythPrice(bytes32 solana_address) external view returns (PythPrice memory);
 }
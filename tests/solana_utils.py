@@ -32,7 +32,8 @@ from .utils.constants import EVM_LOADER, SOLANA_URL, SYSTEM_ADDRESS, NEON_TOKEN_
     ACCOUNT_SEED_VERSION, TREASURY_POOL_SEED
 from .utils.instructions import make_DepositV03, make_Cancel, make_WriteHolder, make_ExecuteTrxFromInstruction, \
     TransactionWithComputeBudget, make_PartialCallOrContinueFromRawEthereumTX, \
-    make_ExecuteTrxFromAccountDataIterativeOrContinue
+    make_ExecuteTrxFromAccountDataIterativeOrContinue, make_ExecuteTrxBatch, make_ExecuteTrxBatchFromAccount, \
+    make_DepositSplToken
 from .utils.layouts import ACCOUNT_INFO_LAYOUT, CREATE_ACCOUNT_LAYOUT
 from .utils.types import Caller, Contract
 
@@ -440,6 +441,11 @@ def send_transaction(client: Client, trx, acc, wait_status=Confirmed):
     return solana_client.get_transaction(tx)
 
 
+def send_transaction_with_signers(client: Client, trx, *signers):
+    return client.send_transaction(trx, *signers,
+                                   opts=TxOpts(skip_confirmation=False, preflight_commitment=Confirmed))
+
+
 def evm_step_cost():
     operator_expences = PAYMENT_TO_TREASURE + LAMPORTS_PER_SIGNATURE
     return math.floor(operator_expences / EVM_STEPS)
@@ -500,6 +506,42 @@ def deposit_neon(evm_loader: EvmLoader, operator_keypair: Keypair, ether_address
     return receipt
 
 
+def deposit_spl_token(evm_loader: EvmLoader, operator_keypair: Keypair, mint: PublicKey,
+                      ether_address: Union[str, bytes], amount: int, chain_id: int):
+    ether_pubkey, _ether_bump_seed = evm_loader.ether2program(ether_address)
+
+    evm_token_authority, _auth_bump_seed = \
+        PublicKey.find_program_address([bytes("Deposit", encoding='utf-8')], evm_loader.loader_id)
+    evm_pool_key = get_associated_token_address(evm_token_authority, mint)
+
+    signer_token_pubkey = get_associated_token_address(operator_keypair.public_key, mint)
+    trx = Transaction()
+    trx.add(
+        spl.token.instructions.approve(
+            ApproveParams(
+                spl.token.constants.TOKEN_PROGRAM_ID,
+                signer_token_pubkey,
+                PublicKey(ether_pubkey),
+                operator_keypair.public_key,
+                amount,
+            )
+        )
+    )
+    trx.add(
+        make_DepositSplToken(
+            evm_loader.ether2bytes(ether_address),
+            chain_id,
+            PublicKey(ether_pubkey),
+            signer_token_pubkey,
+            evm_pool_key,
+            spl.token.constants.TOKEN_PROGRAM_ID,
+            operator_keypair.public_key,
+        )
+    )
+
+    return send_transaction(solana_client, trx, operator_keypair)
+
+
 def cancel_transaction(
         tx_hash: HexBytes,
         holder_acc: PublicKey,
@@ -529,14 +571,18 @@ def write_transaction_to_holder_account(
         holder_account: PublicKey,
         operator: Keypair,
 ):
+    write_to_holder_account(signed_tx.rawTransaction, signed_tx.hash, holder_account, operator)
+
+
+def write_to_holder_account(data: bytes, hash: bytes, holder_account: PublicKey, operator: Keypair):
     # Write transaction to transaction holder account
     offset = 0
     receipts = []
-    rest = signed_tx.rawTransaction
+    rest = data
     while len(rest):
         (part, rest) = (rest[:920], rest[920:])
         trx = Transaction()
-        trx.add(make_WriteHolder(operator.public_key, holder_account, signed_tx.hash, offset, part))
+        trx.add(make_WriteHolder(operator.public_key, holder_account, hash, offset, part))
         receipts.append(
             solana_client.send_transaction(
                 trx,
@@ -561,6 +607,26 @@ def execute_trx_from_instruction(operator: Keypair, evm_loader, treasury_address
     return solana_client.send_transaction(trx, signer, opts=TxOpts(skip_preflight=False, skip_confirmation=False))
 
 
+def execute_trx_batch_from_instruction(operator: Keypair, evm_loader, treasury_address: PublicKey,
+                                       treasury_buffer: bytes, instructions: typing.List[SignedTransaction],
+                                       additional_accounts, signer: Keypair) -> SendTransactionResp:
+    trx = TransactionWithComputeBudget(operator)
+    trx.add(make_ExecuteTrxBatch(operator, evm_loader, treasury_address, treasury_buffer,
+                                 [instruction.rawTransaction for instruction in instructions], additional_accounts))
+
+    return solana_client.send_transaction(trx, signer, opts=TxOpts(skip_preflight=False, skip_confirmation=False))
+
+
+def execute_trx_batch_from_account(operator: Keypair, evm_loader, holder_address: PublicKey,
+                                   treasury_address: PublicKey, treasury_buffer: bytes,
+                                   additional_accounts, signer: Keypair) -> SendTransactionResp:
+    trx = TransactionWithComputeBudget(operator)
+    trx.add(make_ExecuteTrxBatchFromAccount(operator, evm_loader, holder_address, treasury_address, treasury_buffer,
+                                            additional_accounts))
+
+    return solana_client.send_transaction(trx, signer, opts=TxOpts(skip_preflight=False, skip_confirmation=False))
+
+
 def send_transaction_step_from_instruction(operator: Keypair, evm_loader, treasury, storage_account,
                                            instruction: SignedTransaction,
                                            additional_accounts, steps_count, signer: Keypair,
@@ -0,0 +1,60 @@
+import pytest
+import solana
+import solana.system_program as sp
+from solana.transaction import Transaction
+
+from .solana_utils import solana_client, send_transaction, get_solana_balance, create_treasury_pool_address
+from .utils.constants import CHAIN_ID
+from .utils.instructions import make_CollectTreasureBatch, main_treasury_address
+
+# Pools out of the range used by the transaction tests, so the collected amounts are exact
+POOL_INDICES = [100, 101]
+
+
+def collect(operator_keypair, indices, pools, chain_id=CHAIN_ID):
+    trx = Transaction()
+    trx.add(make_CollectTreasureBatch(chain_id, indices, pools))
+    return send_transaction(solana_client, trx, operator_keypair)
+
+
+def test_collect_treasury_batch(operator_keypair):
+    pools = [create_treasury_pool_address(index) for index in POOL_INDICES]
+    trx = Transaction()
+    for pool in pools:
+        trx.add(sp.transfer(sp.TransferParams(from_pubkey=operator_keypair.public_key, to_pubkey=pool,
+                                              lamports=10 ** 9)))
+    send_transaction(solana_client, trx, operator_keypair)
+
+    rent_exempt = solana_client.get_minimum_balance_for_rent_exemption(0).value
+    pool_balances = [get_solana_balance(pool) for pool in pools]
+    main_treasury_balance = get_solana_balance(main_treasury_address())
+
+    collect(operator_keypair, POOL_INDICES, pools)
+
+    for pool in pools:
+        assert get_solana_balance(pool) == rent_exempt
+    collected = sum(balance - rent_exempt for balance in pool_balances)
+    assert get_solana_balance(main_treasury_address()) == main_treasury_balance + collected
+
+
+def test_pool_of_other_index(operator_keypair):
+    pools = [create_treasury_pool_address(index) for index in reversed(POOL_INDICES)]
+    with pytest.raises(solana.rpc.core.RPCException, match="invalid treasure account"):
+        collect(operator_keypair, POOL_INDICES, pools)
+
+
+def test_pool_of_other_chain(operator_keypair):
+    pools = [create_treasury_pool_address(index) for index in POOL_INDICES]
+    with pytest.raises(solana.rpc.core.RPCException, match="invalid treasure account"):
+        collect(operator_keypair, POOL_INDICES, pools, chain_id=CHAIN_ID + 1)
+
+
+def test_missing_pool_account(operator_keypair):
+    pools = [create_treasury_pool_address(POOL_INDICES[0])]
+    with pytest.raises(solana.rpc.core.RPCException, match="expected 2 pool accounts, found 1"):
+        collect(operator_keypair, POOL_INDICES, pools)
+
+
+def test_empty_indices(operator_keypair):
+    with pytest.raises(solana.rpc.core.RPCException, match="invalid pool indices"):
+        collect(operator_keypair, [], [])
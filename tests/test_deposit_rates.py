@@ -0,0 +1,148 @@
+import pytest
+import solana
+import solana.system_program as sp
+import spl.token.instructions as spl_token
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.rpc.commitment import Confirmed
+from solana.transaction import Transaction
+from spl.token.constants import TOKEN_PROGRAM_ID, MINT_LEN
+
+from .solana_utils import solana_client, get_solana_balance, get_neon_balance, deposit_spl_token, \
+    send_transaction_with_signers
+from .utils.constants import EVM_LOADER, CHAIN_ID, DEPOSIT_RATES_SEED, NEON_TOKEN_MINT_ID, TAG_DEPOSIT_RATES
+from .utils.instructions import deposit_rates_address, make_DepositRatesInit, make_DepositRatesSet
+from .utils.layouts import DEPOSIT_RATES_LAYOUT, DEPOSIT_RATE_LAYOUT
+
+# The deposit rates account is shared by all mints, so this module runs after the parallel tests.
+
+MINT_DECIMALS = 6
+MINTED_AMOUNT = 10 ** 9
+
+
+def deposit_rates_data() -> bytes:
+    return solana_client.get_account_info(deposit_rates_address(), commitment=Confirmed).value.data
+
+
+def deposit_rates() -> dict:
+    data = deposit_rates_data()
+    header = DEPOSIT_RATES_LAYOUT.parse(data)
+    entries = data[DEPOSIT_RATES_LAYOUT.sizeof():]
+    entry_size = DEPOSIT_RATE_LAYOUT.sizeof()
+
+    rates = {}
+    for index in range(header.rates_count):
+        rate = DEPOSIT_RATE_LAYOUT.parse(entries[index * entry_size:])
+        rates[(PublicKey(rate.mint), rate.chain_id)] = (rate.numerator, rate.denominator)
+    return rates
+
+
+def set_rate(mint, numerator, denominator, authority, operator_keypair):
+    trx = Transaction()
+    trx.add(make_DepositRatesSet(mint, CHAIN_ID, numerator, denominator, authority.public_key, authority.public_key))
+    return send_transaction_with_signers(solana_client, trx, operator_keypair, authority)
+
+
+@pytest.fixture(scope="module")
+def rates_account(operator_keypair, upgrade_authority_keypair) -> PublicKey:
+    address = deposit_rates_address()
+    if solana_client.get_account_info(address, commitment=Confirmed).value is None:
+        trx = Transaction()
+        trx.add(make_DepositRatesInit(upgrade_authority_keypair.public_key, upgrade_authority_keypair.public_key,
+                                      operator_keypair.public_key))
+        send_transaction_with_signers(solana_client, trx, operator_keypair, upgrade_authority_keypair)
+
+    return address
+
+
+@pytest.fixture(scope="function")
+def spl_mint(operator_keypair, evm_loader) -> PublicKey:
+    """
+    New mint with the operator token account and the Neon EVM deposit pool
+    """
+    mint = Keypair.generate()
+    deposit_authority, _ = PublicKey.find_program_address([b"Deposit"], evm_loader.loader_id)
+    rent = solana_client.get_minimum_balance_for_rent_exemption(MINT_LEN).value
+
+    trx = Transaction()
+    trx.add(sp.create_account(sp.CreateAccountParams(from_pubkey=operator_keypair.public_key,
+                                                     new_account_pubkey=mint.public_key, lamports=rent,
+                                                     space=MINT_LEN, program_id=TOKEN_PROGRAM_ID)))
+    trx.add(spl_token.initialize_mint(spl_token.InitializeMintParams(decimals=MINT_DECIMALS,
+                                                                     program_id=TOKEN_PROGRAM_ID,
+                                                                     mint=mint.public_key,
+                                                                     mint_authority=operator_keypair.public_key)))
+    trx.add(spl_token.create_associated_token_account(operator_keypair.public_key, operator_keypair.public_key,
+                                                      mint.public_key))
+    trx.add(spl_token.create_associated_token_account(operator_keypair.public_key, deposit_authority,
+                                                      mint.public_key))
+    trx.add(spl_token.mint_to(spl_token.MintToParams(
+        program_id=TOKEN_PROGRAM_ID, mint=mint.public_key,
+        dest=spl_token.get_associated_token_address(operator_keypair.public_key, mint.public_key),
+        mint_authority=operator_keypair.public_key, amount=MINTED_AMOUNT)))
+    send_transaction_with_signers(solana_client, trx, operator_keypair, mint)
+
+    return mint.public_key
+
+
+def test_init_by_not_upgrade_authority(operator_keypair, session_user):
+    trx = Transaction()
+    trx.add(make_DepositRatesInit(session_user.solana_account.public_key, session_user.solana_account.public_key,
+                                  operator_keypair.public_key))
+    with pytest.raises(solana.rpc.core.RPCException, match="Deposit Rates - invalid authority"):
+        send_transaction_with_signers(solana_client, trx, operator_keypair, session_user.solana_account)
+
+
+def test_rates_account(rates_account, upgrade_authority_keypair):
+    data = deposit_rates_data()
+    header = DEPOSIT_RATES_LAYOUT.parse(data)
+
+    assert header.tag == TAG_DEPOSIT_RATES
+    assert PublicKey(header.authority) == upgrade_authority_keypair.public_key
+    assert PublicKey.create_program_address([bytes(DEPOSIT_RATES_SEED, 'utf8'), bytes([header.bump_seed])],
+                                            PublicKey(EVM_LOADER)) == rates_account
+    assert len(data) == DEPOSIT_RATES_LAYOUT.sizeof() + header.rates_count * DEPOSIT_RATE_LAYOUT.sizeof()
+
+
+def test_set_by_not_authority(rates_account, operator_keypair, spl_mint):
+    with pytest.raises(solana.rpc.core.RPCException, match="Deposit Rates - invalid authority"):
+        set_rate(spl_mint, 1, 1, operator_keypair, operator_keypair)
+
+
+def test_deposit_spl_token(rates_account, operator_keypair, upgrade_authority_keypair, evm_loader, user_account,
+                           spl_mint):
+    set_rate(spl_mint, 2, 3, upgrade_authority_keypair, operator_keypair)
+    assert deposit_rates()[(spl_mint, CHAIN_ID)] == (2, 3)
+
+    amount = 3000
+    neon_decimals = solana_client.get_token_supply(NEON_TOKEN_MINT_ID).value.decimals
+    balance_before = get_neon_balance(solana_client, user_account.solana_account_address)
+
+    deposit_spl_token(evm_loader, operator_keypair, spl_mint, user_account.eth_address, amount, CHAIN_ID)
+
+    balance_after = get_neon_balance(solana_client, user_account.solana_account_address)
+    assert balance_after - balance_before == amount * 2 * 10 ** (18 - neon_decimals) // 3
+
+    deposit_authority, _ = PublicKey.find_program_address([b"Deposit"], evm_loader.loader_id)
+    pool = spl_token.get_associated_token_address(deposit_authority, spl_mint)
+    assert solana_client.get_token_account_balance(pool, commitment=Confirmed).value.amount == str(amount)
+
+
+def test_deposit_of_not_accepted_mint(rates_account, operator_keypair, evm_loader, user_account, spl_mint):
+    with pytest.raises(solana.rpc.core.RPCException, match="is not accepted for chain"):
+        deposit_spl_token(evm_loader, operator_keypair, spl_mint, user_account.eth_address, 1000, CHAIN_ID)
+
+
+def test_remove_rate(rates_account, operator_keypair, upgrade_authority_keypair, evm_loader, user_account, spl_mint):
+    rates_size = len(deposit_rates_data())
+    set_rate(spl_mint, 1, 1, upgrade_authority_keypair, operator_keypair)
+    assert len(deposit_rates_data()) == rates_size + DEPOSIT_RATE_LAYOUT.sizeof()
+
+    authority_balance = get_solana_balance(upgrade_authority_keypair.public_key)
+    set_rate(spl_mint, 0, 1, upgrade_authority_keypair, operator_keypair)
+
+    assert (spl_mint, CHAIN_ID) not in deposit_rates()
+    assert len(deposit_rates_data()) == rates_size
+    assert get_solana_balance(upgrade_authority_keypair.public_key) > authority_balance
+    with pytest.raises(solana.rpc.core.RPCException, match="is not accepted for chain"):
+        deposit_spl_token(evm_loader, operator_keypair, spl_mint, user_account.eth_address, 1000, CHAIN_ID)
@@ -0,0 +1,110 @@
+import pytest
+import solana
+from sha3 import keccak_256
+
+from .solana_utils import execute_trx_batch_from_instruction, execute_trx_batch_from_account, solana_client, \
+    get_neon_balance, make_new_user, deposit_neon, write_to_holder_account
+from .utils.ethereum import make_eth_transaction
+from .utils.instructions import batch_layout
+from .utils.storage import create_holder
+from .utils.transaction_checks import check_transaction_logs_have_text
+
+
+class TestExecuteTrxBatch:
+
+    def test_transfers_from_instruction(self, operator_keypair, treasury_pool, sender_with_tokens, session_user,
+                                        evm_loader):
+        second_sender = make_new_user(evm_loader)
+        deposit_neon(evm_loader, operator_keypair, second_sender.eth_address, 100)
+        recipient_balance_before = get_neon_balance(solana_client, session_user.solana_account_address)
+
+        signed_txs = [
+            make_eth_transaction(session_user.eth_address, None, sender_with_tokens.solana_account,
+                                 sender_with_tokens.solana_account_address, 10),
+            make_eth_transaction(session_user.eth_address, None, second_sender.solana_account,
+                                 second_sender.solana_account_address, 20),
+        ]
+        resp = execute_trx_batch_from_instruction(operator_keypair, evm_loader, treasury_pool.account,
+                                                  treasury_pool.buffer, signed_txs,
+                                                  [sender_with_tokens.solana_account_address,
+                                                   second_sender.solana_account_address,
+                                                   session_user.solana_account_address],
+                                                  operator_keypair)
+
+        recipient_balance_after = get_neon_balance(solana_client, session_user.solana_account_address)
+        assert recipient_balance_before + 30 == recipient_balance_after
+        check_transaction_logs_have_text(resp.value, "exit_status=0x11")
+
+    def test_failed_transaction_is_skipped(self, operator_keypair, treasury_pool, sender_with_tokens, session_user,
+                                           user_account, evm_loader):
+        recipient_balance_before = get_neon_balance(solana_client, session_user.solana_account_address)
+
+        signed_txs = [
+            make_eth_transaction(session_user.eth_address, None, sender_with_tokens.solana_account,
+                                 sender_with_tokens.solana_account_address, 10),
+            # The caller has no balance to transfer
+            make_eth_transaction(session_user.eth_address, None, user_account.solana_account,
+                                 user_account.solana_account_address, 10),
+        ]
+        resp = execute_trx_batch_from_instruction(operator_keypair, evm_loader, treasury_pool.account,
+                                                  treasury_pool.buffer, signed_txs,
+                                                  [sender_with_tokens.solana_account_address,
+                                                   user_account.solana_account_address,
+                                                   session_user.solana_account_address],
+                                                  operator_keypair)
+
+        recipient_balance_after = get_neon_balance(solana_client, session_user.solana_account_address)
+        assert recipient_balance_before + 10 == recipient_balance_after
+        check_transaction_logs_have_text(resp.value, "Skip transaction 1")
+
+    def test_all_transactions_failed(self, operator_keypair, treasury_pool, session_user, user_account, evm_loader):
+        signed_txs = [
+            make_eth_transaction(session_user.eth_address, None, user_account.solana_account,
+                                 user_account.solana_account_address, 10),
+        ]
+        with pytest.raises(solana.rpc.core.RPCException, match="No transactions executed in batch"):
+            execute_trx_batch_from_instruction(operator_keypair, evm_loader, treasury_pool.account,
+                                               treasury_pool.buffer, signed_txs,
+                                               [user_account.solana_account_address,
+                                                session_user.solana_account_address],
+                                               operator_keypair)
+
+    def test_transfers_from_account(self, operator_keypair, treasury_pool, sender_with_tokens, session_user,
+                                    evm_loader, new_holder_acc):
+        second_sender = make_new_user(evm_loader)
+        deposit_neon(evm_loader, operator_keypair, second_sender.eth_address, 100)
+        recipient_balance_before = get_neon_balance(solana_client, session_user.solana_account_address)
+
+        signed_txs = [
+            make_eth_transaction(session_user.eth_address, None, sender_with_tokens.solana_account,
+                                 sender_with_tokens.solana_account_address, 10),
+            make_eth_transaction(session_user.eth_address, None, second_sender.solana_account,
+                                 second_sender.solana_account_address, 20),
+        ]
+        batch = batch_layout([signed_tx.rawTransaction for signed_tx in signed_txs])
+        write_to_holder_account(batch, keccak_256(batch).digest(), new_holder_acc, operator_keypair)
+
+        resp = execute_trx_batch_from_account(operator_keypair, evm_loader, new_holder_acc, treasury_pool.account,
+                                              treasury_pool.buffer,
+                                              [sender_with_tokens.solana_account_address,
+                                               second_sender.solana_account_address,
+                                               session_user.solana_account_address],
+                                              operator_keypair)
+
+        recipient_balance_after = get_neon_balance(solana_client, session_user.solana_account_address)
+        assert recipient_balance_before + 30 == recipient_balance_after
+        check_transaction_logs_have_text(resp.value, "exit_status=0x11")
+
+    def test_holder_with_wrong_hash(self, operator_keypair, treasury_pool, sender_with_tokens, session_user,
+                                    evm_loader, new_holder_acc):
+        signed_tx = make_eth_transaction(session_user.eth_address, None, sender_with_tokens.solana_account,
+                                         sender_with_tokens.solana_account_address, 10)
+        batch = batch_layout([signed_tx.rawTransaction])
+        write_to_holder_account(batch, signed_tx.hash, new_holder_acc, operator_keypair)
+
+        with pytest.raises(solana.rpc.core.RPCException, match="Holder Account - invalid transaction hash"):
+            execute_trx_batch_from_account(operator_keypair, evm_loader, new_holder_acc, treasury_pool.account,
+                                           treasury_pool.buffer,
+                                           [sender_with_tokens.solana_account_address,
+                                            session_user.solana_account_address],
+                                           operator_keypair)
@@ -116,6 +116,8 @@ def test_write_to_not_finalized_holder(rw_lock_contract, user_account, evm_loade
     account_data = solana_client.get_account_info(new_holder_acc, commitment=Confirmed).value.data
     parsed_data = STORAGE_ACCOUNT_INFO_LAYOUT.parse(account_data)
     assert parsed_data.tag == TAG_STATE
+    assert parsed_data.last_progress_slot >= parsed_data.slot
+    assert parsed_data.chain_id == 111
 
     signed_tx2 = make_contract_call_trx(user_account, rw_lock_contract, "unchange_storage(uint8,uint8)", [1, 1])
 
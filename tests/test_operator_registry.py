@@ -0,0 +1,164 @@
+import json
+import pathlib
+
+import pytest
+import solana
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.rpc.commitment import Confirmed
+from solana.transaction import Transaction
+
+from .solana_utils import solana_client, get_solana_balance, execute_transaction_steps_from_instruction, \
+    send_transaction_with_signers
+from .utils.assert_messages import InstructionAsserts
+from .utils.constants import EVM_LOADER, OPERATOR_REGISTRY_SEED, TAG_OPERATOR_REGISTRY
+from .utils.ethereum import make_eth_transaction
+from .utils.instructions import operator_registry_address, make_OperatorRegistryInit, make_OperatorRegistryAdd, \
+    make_OperatorRegistryRemove, make_OperatorRegistrySuspend
+from .utils.layouts import OPERATOR_REGISTRY_LAYOUT, OPERATOR_REGISTRY_ENTRY_LAYOUT
+from .utils.storage import create_holder
+
+# Operators are checked against the registry passed as the last instruction account,
+# otherwise against the compiled whitelist. The registry is shared by all tests,
+# so this module runs after the parallel tests and registers every test operator.
+
+STATUS_ACTIVE = 1
+STATUS_SUSPENDED = 2
+
+
+def registry_data() -> bytes:
+    return solana_client.get_account_info(operator_registry_address(), commitment=Confirmed).value.data
+
+
+def registered_operators() -> dict:
+    data = registry_data()
+    header = OPERATOR_REGISTRY_LAYOUT.parse(data)
+    entries = data[OPERATOR_REGISTRY_LAYOUT.sizeof():]
+    entry_size = OPERATOR_REGISTRY_ENTRY_LAYOUT.sizeof()
+
+    operators = {}
+    for index in range(header.operators_count):
+        entry = OPERATOR_REGISTRY_ENTRY_LAYOUT.parse(entries[index * entry_size:])
+        operators[PublicKey(entry.operator)] = entry.status
+    return operators
+
+
+@pytest.fixture(scope="module")
+def operator_registry(operator_keypair, upgrade_authority_keypair) -> PublicKey:
+    address = operator_registry_address()
+    if solana_client.get_account_info(address, commitment=Confirmed).value is None:
+        trx = Transaction()
+        trx.add(make_OperatorRegistryInit(upgrade_authority_keypair.public_key, upgrade_authority_keypair.public_key,
+                                          operator_keypair.public_key))
+        send_transaction_with_signers(solana_client, trx, operator_keypair, upgrade_authority_keypair)
+
+    registered = registered_operators()
+    key_path = pathlib.Path(__file__).parent.parent / "operator-keypairs"
+    for key_file in sorted(key_path.glob("*.json")):
+        with open(key_file, "r") as key:
+            operator = Keypair.from_secret_key(json.load(key)[:32]).public_key
+        if operator not in registered:
+            trx = Transaction()
+            trx.add(make_OperatorRegistryAdd(operator, upgrade_authority_keypair.public_key,
+                                             operator_keypair.public_key))
+            send_transaction_with_signers(solana_client, trx, operator_keypair, upgrade_authority_keypair)
+
+    return address
+
+
+def transfer(operator: Keypair, holder: PublicKey, evm_loader, treasury_pool, sender_with_tokens, session_user,
+             with_registry=True):
+    signed_tx = make_eth_transaction(session_user.eth_address, None, sender_with_tokens.solana_account,
+                                     sender_with_tokens.solana_account_address, 1)
+    accounts = [session_user.solana_account_address, sender_with_tokens.solana_account_address]
+    if with_registry:
+        accounts.append(operator_registry_address())
+    return execute_transaction_steps_from_instruction(operator, evm_loader, treasury_pool, holder, signed_tx,
+                                                      accounts, 0)
+
+
+def test_init_by_not_upgrade_authority(operator_keypair, session_user):
+    trx = Transaction()
+    trx.add(make_OperatorRegistryInit(session_user.solana_account.public_key, session_user.solana_account.public_key,
+                                      operator_keypair.public_key))
+    with pytest.raises(solana.rpc.core.RPCException, match="Operator Registry - invalid authority"):
+        send_transaction_with_signers(solana_client, trx, operator_keypair, session_user.solana_account)
+
+
+def test_registry_account(operator_registry, operator_keypair, upgrade_authority_keypair):
+    data = registry_data()
+    header = OPERATOR_REGISTRY_LAYOUT.parse(data)
+
+    assert header.tag == TAG_OPERATOR_REGISTRY
+    assert PublicKey(header.authority) == upgrade_authority_keypair.public_key
+    assert PublicKey.create_program_address([bytes(OPERATOR_REGISTRY_SEED, 'utf8'), bytes([header.bump_seed])],
+                                            PublicKey(EVM_LOADER)) == operator_registry
+    assert len(data) == OPERATOR_REGISTRY_LAYOUT.sizeof() + header.operators_count * 33
+    assert registered_operators()[operator_keypair.public_key] == STATUS_ACTIVE
+
+
+def test_add_by_not_authority(operator_registry, operator_keypair, user_account):
+    trx = Transaction()
+    trx.add(make_OperatorRegistryAdd(user_account.solana_account.public_key, operator_keypair.public_key,
+                                     operator_keypair.public_key))
+    with pytest.raises(solana.rpc.core.RPCException, match="Operator Registry - invalid authority"):
+        send_transaction_with_signers(solana_client, trx, operator_keypair)
+
+
+def test_add_registered_operator(operator_registry, operator_keypair, upgrade_authority_keypair):
+    trx = Transaction()
+    trx.add(make_OperatorRegistryAdd(operator_keypair.public_key, upgrade_authority_keypair.public_key,
+                                     operator_keypair.public_key))
+    with pytest.raises(solana.rpc.core.RPCException, match="is already registered"):
+        send_transaction_with_signers(solana_client, trx, operator_keypair, upgrade_authority_keypair)
+
+
+def test_remove_not_registered_operator(operator_registry, operator_keypair, upgrade_authority_keypair):
+    trx = Transaction()
+    trx.add(make_OperatorRegistryRemove(Keypair.generate().public_key, upgrade_authority_keypair.public_key))
+    with pytest.raises(solana.rpc.core.RPCException, match="is not registered"):
+        send_transaction_with_signers(solana_client, trx, operator_keypair, upgrade_authority_keypair)
+
+
+def test_operator_lifecycle(operator_registry, operator_keypair, upgrade_authority_keypair, user_account,
+                            evm_loader, treasury_pool, sender_with_tokens, session_user):
+    operator = user_account.solana_account
+    authority = upgrade_authority_keypair
+    holder = create_holder(operator)
+    registry_size = len(registry_data())
+
+    with pytest.raises(solana.rpc.core.RPCException, match=InstructionAsserts.NOT_AUTHORIZED_OPERATOR):
+        transfer(operator, holder, evm_loader, treasury_pool, sender_with_tokens, session_user)
+
+    trx = Transaction()
+    trx.add(make_OperatorRegistryAdd(operator.public_key, authority.public_key, operator_keypair.public_key))
+    send_transaction_with_signers(solana_client, trx, operator_keypair, authority)
+    assert registered_operators()[operator.public_key] == STATUS_ACTIVE
+    assert len(registry_data()) == registry_size + 33
+    transfer(operator, holder, evm_loader, treasury_pool, sender_with_tokens, session_user)
+    # Without the registry the operator is checked against the compiled whitelist
+    with pytest.raises(solana.rpc.core.RPCException, match=InstructionAsserts.NOT_AUTHORIZED_OPERATOR):
+        transfer(operator, holder, evm_loader, treasury_pool, sender_with_tokens, session_user, with_registry=False)
+
+    trx = Transaction()
+    trx.add(make_OperatorRegistrySuspend(operator.public_key, authority.public_key, True))
+    send_transaction_with_signers(solana_client, trx, operator_keypair, authority)
+    assert registered_operators()[operator.public_key] == STATUS_SUSPENDED
+    with pytest.raises(solana.rpc.core.RPCException, match="is suspended"):
+        transfer(operator, holder, evm_loader, treasury_pool, sender_with_tokens, session_user)
+
+    trx = Transaction()
+    trx.add(make_OperatorRegistrySuspend(operator.public_key, authority.public_key, False))
+    send_transaction_with_signers(solana_client, trx, operator_keypair, authority)
+    assert registered_operators()[operator.public_key] == STATUS_ACTIVE
+    transfer(operator, holder, evm_loader, treasury_pool, sender_with_tokens, session_user)
+
+    authority_balance = get_solana_balance(authority.public_key)
+    trx = Transaction()
+    trx.add(make_OperatorRegistryRemove(operator.public_key, authority.public_key))
+    send_transaction_with_signers(solana_client, trx, operator_keypair, authority)
+    assert operator.public_key not in registered_operators()
+    assert len(registry_data()) == registry_size
+    assert get_solana_balance(authority.public_key) > authority_balance
+    with pytest.raises(solana.rpc.core.RPCException, match=InstructionAsserts.NOT_AUTHORIZED_OPERATOR):
+        transfer(operator, holder, evm_loader, treasury_pool, sender_with_tokens, session_user)
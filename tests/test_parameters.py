@@ -0,0 +1,101 @@
+import pytest
+import solana
+from solana.publickey import PublicKey
+from solana.rpc.commitment import Confirmed
+from solana.transaction import Transaction
+
+from .solana_utils import solana_client, get_solana_balance, execute_trx_from_instruction, \
+    send_transaction_with_signers
+from .utils.constants import EVM_LOADER, PARAMETERS_SEED, PAYMENT_TO_TREASURE, TAG_PARAMETERS
+from .utils.ethereum import make_eth_transaction
+from .utils.instructions import parameters_address, make_ParametersInit, make_ParametersUpdate
+from .utils.layouts import PARAMETERS_LAYOUT
+
+# Overridden parameters change the behavior of every instruction passing the parameters account,
+# so this module runs after the parallel tests and restores the compiled defaults.
+
+PAYMENT_TO_TREASURE_INDEX = 0
+EVM_STEPS_MIN_INDEX = 1
+
+
+def parameters_data():
+    data = solana_client.get_account_info(parameters_address(), commitment=Confirmed).value.data
+    return PARAMETERS_LAYOUT.parse(data)
+
+
+def update(index, value, authority, payer):
+    trx = Transaction()
+    trx.add(make_ParametersUpdate(index, value, authority.public_key))
+    return send_transaction_with_signers(solana_client, trx, payer, authority)
+
+
+@pytest.fixture(scope="module")
+def parameters(operator_keypair, upgrade_authority_keypair) -> PublicKey:
+    address = parameters_address()
+    if solana_client.get_account_info(address, commitment=Confirmed).value is None:
+        trx = Transaction()
+        trx.add(make_ParametersInit(upgrade_authority_keypair.public_key, upgrade_authority_keypair.public_key,
+                                    operator_keypair.public_key))
+        send_transaction_with_signers(solana_client, trx, operator_keypair, upgrade_authority_keypair)
+
+    return address
+
+
+def test_init_by_not_upgrade_authority(operator_keypair, session_user):
+    trx = Transaction()
+    trx.add(make_ParametersInit(session_user.solana_account.public_key, session_user.solana_account.public_key,
+                                operator_keypair.public_key))
+    with pytest.raises(solana.rpc.core.RPCException, match="Parameters - invalid authority"):
+        send_transaction_with_signers(solana_client, trx, operator_keypair, session_user.solana_account)
+
+
+def test_parameters_account(parameters, upgrade_authority_keypair):
+    data = parameters_data()
+
+    assert data.tag == TAG_PARAMETERS
+    assert PublicKey(data.authority) == upgrade_authority_keypair.public_key
+    assert PublicKey.create_program_address([bytes(PARAMETERS_SEED, 'utf8'), bytes([data.bump_seed])],
+                                            PublicKey(EVM_LOADER)) == parameters
+
+
+def test_update_by_not_authority(parameters, operator_keypair):
+    with pytest.raises(solana.rpc.core.RPCException, match="Parameters - invalid authority"):
+        update(PAYMENT_TO_TREASURE_INDEX, 1, operator_keypair, operator_keypair)
+
+
+def test_update_unknown_parameter(parameters, operator_keypair, upgrade_authority_keypair):
+    with pytest.raises(solana.rpc.core.RPCException, match="Unknown parameter index 5"):
+        update(5, 1, upgrade_authority_keypair, operator_keypair)
+
+
+def test_update_divisor_to_zero(parameters, operator_keypair, upgrade_authority_keypair):
+    with pytest.raises(solana.rpc.core.RPCException, match="Parameter EVM_STEPS_MIN can't be zero"):
+        update(EVM_STEPS_MIN_INDEX, 0, upgrade_authority_keypair, operator_keypair)
+
+
+def test_override_payment_to_treasure(parameters, operator_keypair, upgrade_authority_keypair, evm_loader,
+                                      treasury_pool, sender_with_tokens, session_user):
+    def treasury_payment(with_parameters=True):
+        signed_tx = make_eth_transaction(session_user.eth_address, None, sender_with_tokens.solana_account,
+                                         sender_with_tokens.solana_account_address, 1)
+        balance_before = get_solana_balance(treasury_pool.account)
+        accounts = [session_user.solana_account_address, sender_with_tokens.solana_account_address]
+        # The parameters account is optional, passed as the last account
+        if with_parameters:
+            accounts.append(parameters)
+        execute_trx_from_instruction(operator_keypair, evm_loader, treasury_pool.account, treasury_pool.buffer,
+                                     signed_tx, accounts, operator_keypair)
+        return get_solana_balance(treasury_pool.account) - balance_before
+
+    payment = PAYMENT_TO_TREASURE + 1000
+    try:
+        update(PAYMENT_TO_TREASURE_INDEX, payment, upgrade_authority_keypair, operator_keypair)
+        override = parameters_data().overrides[PAYMENT_TO_TREASURE_INDEX]
+        assert (override.is_set, override.value) == (1, payment)
+        assert treasury_payment() == payment
+        assert treasury_payment(with_parameters=False) == PAYMENT_TO_TREASURE
+    finally:
+        update(PAYMENT_TO_TREASURE_INDEX, None, upgrade_authority_keypair, operator_keypair)
+
+    assert parameters_data().overrides[PAYMENT_TO_TREASURE_INDEX].is_set == 0
+    assert treasury_payment() == PAYMENT_TO_TREASURE
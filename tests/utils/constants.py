@@ -9,20 +9,29 @@ SYSVAR_CLOCK_ADDRESS = "SysvarC1ock11111111111111111111111111111111"
 SYS_INSTRUCT_ADDRESS = "Sysvar1nstructions1111111111111111111111111"
 KECCAKPROG_ADDRESS = "KeccakSecp256k11111111111111111111111111111"
 RENT_ID_ADDRESS = "SysvarRent111111111111111111111111111111111"
+BPF_LOADER_UPGRADEABLE_ADDRESS = "BPFLoaderUpgradeab1e11111111111111111111111"
 INCINERATOR_ADDRESS = "1nc1nerator11111111111111111111111111111111"
 TREASURY_POOL_SEED = os.environ.get("NEON_POOL_SEED", "treasury_pool")
 TREASURY_POOL_COUNT = os.environ.get("NEON_POOL_COUNT", 128)
+OPERATOR_REGISTRY_SEED = os.environ.get("NEON_OPERATOR_REGISTRY_SEED", "operator_registry")
+PARAMETERS_SEED = os.environ.get("NEON_PARAMETERS_SEED", "parameters")
+DEPOSIT_RATES_SEED = os.environ.get("NEON_DEPOSIT_RATES_SEED", "deposit_rates")
+PAYMENT_TO_TREASURE = int(os.environ.get("NEON_PAYMENT_TO_TREASURE", 5000))
 COMPUTE_BUDGET_ID: PublicKey = PublicKey("ComputeBudget111111111111111111111111111111")
 
 ACCOUNT_SEED_VERSION = b'\3'
 
 TAG_EMPTY = 0
 TAG_ACCOUNT_V3 = 12
-TAG_STATE = 22
+TAG_STATE = 23
 TAG_FINALIZED_STATE = 31
 TAG_CONTRACT_STORAGE = 42
 TAG_HOLDER = 51
+TAG_OPERATOR_REGISTRY = 61
+TAG_PARAMETERS = 62
+TAG_DEPOSIT_RATES = 63
 
 SOLANA_URL = os.environ.get("SOLANA_URL", "http://localhost:8899")
 EVM_LOADER = os.environ.get("EVM_LOADER")
+CHAIN_ID = int(os.environ.get("NEON_CHAIN_ID", 111))
 NEON_TOKEN_MINT_ID: PublicKey = PublicKey(os.environ.get("NEON_TOKEN_MINT"))
@@ -6,7 +6,8 @@ from solana.publickey import PublicKey
 import solana.system_program as sp
 from solana.transaction import AccountMeta, TransactionInstruction, Transaction
 
-from .constants import EVM_LOADER, INCINERATOR_ADDRESS
+from .constants import EVM_LOADER, INCINERATOR_ADDRESS, OPERATOR_REGISTRY_SEED, PARAMETERS_SEED, \
+    DEPOSIT_RATES_SEED, BPF_LOADER_UPGRADEABLE_ADDRESS, TREASURY_POOL_SEED
 
 DEFAULT_UNITS = 500 * 1000
 DEFAULT_HEAP_FRAME = 256 * 1024
@@ -46,6 +47,28 @@ class TransactionWithComputeBudget(Transaction):
             self.add(ComputeBudget.request_heap_frame(operator, heap_frame))
 
 
+def operator_registry_address(evm_loader=EVM_LOADER) -> PublicKey:
+    return PublicKey.find_program_address([bytes(OPERATOR_REGISTRY_SEED, 'utf8')], PublicKey(evm_loader))[0]
+
+
+def parameters_address(evm_loader=EVM_LOADER) -> PublicKey:
+    return PublicKey.find_program_address([bytes(PARAMETERS_SEED, 'utf8')], PublicKey(evm_loader))[0]
+
+
+def deposit_rates_address(evm_loader=EVM_LOADER) -> PublicKey:
+    return PublicKey.find_program_address([bytes(DEPOSIT_RATES_SEED, 'utf8')], PublicKey(evm_loader))[0]
+
+
+def main_treasury_address(evm_loader=EVM_LOADER) -> PublicKey:
+    return PublicKey.find_program_address([bytes(TREASURY_POOL_SEED, 'utf8')], PublicKey(evm_loader))[0]
+
+
+def program_data_address(evm_loader=EVM_LOADER) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes(PublicKey(evm_loader))], PublicKey(BPF_LOADER_UPGRADEABLE_ADDRESS)
+    )[0]
+
+
 def write_holder_layout(hash: bytes, offset: int, data: bytes):
     assert (len(hash) == 32)
     return (
@@ -103,6 +126,69 @@ def make_ExecuteTrxFromInstruction(
     )
 
 
+def batch_layout(messages: tp.List[bytes]) -> bytes:
+    return b"".join(len(message).to_bytes(4, "little") + message for message in messages)
+
+
+def make_ExecuteTrxBatch(
+        operator: Keypair,
+        evm_loader: "EvmLoader",
+        treasury_address: PublicKey,
+        treasury_buffer: bytes,
+        messages: tp.List[bytes],
+        additional_accounts: tp.List[PublicKey],
+        system_program=sp.SYS_PROGRAM_ID,
+        evm_loader_public_key=PublicKey(EVM_LOADER)
+):
+    data = bytes.fromhex('31') + treasury_buffer + batch_layout(messages)
+    operator_ether = eth_keys.PrivateKey(operator.secret_key[:32]).public_key.to_canonical_address()
+    accounts = [
+        AccountMeta(pubkey=operator.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=treasury_address, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=PublicKey(evm_loader.ether2program(operator_ether)[0]), is_signer=False, is_writable=True),
+        AccountMeta(system_program, is_signer=False, is_writable=True),
+        AccountMeta(evm_loader_public_key, is_signer=False, is_writable=False),
+    ]
+    for acc in additional_accounts:
+        accounts.append(AccountMeta(acc, is_signer=False, is_writable=True), )
+
+    return TransactionInstruction(
+        program_id=PublicKey(EVM_LOADER),
+        data=data,
+        keys=accounts
+    )
+
+
+def make_ExecuteTrxBatchFromAccount(
+        operator: Keypair,
+        evm_loader: "EvmLoader",
+        holder_address: PublicKey,
+        treasury_address: PublicKey,
+        treasury_buffer: bytes,
+        additional_accounts: tp.List[PublicKey],
+        system_program=sp.SYS_PROGRAM_ID,
+        evm_loader_public_key=PublicKey(EVM_LOADER)
+):
+    data = bytes.fromhex('3c') + treasury_buffer
+    operator_ether = eth_keys.PrivateKey(operator.secret_key[:32]).public_key.to_canonical_address()
+    accounts = [
+        AccountMeta(pubkey=holder_address, is_signer=False, is_writable=False),
+        AccountMeta(pubkey=operator.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=treasury_address, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=PublicKey(evm_loader.ether2program(operator_ether)[0]), is_signer=False, is_writable=True),
+        AccountMeta(system_program, is_signer=False, is_writable=True),
+        AccountMeta(evm_loader_public_key, is_signer=False, is_writable=False),
+    ]
+    for acc in additional_accounts:
+        accounts.append(AccountMeta(acc, is_signer=False, is_writable=True), )
+
+    return TransactionInstruction(
+        program_id=PublicKey(EVM_LOADER),
+        data=data,
+        keys=accounts
+    )
+
+
 def make_ExecuteTrxFromAccountDataIterativeOrContinue(
         operator: Keypair,
         evm_loader: "EvmLoader",
@@ -216,3 +302,156 @@ def make_DepositV03(
     ]
 
     return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_OperatorRegistryInit(authority: PublicKey, upgrade_authority: PublicKey,
+                              payer: PublicKey) -> TransactionInstruction:
+    data = bytes.fromhex('32') + bytes(authority)
+
+    accounts = [
+        AccountMeta(pubkey=operator_registry_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=program_data_address(), is_signer=False, is_writable=False),
+        AccountMeta(pubkey=upgrade_authority, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=payer, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_OperatorRegistryAdd(operator: PublicKey, authority: PublicKey, payer: PublicKey) -> TransactionInstruction:
+    data = bytes.fromhex('33') + bytes(operator)
+
+    accounts = [
+        AccountMeta(pubkey=operator_registry_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=authority, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=payer, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_OperatorRegistryRemove(operator: PublicKey, authority: PublicKey) -> TransactionInstruction:
+    data = bytes.fromhex('34') + bytes(operator)
+
+    accounts = [
+        AccountMeta(pubkey=operator_registry_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=authority, is_signer=True, is_writable=True),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_OperatorRegistrySuspend(operator: PublicKey, authority: PublicKey, suspend: bool) -> TransactionInstruction:
+    data = bytes.fromhex('35') + bytes(operator) + int(suspend).to_bytes(1, "little")
+
+    accounts = [
+        AccountMeta(pubkey=operator_registry_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=authority, is_signer=True, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_ParametersInit(authority: PublicKey, upgrade_authority: PublicKey, payer: PublicKey) -> TransactionInstruction:
+    data = bytes.fromhex('36') + bytes(authority)
+
+    accounts = [
+        AccountMeta(pubkey=parameters_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=program_data_address(), is_signer=False, is_writable=False),
+        AccountMeta(pubkey=upgrade_authority, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=payer, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_ParametersUpdate(index: int, value: tp.Optional[int], authority: PublicKey) -> TransactionInstruction:
+    data = (
+            bytes.fromhex('37')
+            + index.to_bytes(1, "little")
+            + int(value is not None).to_bytes(1, "little")
+            + (value or 0).to_bytes(8, "little")
+    )
+
+    accounts = [
+        AccountMeta(pubkey=parameters_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=authority, is_signer=True, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_CollectTreasureBatch(chain_id: int, indices: tp.List[int], pools: tp.List[PublicKey]) -> TransactionInstruction:
+    data = bytes.fromhex('38') + chain_id.to_bytes(8, "little") + b"".join(
+        index.to_bytes(4, "little") for index in indices)
+
+    accounts = [
+        AccountMeta(pubkey=main_treasury_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+    for pool in pools:
+        accounts.append(AccountMeta(pubkey=pool, is_signer=False, is_writable=True))
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_DepositRatesInit(authority: PublicKey, upgrade_authority: PublicKey,
+                          payer: PublicKey) -> TransactionInstruction:
+    data = bytes.fromhex('39') + bytes(authority)
+
+    accounts = [
+        AccountMeta(pubkey=deposit_rates_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=program_data_address(), is_signer=False, is_writable=False),
+        AccountMeta(pubkey=upgrade_authority, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=payer, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_DepositRatesSet(mint: PublicKey, chain_id: int, numerator: int, denominator: int,
+                         authority: PublicKey, payer: PublicKey) -> TransactionInstruction:
+    data = (
+            bytes.fromhex('3a')
+            + bytes(mint)
+            + chain_id.to_bytes(8, "little")
+            + numerator.to_bytes(8, "little")
+            + denominator.to_bytes(8, "little")
+    )
+
+    accounts = [
+        AccountMeta(pubkey=deposit_rates_address(), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=authority, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=payer, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
+
+
+def make_DepositSplToken(
+        ether_address: bytes,
+        chain_id: int,
+        solana_account: PublicKey,
+        source: PublicKey,
+        pool: PublicKey,
+        token_program: PublicKey,
+        operator_pubkey: PublicKey,
+) -> TransactionInstruction:
+    data = bytes.fromhex('3b') + ether_address + chain_id.to_bytes(8, "little")
+
+    accounts = [
+        AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=solana_account, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=token_program, is_signer=False, is_writable=False),
+        AccountMeta(pubkey=operator_pubkey, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=sp.SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        AccountMeta(pubkey=deposit_rates_address(), is_signer=False, is_writable=False),
+    ]
+
+    return TransactionInstruction(program_id=PublicKey(EVM_LOADER), data=data, keys=accounts)
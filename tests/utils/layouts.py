@@ -1,4 +1,4 @@
-from construct import Bytes, Int8ul, Struct, Int64ul, Int32ul
+from construct import Array, Bytes, Int8ul, Struct, Int64ul, Int32ul, this
 
 STORAGE_ACCOUNT_INFO_LAYOUT = Struct(
     "tag" / Int8ul,
@@ -10,6 +10,8 @@ STORAGE_ACCOUNT_INFO_LAYOUT = Struct(
     "gas_used" / Bytes(32),
     "operator" / Bytes(32),
     "slot" / Int64ul,
+    "last_progress_slot" / Int64ul,
+    "chain_id" / Int64ul,
     "account_list_len" / Int64ul,
 )
 
@@ -40,6 +42,44 @@ ACCOUNT_INFO_LAYOUT = Struct(
 )
 
 
+OPERATOR_REGISTRY_LAYOUT = Struct(
+    "tag" / Int8ul,
+    "authority" / Bytes(32),
+    "bump_seed" / Int8ul,
+    "operators_count" / Int64ul,
+)
+
+OPERATOR_REGISTRY_ENTRY_LAYOUT = Struct(
+    "operator" / Bytes(32),
+    "status" / Int8ul,
+)
+
+PARAMETERS_LAYOUT = Struct(
+    "tag" / Int8ul,
+    "authority" / Bytes(32),
+    "bump_seed" / Int8ul,
+    "count" / Int64ul,
+    "overrides" / Array(this.count, Struct(
+        "is_set" / Int8ul,
+        "value" / Int64ul,
+    )),
+)
+
+DEPOSIT_RATES_LAYOUT = Struct(
+    "tag" / Int8ul,
+    "authority" / Bytes(32),
+    "bump_seed" / Int8ul,
+    "rates_count" / Int64ul,
+)
+
+DEPOSIT_RATE_LAYOUT = Struct(
+    "mint" / Bytes(32),
+    "chain_id" / Int64ul,
+    "numerator" / Int64ul,
+    "denominator" / Int64ul,
+)
+
+
 CREATE_ACCOUNT_LAYOUT = Struct(
     "ether" / Bytes(20),
 )